@@ -0,0 +1,75 @@
+use tauri::{AppHandle, State};
+use crate::{AppState, database::{Attachment, AttachmentParent}, services::AttachmentService};
+use super::dialog::{pick_file, save_file};
+
+/// Lets the user pick a file and stores it in the vault. Returns `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn add_attachment(
+    parent: AttachmentParent,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<Option<Attachment>, String> {
+    // Don't hold the database lock while the dialog is open
+    let Some(source) = pick_file(&app, "Add attachment").await else {
+        return Ok(None);
+    };
+
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AttachmentService::add_attachment(db_manager, parent, &source)
+        .await
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_attachments(parent: AttachmentParent, state: State<'_, AppState>) -> Result<Vec<Attachment>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AttachmentService::get_attachments(db_manager, parent)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Asks where to save an attachment and writes it there. Returns the saved path, or `None` if cancelled.
+#[tauri::command]
+pub async fn export_attachment(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<Option<String>, String> {
+    let file_name = {
+        let db_manager_opt = state.lock().await;
+        let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+        AttachmentService::get_attachment(db_manager, id)
+            .await
+            .map_err(|e| e.to_string())?
+            .file_name
+    };
+
+    let Some(destination) = save_file(&app, "Export attachment", &file_name).await else {
+        return Ok(None);
+    };
+
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AttachmentService::export_attachment(db_manager, id, &destination)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(destination.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+pub async fn verify_attachment(id: i64, state: State<'_, AppState>) -> Result<bool, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AttachmentService::verify_attachment(db_manager, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_attachment(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AttachmentService::delete_attachment(db_manager, id)
+        .await
+        .map_err(|e| e.to_string())
+}
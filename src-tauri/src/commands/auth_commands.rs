@@ -1,7 +1,7 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use crate::{AppState, services::AuthService, database::DatabaseManager};
+use crate::{AppState, services::{AuthService, MasterPasswordService}, database::{DatabaseManager, MasterPasswordPolicy}};
 
 pub type AuthState = Arc<Mutex<AuthService>>;
 
@@ -17,10 +17,17 @@ pub type AuthState = Arc<Mutex<AuthService>>;
 pub async fn initialize_database_with_path(
     password: String,
     vaultPath: String,
+    app: AppHandle,
     db_state: State<'_, AppState>
 ) -> Result<(), String> {
     println!("initialize_database_with_path called with path: {}", vaultPath);
     
+    // Opening an existing vault only needs the right password; creating one has to meet the policy
+    if !DatabaseManager::is_vault_file_valid(&vaultPath) {
+        MasterPasswordService::check(&password, &MasterPasswordPolicy::default())
+            .map_err(|e| e.to_string())?;
+    }
+    
     // Create the encrypted database with specified path
     let db_manager = DatabaseManager::new_with_encryption_and_path(&password, &vaultPath)
         .await
@@ -34,8 +41,10 @@ pub async fn initialize_database_with_path(
     // Update the app state with the new database manager
     let mut db_state_guard = db_state.lock().await;
     *db_state_guard = Some(db_manager);
+    drop(db_state_guard);
     
     println!("App state updated successfully");
+    super::expiry_commands::spawn_expiry_watch(app);
     Ok(())
 }
 
@@ -44,6 +53,9 @@ pub async fn create_new_vault(
     password: String,
     db_state: State<'_, AppState>
 ) -> Result<String, String> {
+    MasterPasswordService::check(&password, &MasterPasswordPolicy::default())
+        .map_err(|e| e.to_string())?;
+    
     // Create a new vault with timestamp
     let vault_path = DatabaseManager::create_vault_with_timestamp()
         .map_err(|e| e.to_string())?;
@@ -60,6 +72,34 @@ pub async fn create_new_vault(
     Ok(vault_path.to_string_lossy().to_string())
 }
 
+/// Returns whether the vault is still unlocked. After a successful change it is locked only
+/// if the re-keyed vault couldn't be reopened, and then unlocks with the new password.
+#[tauri::command]
+pub async fn change_master_password(
+    current_password: String,
+    new_password: String,
+    db_state: State<'_, AppState>
+) -> Result<bool, String> {
+    let mut db_state_guard = db_state.lock().await;
+    let db_manager = db_state_guard.as_ref().ok_or("Database not initialized")?;
+    
+    let rekeyed = MasterPasswordService::change_master_password(db_manager, &current_password, &new_password)
+        .await
+        .map_err(|e| e.to_string())?;
+    let still_unlocked = rekeyed.is_some();
+    
+    // Connections from the old manager still carry the old key
+    let old_manager = match rekeyed {
+        Some(rekeyed) => db_state_guard.replace(rekeyed),
+        None => db_state_guard.take(),
+    };
+    if let Some(old_manager) = old_manager {
+        old_manager.disconnect();
+    }
+    
+    Ok(still_unlocked)
+}
+
 #[tauri::command]
 pub async fn get_vault_directory() -> Result<String, String> {
     DatabaseManager::get_vault_directory()
@@ -0,0 +1,24 @@
+use tauri::State;
+use crate::{AppState, database::{AutofillCandidate, PhishingWarning}, services::{AutofillService, LookalikeService}};
+
+#[tauri::command]
+pub async fn get_autofill_candidates(
+    url: String,
+    field_names: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AutofillCandidate>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    AutofillService::get_candidates(db_manager, &url, &field_names.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_url_lookalikes(url: String, state: State<'_, AppState>) -> Result<Vec<PhishingWarning>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    LookalikeService::check_url(db_manager, &url)
+        .await
+        .map_err(|e| e.to_string())
+}
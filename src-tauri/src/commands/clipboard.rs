@@ -0,0 +1,23 @@
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Copies a secret to the clipboard and, when asked, clears it again after the given delay.
+/// The clipboard is only cleared if it still holds the copied value.
+pub(crate) fn copy_secret<R: Runtime>(app: &AppHandle<R>, value: String, clear_after_seconds: Option<u64>) -> Result<(), String> {
+    app.clipboard()
+        .write_text(value.as_str())
+        .map_err(|e| e.to_string())?;
+
+    if let Some(seconds) = clear_after_seconds.filter(|s| *s > 0) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(seconds)).await;
+            if app.clipboard().read_text().map(|current| current == value).unwrap_or(false) {
+                let _ = app.clipboard().write_text("");
+            }
+        });
+    }
+
+    Ok(())
+}
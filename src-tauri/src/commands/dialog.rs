@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_dialog::DialogExt;
+use tokio::sync::oneshot;
+
+/// Shows the native "open file" dialog. Returns `None` if the user cancelled.
+pub(crate) async fn pick_file<R: Runtime>(app: &AppHandle<R>, title: &str) -> Option<PathBuf> {
+    let (tx, rx) = oneshot::channel();
+    app.dialog().file().set_title(title).pick_file(move |path| {
+        let _ = tx.send(path);
+    });
+
+    rx.await.ok().flatten().and_then(|path| path.into_path().ok())
+}
+
+/// Shows the native folder picker. Returns `None` if the user cancelled.
+pub(crate) async fn pick_folder<R: Runtime>(app: &AppHandle<R>, title: &str) -> Option<PathBuf> {
+    let (tx, rx) = oneshot::channel();
+    app.dialog().file().set_title(title).pick_folder(move |path| {
+        let _ = tx.send(path);
+    });
+
+    rx.await.ok().flatten().and_then(|path| path.into_path().ok())
+}
+
+/// Shows the native "save file" dialog with a suggested file name. Returns `None` if the user cancelled.
+pub(crate) async fn save_file<R: Runtime>(app: &AppHandle<R>, title: &str, file_name: &str) -> Option<PathBuf> {
+    let (tx, rx) = oneshot::channel();
+    app.dialog().file().set_title(title).set_file_name(file_name).save_file(move |path| {
+        let _ = tx.send(path);
+    });
+
+    rx.await.ok().flatten().and_then(|path| path.into_path().ok())
+}
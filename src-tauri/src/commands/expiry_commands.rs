@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use crate::{AppState, database::{ExpiringEntry, FolderRotation}, services::ExpiryService};
+
+/// Emitted with the entries that fall due within the vault's warning window.
+const ENTRIES_EXPIRING_EVENT: &str = "entries-expiring";
+/// Emitted with the entries whose due date has passed.
+const ENTRIES_EXPIRED_EVENT: &str = "entries-expired";
+
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Bumped whenever a vault is unlocked, so the previous vault's watch stops.
+static EXPIRY_WATCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[tauri::command]
+pub async fn list_expiring_entries(
+    within_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ExpiringEntry>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    ExpiryService::list_expiring_entries(db_manager, within_days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_expired_entries(state: State<'_, AppState>) -> Result<Vec<ExpiringEntry>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    ExpiryService::list_expired_entries(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_folder_rotations(state: State<'_, AppState>) -> Result<Vec<FolderRotation>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    ExpiryService::get_folder_rotations(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// `rotation_days: None` clears the folder's interval.
+#[tauri::command]
+pub async fn set_folder_rotation(
+    folder: String,
+    rotation_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    ExpiryService::set_folder_rotation(db_manager, &folder, rotation_days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Checks for expiring and expired entries right after unlock and then every few hours,
+/// emitting an event for each non-empty list. Stops once the vault is locked or another is unlocked.
+pub(crate) fn spawn_expiry_watch(app: AppHandle) {
+    let generation = EXPIRY_WATCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if EXPIRY_WATCH_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let state = app.state::<AppState>();
+            let due = {
+                let db_manager_opt = state.lock().await;
+                let Some(db_manager) = db_manager_opt.as_ref() else {
+                    return;
+                };
+                let expiring = ExpiryService::list_expiring_entries(db_manager, None).await;
+                let expired = ExpiryService::list_expired_entries(db_manager).await;
+                expiring.and_then(|expiring| expired.map(|expired| (expiring, expired)))
+            };
+
+            match due {
+                Ok((expiring, expired)) => {
+                    if !expiring.is_empty() {
+                        let _ = app.emit(ENTRIES_EXPIRING_EVENT, expiring);
+                    }
+                    if !expired.is_empty() {
+                        let _ = app.emit(ENTRIES_EXPIRED_EVENT, expired);
+                    }
+                }
+                Err(e) => eprintln!("Expiry check failed: {}", e),
+            }
+
+            tokio::time::sleep(EXPIRY_CHECK_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,36 @@
+use tauri::AppHandle;
+use crate::{database::{GeneratedPassword, GeneratorOptions, PassphraseOptions, PasswordStrength, WordlistInfo}, services::{GeneratorService, StrengthService}};
+use super::dialog::pick_file;
+
+#[tauri::command]
+pub fn generate_password(options: Option<GeneratorOptions>) -> Result<GeneratedPassword, String> {
+    GeneratorService::generate_password(&options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn generate_passphrase(options: Option<PassphraseOptions>) -> Result<GeneratedPassword, String> {
+    GeneratorService::generate_passphrase(&options.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lets the user choose a custom wordlist file and checks it. Returns `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn pick_wordlist(app: AppHandle) -> Result<Option<WordlistInfo>, String> {
+    let Some(source) = pick_file(&app, "Choose a wordlist").await else {
+        return Ok(None);
+    };
+    GeneratorService::load_wordlist(&source)
+        .await
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// `user_inputs` are details the password shouldn't contain, such as the site and username.
+/// The estimate runs on a blocking thread, since long passwords take a while to match.
+#[tauri::command]
+pub async fn estimate_password_strength(password: String, user_inputs: Option<Vec<String>>) -> Result<PasswordStrength, String> {
+    tauri::async_runtime::spawn_blocking(move || StrengthService::estimate(&password, &user_inputs.unwrap_or_default()))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,90 @@
+use tauri::{AppHandle, Emitter, State};
+use crate::{
+    AppState,
+    database::{BreachedEntry, HealthExportFormat, PwnedIndexInfo, VaultHealthReport},
+    services::{export_health_report, health_export_file_name, BreachService, HealthService, DEFAULT_MAX_PASSWORD_AGE_DAYS},
+};
+use super::dialog::{pick_file, pick_folder, save_file};
+
+/// Emitted while a dump is imported, with the number of hashes read so far.
+const PWNED_IMPORT_PROGRESS_EVENT: &str = "pwned-import-progress";
+
+#[tauri::command]
+pub async fn vault_health_report(
+    max_age_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<VaultHealthReport, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    HealthService::vault_health_report(db_manager, max_age_days.unwrap_or(DEFAULT_MAX_PASSWORD_AGE_DAYS))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Audits the vault and writes the findings, without any secrets, to an HTML or CSV file
+/// chosen by the user. Returns the saved path, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_vault_health_report(
+    format: HealthExportFormat,
+    max_age_days: Option<u32>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let data = {
+        let db_manager_opt = state.lock().await;
+        let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+        let report = HealthService::vault_health_report(db_manager, max_age_days.unwrap_or(DEFAULT_MAX_PASSWORD_AGE_DAYS))
+            .await
+            .map_err(|e| e.to_string())?;
+        export_health_report(&report, format)
+    };
+
+    let Some(destination) = save_file(&app, "Export health report", health_export_file_name(format)).await else {
+        return Ok(None);
+    };
+    tokio::fs::write(&destination, data).await.map_err(|e| e.to_string())?;
+
+    Ok(Some(destination.to_string_lossy().to_string()))
+}
+
+/// Lets the user choose a downloaded Have I Been Pwned dump, a folder of range files when
+/// `from_folder` is set or else the single ordered-by-hash file, and builds the local index
+/// from it. Returns `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn import_pwned_passwords(app: AppHandle, from_folder: Option<bool>) -> Result<Option<PwnedIndexInfo>, String> {
+    let source = if from_folder.unwrap_or(false) {
+        pick_folder(&app, "Choose the folder of Pwned Passwords range files").await
+    } else {
+        pick_file(&app, "Choose the Pwned Passwords file").await
+    };
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    BreachService::import_dump(&source, |hashes| {
+        let _ = app.emit(PWNED_IMPORT_PROGRESS_EVENT, hashes);
+    })
+    .await
+    .map(Some)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_pwned_index_info() -> Result<Option<PwnedIndexInfo>, String> {
+    BreachService::index_info().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_breached_passwords(state: State<'_, AppState>) -> Result<Vec<BreachedEntry>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    BreachService::check_breached_passwords(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// For checking a password before it is saved.
+#[tauri::command]
+pub async fn is_password_breached(password: String) -> Result<bool, String> {
+    BreachService::is_password_breached(&password).await.map_err(|e| e.to_string())
+}
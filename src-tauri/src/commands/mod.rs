@@ -1,7 +1,33 @@
+mod clipboard;
+mod dialog;
 pub mod password_commands;
 pub mod notes_commands;
 pub mod auth_commands;
+pub mod settings_commands;
+pub mod attachment_commands;
+pub mod template_commands;
+pub mod autofill_commands;
+pub mod search_commands;
+pub mod generator_commands;
+pub mod health_commands;
+pub mod expiry_commands;
+pub mod rotation_commands;
+pub mod usage_commands;
+pub mod recovery_code_commands;
+pub mod passkey_commands;
 
 pub use password_commands::*;
 pub use notes_commands::*;
 pub use auth_commands::*;
+pub use settings_commands::*;
+pub use attachment_commands::*;
+pub use template_commands::*;
+pub use autofill_commands::*;
+pub use search_commands::*;
+pub use generator_commands::*;
+pub use health_commands::*;
+pub use expiry_commands::*;
+pub use rotation_commands::*;
+pub use usage_commands::*;
+pub use recovery_code_commands::*;
+pub use passkey_commands::*;
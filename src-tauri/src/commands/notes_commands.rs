@@ -1,11 +1,11 @@
 use tauri::State;
-use crate::{AppState, database::Note, services::NotesService};
+use crate::{AppState, database::{Note, NoteListOptions, NoteRevision, NoteRevisionDiff}, services::NotesService};
 
 #[tauri::command]
-pub async fn get_notes(state: State<'_, AppState>) -> Result<Vec<Note>, String> {
+pub async fn get_notes(options: Option<NoteListOptions>, state: State<'_, AppState>) -> Result<Vec<Note>, String> {
     let db_manager_opt = state.lock().await;
     let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
-    NotesService::get_notes(db_manager)
+    NotesService::get_notes(db_manager, options.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -36,3 +36,35 @@ pub async fn delete_note(id: i64, state: State<'_, AppState>) -> Result<(), Stri
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn list_note_revisions(note_id: i64, state: State<'_, AppState>) -> Result<Vec<NoteRevision>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    NotesService::get_note_revisions(db_manager, note_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn diff_note_revisions(
+    note_id: i64,
+    from_revision: i64,
+    to_revision: i64,
+    state: State<'_, AppState>
+) -> Result<NoteRevisionDiff, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    NotesService::diff_note_revisions(db_manager, note_id, from_revision, to_revision)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn restore_note_revision(note_id: i64, revision: i64, state: State<'_, AppState>) -> Result<i64, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    NotesService::restore_note_revision(db_manager, note_id, revision)
+        .await
+        .map_err(|e| e.to_string())
+}
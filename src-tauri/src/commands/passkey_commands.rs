@@ -0,0 +1,31 @@
+use tauri::State;
+use crate::{AppState, database::{PasskeyAssertion, PasskeyAssertionRequest, PasskeyCreationOptions, PasskeyRegistration, PasskeySummary}, services::PasskeyService};
+
+/// Answers a site's `navigator.credentials.create()` with a new passkey saved in the vault.
+#[tauri::command]
+pub async fn create_passkey(options: PasskeyCreationOptions, state: State<'_, AppState>) -> Result<PasskeyRegistration, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasskeyService::create_passkey(db_manager, options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_passkeys(rp_id: Option<String>, state: State<'_, AppState>) -> Result<Vec<PasskeySummary>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasskeyService::list_passkeys(db_manager, rp_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Answers a site's `navigator.credentials.get()` by signing with a saved passkey.
+#[tauri::command]
+pub async fn get_passkey_assertion(request: PasskeyAssertionRequest, state: State<'_, AppState>) -> Result<PasskeyAssertion, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasskeyService::get_assertion(db_manager, request)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,11 +1,30 @@
-use tauri::State;
-use crate::{AppState, database::PasswordEntry, services::PasswordService};
+use tauri::{AppHandle, State};
+use crate::{AppState, database::{ItemKind, ListOptions, Page, PasswordEntry, PasswordHistoryEntry, PasswordSummary, VaultQueryResults}, services::{PasswordService, SearchService}};
+use super::clipboard::copy_secret;
 
 #[tauri::command]
-pub async fn get_passwords(state: State<'_, AppState>) -> Result<Vec<PasswordEntry>, String> {
+pub async fn get_passwords(kind: Option<ItemKind>, state: State<'_, AppState>) -> Result<Vec<PasswordEntry>, String> {
     let db_manager_opt = state.lock().await;
     let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
-    PasswordService::get_passwords(db_manager)
+    PasswordService::get_passwords(db_manager, kind)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_passwords(options: Option<ListOptions>, state: State<'_, AppState>) -> Result<Page<PasswordSummary>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::list_passwords(db_manager, options.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reveal_password(id: i64, state: State<'_, AppState>) -> Result<String, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::reveal_password(db_manager, id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -38,10 +57,85 @@ pub async fn delete_password(id: i64, state: State<'_, AppState>) -> Result<(),
 }
 
 #[tauri::command]
-pub async fn search_passwords(query: String, state: State<'_, AppState>) -> Result<Vec<PasswordEntry>, String> {
+pub async fn mark_compromised(id: i64, reason: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::mark_compromised(db_manager, id, reason)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn clear_compromised(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::clear_compromised(db_manager, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_compromised(state: State<'_, AppState>) -> Result<Vec<PasswordSummary>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::list_compromised(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn search_passwords(
+    query: String,
+    kind: Option<ItemKind>,
+    fuzzy: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PasswordEntry>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::search_passwords(db_manager, &query, kind, fuzzy.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn query_vault(query: String, state: State<'_, AppState>) -> Result<VaultQueryResults, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    SearchService::query_vault(db_manager, &query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_password_history(entry_id: i64, state: State<'_, AppState>) -> Result<Vec<PasswordHistoryEntry>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    PasswordService::get_password_history(db_manager, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn copy_password_history(
+    history_id: i64,
+    clear_after_seconds: Option<u64>,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    let value = PasswordService::get_password_history_value(db_manager, history_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    copy_secret(&app, value, clear_after_seconds)
+}
+
+#[tauri::command]
+pub async fn find_entries_for_url(url: String, state: State<'_, AppState>) -> Result<Vec<PasswordEntry>, String> {
     let db_manager_opt = state.lock().await;
     let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
-    PasswordService::search_passwords(db_manager, &query)
+    PasswordService::find_entries_for_url(db_manager, &url)
         .await
         .map_err(|e| e.to_string())
 }
@@ -0,0 +1,45 @@
+use tauri::State;
+use crate::{AppState, database::RecoveryCodeStatus, services::{recovery_codes, RecoveryCodeService}};
+
+/// Marks a code as used and returns how many are left.
+#[tauri::command]
+pub async fn consume_recovery_code(item_id: i64, code: String, state: State<'_, AppState>) -> Result<RecoveryCodeStatus, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RecoveryCodeService::consume_recovery_code(db_manager, item_id, &code)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_recovery_code_status(item_id: i64, state: State<'_, AppState>) -> Result<RecoveryCodeStatus, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RecoveryCodeService::recovery_code_status(db_manager, item_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_recovery_codes_for_entry(entry_id: i64, state: State<'_, AppState>) -> Result<Vec<RecoveryCodeStatus>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RecoveryCodeService::recovery_codes_for_entry(db_manager, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_low_recovery_codes(state: State<'_, AppState>) -> Result<Vec<RecoveryCodeStatus>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RecoveryCodeService::list_low_recovery_codes(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Pulls the codes out of text pasted from a provider, ready to save in a recovery-codes item.
+#[tauri::command]
+pub fn parse_recovery_codes(text: String) -> Vec<String> {
+    recovery_codes::parse_recovery_codes(&text)
+}
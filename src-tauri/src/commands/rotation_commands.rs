@@ -0,0 +1,85 @@
+use tauri::State;
+use crate::{
+    AppState,
+    database::{GeneratorOptions, RotationSession, RotationSessionSummary},
+    services::RotationService,
+};
+
+/// Starts a rotation session over the given logins, generating new passwords under
+/// `options` (the generator defaults when omitted).
+#[tauri::command]
+pub async fn start_rotation_session(
+    entry_ids: Vec<i64>,
+    options: Option<GeneratorOptions>,
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RotationSession, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::start_session(db_manager, &entry_ids, options.unwrap_or_default(), name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_rotation_sessions(state: State<'_, AppState>) -> Result<Vec<RotationSessionSummary>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::list_sessions(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_rotation_session(session_id: i64, state: State<'_, AppState>) -> Result<RotationSession, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::get_session(db_manager, session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reveal_pending_password(session_id: i64, entry_id: i64, state: State<'_, AppState>) -> Result<String, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::reveal_pending_password(db_manager, session_id, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn regenerate_pending_password(session_id: i64, entry_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::regenerate_pending_password(db_manager, session_id, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn confirm_rotation(session_id: i64, entry_id: i64, state: State<'_, AppState>) -> Result<RotationSession, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::confirm_rotation(db_manager, session_id, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn roll_back_rotation(session_id: i64, entry_id: i64, state: State<'_, AppState>) -> Result<RotationSession, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::roll_back_rotation(db_manager, session_id, entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_rotation_session(session_id: i64, state: State<'_, AppState>) -> Result<RotationSession, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    RotationService::cancel_session(db_manager, session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,16 @@
+use tauri::State;
+use crate::{AppState, database::SearchHit, services::SearchService};
+
+#[tauri::command]
+pub async fn search_vault(
+    query: String,
+    limit: Option<u32>,
+    fuzzy: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    SearchService::search_vault(db_manager, &query, limit, fuzzy.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,20 @@
+use tauri::State;
+use crate::{AppState, database::VaultSettings, services::SettingsService};
+
+#[tauri::command]
+pub async fn get_vault_settings(state: State<'_, AppState>) -> Result<VaultSettings, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    SettingsService::get_settings(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_vault_settings(settings: VaultSettings, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    SettingsService::update_settings(db_manager, settings)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,88 @@
+use tauri::{AppHandle, State};
+use crate::{AppState, database::{EntryTemplate, PasswordEntry, TemplateImportSummary}, services::TemplateService};
+use super::dialog::{pick_file, save_file};
+
+#[tauri::command]
+pub async fn get_templates(state: State<'_, AppState>) -> Result<Vec<EntryTemplate>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::get_templates(db_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_template(template: EntryTemplate, state: State<'_, AppState>) -> Result<i64, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::create_template(db_manager, template)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_template(id: i64, template: EntryTemplate, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::update_template(db_manager, id, template)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_template(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::delete_template(db_manager, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn new_entry_from_template(template_id: i64, state: State<'_, AppState>) -> Result<PasswordEntry, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::new_entry_from_template(db_manager, template_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes the selected templates (or all of them) to a file chosen by the user.
+/// Returns the saved path, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_templates(
+    ids: Option<Vec<i64>>,
+    app: AppHandle,
+    state: State<'_, AppState>
+) -> Result<Option<String>, String> {
+    let data = {
+        let db_manager_opt = state.lock().await;
+        let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+        TemplateService::export_templates(db_manager, ids)
+            .await
+            .map_err(|e| e.to_string())?
+    };
+
+    let Some(destination) = save_file(&app, "Export templates", "templates.json").await else {
+        return Ok(None);
+    };
+    tokio::fs::write(&destination, data).await.map_err(|e| e.to_string())?;
+
+    Ok(Some(destination.to_string_lossy().to_string()))
+}
+
+/// Imports templates from a file chosen by the user. Returns `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn import_templates(app: AppHandle, state: State<'_, AppState>) -> Result<Option<TemplateImportSummary>, String> {
+    let Some(source) = pick_file(&app, "Import templates").await else {
+        return Ok(None);
+    };
+    let data = tokio::fs::read_to_string(&source).await.map_err(|e| e.to_string())?;
+
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    TemplateService::import_templates(db_manager, &data)
+        .await
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,20 @@
+use tauri::State;
+use crate::{AppState, database::{SearchHitKind, UsedItem}, services::UsageService};
+
+#[tauri::command]
+pub async fn record_usage(kind: SearchHitKind, id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    UsageService::record_usage(db_manager, kind, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn frequently_used(limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<UsedItem>, String> {
+    let db_manager_opt = state.lock().await;
+    let db_manager = db_manager_opt.as_ref().ok_or("Database not initialized")?;
+    UsageService::frequently_used(db_manager, limit)
+        .await
+        .map_err(|e| e.to_string())
+}
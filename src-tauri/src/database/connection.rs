@@ -1,5 +1,5 @@
-use libsql::{Connection, Database, Builder, EncryptionConfig, Cipher};
-use std::path::PathBuf;
+use libsql::{Connection, Database, Builder, EncryptionConfig, Cipher, OpenFlags};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,18 +10,40 @@ pub enum DatabaseError {
     Migration(String),
     #[error("Query error: {0}")]
     Query(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Validation error: {0}")]
+    Validation(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("Invalid master password")]
     InvalidMasterPassword,
     #[error("Master password not set")]
     MasterPasswordNotSet,
+    #[error(transparent)]
+    PasswordPolicy(#[from] PasswordPolicyError),
+}
+
+/// Why a proposed master password was rejected by the vault's policy.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PasswordPolicyError {
+    #[error("Master password cannot be empty")]
+    Empty,
+    #[error("Master password is too weak: about {estimated_bits} bits, at least {required_bits} required")]
+    TooWeak { estimated_bits: u32, required_bits: u32 },
+    #[error("Master password is on the list of commonly used passwords")]
+    Common,
+    #[error("Master password is already used by {count} of this vault's entries")]
+    ReusedByEntries { count: usize },
+    #[error("New master password must be different from the current one")]
+    Unchanged,
 }
 
 pub type Result<T> = std::result::Result<T, DatabaseError>;
 
 pub struct DatabaseManager {
     db: Database,
+    path: PathBuf,
 }
 
 impl DatabaseManager {
@@ -50,7 +72,7 @@ impl DatabaseManager {
                 DatabaseError::Connection(e)
             })?;
 
-        let manager = Self { db };
+        let manager = Self { db, path: db_path };
         
         // Run migrations on initialization
         manager.run_migrations().await?;
@@ -59,6 +81,41 @@ impl DatabaseManager {
     }
 
 
+    /// Whether `master_password` unlocks the vault at `vault_path`. The vault is opened read-only
+    /// just long enough to read its schema; no migrations run and nothing stays open.
+    pub async fn verify_master_password(master_password: &str, vault_path: &str) -> bool {
+        let encryption_config = EncryptionConfig::new(Cipher::Aes256Cbc, master_password.as_bytes().to_vec().into());
+        let Ok(db) = Builder::new_local(vault_path)
+            .flags(OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .encryption_config(encryption_config)
+            .build()
+            .await
+        else {
+            return false;
+        };
+        let Ok(conn) = db.connect() else {
+            return false;
+        };
+        match conn.query("SELECT count(*) FROM sqlite_master", ()).await {
+            Ok(mut rows) => rows.next().await.is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// The vault file this manager has open.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Re-encrypts the vault under a new master password. This manager keeps using the
+    /// old key for new connections, so the vault has to be reopened with the new one.
+    pub async fn rekey(&self, new_password: &str) -> Result<()> {
+        let conn = self.get_connection().await?;
+        let pragma = format!("PRAGMA rekey = '{}'", new_password.replace('\'', "''"));
+        let mut rows = conn.query(&pragma, ()).await?;
+        while rows.next().await?.is_some() {}
+        Ok(())
+    }
 
     pub fn is_vault_file_valid(vault_path: &str) -> bool {
         PathBuf::from(vault_path).exists()
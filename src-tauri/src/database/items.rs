@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON layout stored in `passwords.item_data`.
+/// Bump it (and teach `ItemDetails` to read the old layout) whenever a details struct changes shape.
+pub const ITEM_DATA_VERSION: i64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    #[default]
+    Login,
+    PaymentCard,
+    Identity,
+    SshKey,
+    ApiCredential,
+    WifiNetwork,
+    SoftwareLicense,
+    RecoveryCodes,
+    Passkey,
+}
+
+impl ItemKind {
+    pub const ALL: [ItemKind; 9] = [
+        ItemKind::Login,
+        ItemKind::PaymentCard,
+        ItemKind::Identity,
+        ItemKind::SshKey,
+        ItemKind::ApiCredential,
+        ItemKind::WifiNetwork,
+        ItemKind::SoftwareLicense,
+        ItemKind::RecoveryCodes,
+        ItemKind::Passkey,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemKind::Login => "login",
+            ItemKind::PaymentCard => "payment_card",
+            ItemKind::Identity => "identity",
+            ItemKind::SshKey => "ssh_key",
+            ItemKind::ApiCredential => "api_credential",
+            ItemKind::WifiNetwork => "wifi_network",
+            ItemKind::SoftwareLicense => "software_license",
+            ItemKind::RecoveryCodes => "recovery_codes",
+            ItemKind::Passkey => "passkey",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// Structured fields for entries that aren't plain website logins.
+/// For these items `PasswordEntry.website` holds the display title.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ItemDetails {
+    PaymentCard(PaymentCard),
+    Identity(Identity),
+    SshKey(SshKey),
+    ApiCredential(ApiCredential),
+    WifiNetwork(WifiNetwork),
+    SoftwareLicense(SoftwareLicense),
+    RecoveryCodes(RecoveryCodes),
+    Passkey(Passkey),
+}
+
+impl ItemDetails {
+    pub fn kind(&self) -> ItemKind {
+        match self {
+            ItemDetails::PaymentCard(_) => ItemKind::PaymentCard,
+            ItemDetails::Identity(_) => ItemKind::Identity,
+            ItemDetails::SshKey(_) => ItemKind::SshKey,
+            ItemDetails::ApiCredential(_) => ItemKind::ApiCredential,
+            ItemDetails::WifiNetwork(_) => ItemKind::WifiNetwork,
+            ItemDetails::SoftwareLicense(_) => ItemKind::SoftwareLicense,
+            ItemDetails::RecoveryCodes(_) => ItemKind::RecoveryCodes,
+            ItemDetails::Passkey(_) => ItemKind::Passkey,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentCard {
+    pub cardholder_name: String,
+    pub number: String,
+    #[serde(default)]
+    pub brand: Option<String>, // derived from the number on save
+    pub expiry_month: u8,
+    pub expiry_year: u16,
+    #[serde(default)]
+    pub security_code: Option<String>,
+    #[serde(default)]
+    pub pin: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Identity {
+    pub title: Option<String>,
+    pub first_name: String,
+    pub middle_name: Option<String>,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub date_of_birth: Option<String>, // YYYY-MM-DD
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+    pub passport_number: Option<String>,
+    pub national_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshKey {
+    #[serde(default)]
+    pub private_key: Option<String>,
+    #[serde(default)]
+    pub public_key: Option<String>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+    // Derived from the public key on save
+    #[serde(default)]
+    pub key_type: Option<String>,
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiCredential {
+    #[serde(default)]
+    pub key_id: Option<String>,
+    pub secret: String,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub expires_on: Option<String>, // YYYY-MM-DD
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WifiSecurity {
+    Open,
+    Wep,
+    WpaPersonal,
+    Wpa2Personal,
+    Wpa3Personal,
+    Enterprise,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub security: WifiSecurity,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoftwareLicense {
+    pub product: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    pub license_key: String,
+    #[serde(default)]
+    pub licensed_to: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub purchase_date: Option<String>, // YYYY-MM-DD
+    #[serde(default)]
+    pub expires_on: Option<String>, // YYYY-MM-DD
+    #[serde(default)]
+    pub seats: Option<u32>,
+}
+
+/// Two-factor backup codes for the account of a login entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCodes {
+    #[serde(default)]
+    pub entry_id: Option<i64>, // the login the codes belong to
+    pub codes: Vec<RecoveryCode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCode {
+    pub code: String,
+    #[serde(default)]
+    pub used: bool,
+    #[serde(default)]
+    pub used_at: Option<String>, // UTC, "YYYY-MM-DD HH:MM:SS"
+}
+
+/// A WebAuthn credential held by the vault's software authenticator.
+/// Binary values are base64url without padding, as WebAuthn clients exchange them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Passkey {
+    pub credential_id: String,
+    pub rp_id: String,
+    #[serde(default)]
+    pub rp_name: Option<String>,
+    pub user_handle: String,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub user_display_name: Option<String>,
+    pub algorithm: PasskeyAlgorithm,
+    pub private_key: String, // PKCS#8
+    #[serde(default)]
+    pub sign_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasskeyAlgorithm {
+    Es256,
+    #[serde(rename = "eddsa")]
+    EdDsa,
+}
+
+impl PasskeyAlgorithm {
+    /// The algorithm's COSE identifier, as listed in `pubKeyCredParams`.
+    pub fn cose_id(&self) -> i64 {
+        match self {
+            PasskeyAlgorithm::Es256 => -7,
+            PasskeyAlgorithm::EdDsa => -8,
+        }
+    }
+
+    pub fn from_cose_id(id: i64) -> Option<Self> {
+        match id {
+            -7 => Some(PasskeyAlgorithm::Es256),
+            -8 => Some(PasskeyAlgorithm::EdDsa),
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,17 @@
 use libsql::Connection;
 use crate::database::{DatabaseError, Result};
 
+/// Existing websites are split into URIs right after this statement creates `entry_uris`.
+const CREATE_ENTRY_URIS: &str = r#"
+    CREATE TABLE IF NOT EXISTS entry_uris (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        entry_id INTEGER NOT NULL,
+        position INTEGER NOT NULL DEFAULT 0,
+        uri TEXT NOT NULL,
+        match_mode TEXT NOT NULL DEFAULT 'base_domain'
+    );
+    "#;
+
 const MIGRATIONS: &[&str] = &[
     // Migration 1: Create initial tables
     r#"
@@ -46,6 +57,323 @@ const MIGRATIONS: &[&str] = &[
         applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
     );
     "#,
+    // Migration 4: Password history
+    r#"
+    CREATE TABLE IF NOT EXISTS password_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        entry_id INTEGER NOT NULL,
+        field_name TEXT NOT NULL DEFAULT 'password',
+        value TEXT NOT NULL,
+        changed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_password_history_entry ON password_history(entry_id, changed_at);
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_delete_history
+    AFTER DELETE ON passwords
+    BEGIN
+        DELETE FROM password_history WHERE entry_id = OLD.id;
+    END;
+    "#,
+    // Migration 5: Note revisions
+    r#"
+    CREATE TABLE IF NOT EXISTS note_revisions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        note_id INTEGER NOT NULL,
+        revision INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE(note_id, revision)
+    );
+    "#,
+    r#"
+    INSERT INTO note_revisions (note_id, revision, title, content, created_at)
+    SELECT id, 1, title, content, COALESCE(updated_at, created_at, CURRENT_TIMESTAMP) FROM notes;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_notes_delete_revisions
+    AFTER DELETE ON notes
+    BEGIN
+        DELETE FROM note_revisions WHERE note_id = OLD.id;
+    END;
+    "#,
+    // Migration 6: Attachments, stored as ordered chunks so files can be streamed in and out
+    r#"
+    CREATE TABLE IF NOT EXISTS attachments (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        entry_id INTEGER,
+        note_id INTEGER,
+        file_name TEXT NOT NULL,
+        mime_type TEXT NOT NULL,
+        size INTEGER NOT NULL DEFAULT 0,
+        sha256 TEXT NOT NULL DEFAULT '',
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        CHECK ((entry_id IS NULL) <> (note_id IS NULL))
+    );
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS attachment_chunks (
+        attachment_id INTEGER NOT NULL,
+        seq INTEGER NOT NULL,
+        data BLOB NOT NULL,
+        PRIMARY KEY (attachment_id, seq)
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_attachments_entry ON attachments(entry_id);
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_attachments_note ON attachments(note_id);
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_delete_attachments
+    AFTER DELETE ON passwords
+    BEGIN
+        DELETE FROM attachments WHERE entry_id = OLD.id;
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_notes_delete_attachments
+    AFTER DELETE ON notes
+    BEGIN
+        DELETE FROM attachments WHERE note_id = OLD.id;
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_attachments_delete_chunks
+    AFTER DELETE ON attachments
+    BEGIN
+        DELETE FROM attachment_chunks WHERE attachment_id = OLD.id;
+    END;
+    "#,
+    // Migration 7: Typed item kinds
+    r#"
+    ALTER TABLE passwords ADD COLUMN item_type TEXT NOT NULL DEFAULT 'login';
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN item_data TEXT;
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN item_data_version INTEGER;
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_passwords_item_type ON passwords(item_type);
+    "#,
+    // Migration 8: Entry templates and custom fields
+    r#"
+    CREATE TABLE IF NOT EXISTS entry_templates (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        description TEXT,
+        fields TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN template_id INTEGER;
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN custom_fields TEXT;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_entry_templates_delete_detach
+    AFTER DELETE ON entry_templates
+    BEGIN
+        UPDATE passwords SET template_id = NULL WHERE template_id = OLD.id;
+    END;
+    "#,
+    // Migration 9: Multiple URIs per entry (existing `website` values are split by `split_website_uris`)
+    CREATE_ENTRY_URIS,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_entry_uris_entry ON entry_uris(entry_id, position);
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_delete_uris
+    AFTER DELETE ON passwords
+    BEGIN
+        DELETE FROM entry_uris WHERE entry_id = OLD.id;
+    END;
+    "#,
+    // Migration 10: Full-text search over entries and notes (external-content FTS5 tables kept in sync by triggers).
+    // Secrets (passwords, item details, custom fields) are deliberately not indexed.
+    r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS passwords_fts USING fts5(
+        website, username, notes,
+        content = 'passwords', content_rowid = 'id',
+        tokenize = 'unicode61 remove_diacritics 2', prefix = '2 3'
+    );
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_fts_insert
+    AFTER INSERT ON passwords
+    BEGIN
+        INSERT INTO passwords_fts (rowid, website, username, notes) VALUES (NEW.id, NEW.website, NEW.username, NEW.notes);
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_fts_delete
+    AFTER DELETE ON passwords
+    BEGIN
+        INSERT INTO passwords_fts (passwords_fts, rowid, website, username, notes) VALUES ('delete', OLD.id, OLD.website, OLD.username, OLD.notes);
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_fts_update
+    AFTER UPDATE OF website, username, notes ON passwords
+    BEGIN
+        INSERT INTO passwords_fts (passwords_fts, rowid, website, username, notes) VALUES ('delete', OLD.id, OLD.website, OLD.username, OLD.notes);
+        INSERT INTO passwords_fts (rowid, website, username, notes) VALUES (NEW.id, NEW.website, NEW.username, NEW.notes);
+    END;
+    "#,
+    r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+        title, content,
+        content = 'notes', content_rowid = 'id',
+        tokenize = 'unicode61 remove_diacritics 2', prefix = '2 3'
+    );
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_notes_fts_insert
+    AFTER INSERT ON notes
+    BEGIN
+        INSERT INTO notes_fts (rowid, title, content) VALUES (NEW.id, NEW.title, NEW.content);
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_notes_fts_delete
+    AFTER DELETE ON notes
+    BEGIN
+        INSERT INTO notes_fts (notes_fts, rowid, title, content) VALUES ('delete', OLD.id, OLD.title, OLD.content);
+    END;
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_notes_fts_update
+    AFTER UPDATE OF title, content ON notes
+    BEGIN
+        INSERT INTO notes_fts (notes_fts, rowid, title, content) VALUES ('delete', OLD.id, OLD.title, OLD.content);
+        INSERT INTO notes_fts (rowid, title, content) VALUES (NEW.id, NEW.title, NEW.content);
+    END;
+    "#,
+    r#"
+    INSERT INTO passwords_fts (passwords_fts) VALUES ('rebuild');
+    "#,
+    r#"
+    INSERT INTO notes_fts (notes_fts) VALUES ('rebuild');
+    "#,
+    // Migration 11: Folders and tags (tags are a JSON array of strings)
+    r#"
+    ALTER TABLE passwords ADD COLUMN folder TEXT;
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN tags TEXT;
+    "#,
+    r#"
+    ALTER TABLE notes ADD COLUMN folder TEXT;
+    "#,
+    r#"
+    ALTER TABLE notes ADD COLUMN tags TEXT;
+    "#,
+    // Migration 12: Favorites
+    r#"
+    ALTER TABLE passwords ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0;
+    "#,
+    r#"
+    ALTER TABLE notes ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0;
+    "#,
+    // Migration 13: When an entry's password was last revealed
+    r#"
+    ALTER TABLE passwords ADD COLUMN last_used_at DATETIME;
+    "#,
+    // Migration 14: Password expiry; folders can set a default rotation interval for their entries
+    r#"
+    ALTER TABLE passwords ADD COLUMN expires_on TEXT;
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN rotation_days INTEGER;
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS folder_rotation (
+        folder TEXT PRIMARY KEY,
+        rotation_days INTEGER NOT NULL
+    );
+    "#,
+    // Migration 15: Bulk rotation sessions; new passwords wait in rotation_items until the user confirms them
+    r#"
+    CREATE TABLE IF NOT EXISTS rotation_sessions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT,
+        generator_options TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'active',
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        finished_at DATETIME
+    );
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS rotation_items (
+        session_id INTEGER NOT NULL,
+        entry_id INTEGER NOT NULL,
+        pending_password TEXT,
+        status TEXT NOT NULL DEFAULT 'pending',
+        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (session_id, entry_id)
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_rotation_items_entry ON rotation_items(entry_id);
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS rotation_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id INTEGER NOT NULL,
+        entry_id INTEGER,
+        message TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_rotation_log_session ON rotation_log(session_id, id);
+    "#,
+    r#"
+    CREATE TRIGGER IF NOT EXISTS trg_passwords_delete_rotation
+    AFTER DELETE ON passwords
+    BEGIN
+        DELETE FROM rotation_items WHERE entry_id = OLD.id;
+    END;
+    "#,
+    // Migration 16: Compromised flag, set by hand or by a breach check and cleared when the password changes
+    r#"
+    ALTER TABLE passwords ADD COLUMN compromised_at DATETIME;
+    "#,
+    r#"
+    ALTER TABLE passwords ADD COLUMN compromised_reason TEXT;
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_passwords_compromised ON passwords(compromised_at) WHERE compromised_at IS NOT NULL;
+    "#,
+    // Migration 17: Usage counts for entries, and usage tracking for notes
+    r#"
+    ALTER TABLE passwords ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0;
+    "#,
+    r#"
+    ALTER TABLE notes ADD COLUMN last_used_at DATETIME;
+    "#,
+    r#"
+    ALTER TABLE notes ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0;
+    "#,
+    // Migration 18: When each entry's password was last set; history can be pruned, so it can't be relied on for this
+    r#"
+    ALTER TABLE passwords ADD COLUMN password_changed_at DATETIME;
+    "#,
+    r#"
+    UPDATE passwords SET password_changed_at = COALESCE(
+        (SELECT MAX(changed_at) FROM password_history WHERE entry_id = passwords.id AND field_name = 'password'),
+        created_at
+    );
+    "#,
 ];
 
 pub async fn run_migrations(conn: &Connection) -> Result<()> {
@@ -73,6 +401,8 @@ pub async fn run_migrations(conn: &Connection) -> Result<()> {
             conn.execute(migration, ())
                 .await
                 .map_err(|e| DatabaseError::Migration(format!("Failed to apply migration {}: {}", version, e)))?;
+
+            run_data_migration(conn, migration).await?;
             
             // Record that this migration was applied
             conn.execute(
@@ -87,6 +417,36 @@ pub async fn run_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Data migrations that can't be written in SQL, keyed by the statement they follow.
+async fn run_data_migration(conn: &Connection, migration: &str) -> Result<()> {
+    if migration == CREATE_ENTRY_URIS {
+        split_website_uris(conn).await?;
+    }
+    Ok(())
+}
+
+/// Turns the free-text `website` of existing logins into normalized entry URIs.
+async fn split_website_uris(conn: &Connection) -> Result<()> {
+    let mut rows = conn
+        .query("SELECT id, website FROM passwords WHERE item_type = 'login'", ())
+        .await
+        .map_err(|e| DatabaseError::Migration(format!("Failed to read websites: {}", e)))?;
+
+    let mut entries = Vec::new();
+    while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Migration(format!("Failed to read website row: {}", e)))? {
+        entries.push((row.get::<i64>(0)?, row.get::<String>(1)?));
+    }
+
+    for (entry_id, website) in entries {
+        let uris = crate::services::website_uris(&website);
+        crate::repositories::EntryUriRepository::replace_for_entry(conn, entry_id, &uris)
+            .await
+            .map_err(|e| DatabaseError::Migration(format!("Failed to split website of entry {}: {}", entry_id, e)))?;
+    }
+
+    Ok(())
+}
+
 async fn get_current_version(conn: &Connection) -> Result<i64> {
     let mut rows = conn
         .query("SELECT MAX(version) as version FROM schema_version", ())
@@ -1,7 +1,11 @@
 pub mod connection;
 pub mod migrations;
 pub mod models;
+pub mod items;
+pub mod templates;
 
 pub use connection::*;
 pub use migrations::*;
 pub use models::*;
+pub use items::*;
+pub use templates::*;
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::items::{ItemDetails, ItemKind, PasskeyAlgorithm};
+use super::templates::CustomField;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PasswordEntry {
     pub id: Option<i64>,
     pub website: String,
@@ -9,6 +12,38 @@ pub struct PasswordEntry {
     pub notes: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub details: Option<ItemDetails>, // None for plain website logins
+    #[serde(default)]
+    pub template_id: Option<i64>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub uris: Vec<EntryUri>,
+    #[serde(default)]
+    pub folder: Option<String>, // `/` separates nested folders, e.g. "clients/acme"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub last_used_at: Option<String>, // set when the password is revealed or copied
+    #[serde(default)]
+    pub use_count: u32, // read-only; counted by `record_usage`
+    #[serde(default)]
+    pub expires_on: Option<String>, // YYYY-MM-DD; takes precedence over any rotation interval
+    #[serde(default)]
+    pub rotation_days: Option<u32>, // overrides the folder's interval
+    #[serde(default)]
+    pub compromised_at: Option<String>, // read-only here; see `mark_compromised`
+    #[serde(default)]
+    pub compromised_reason: Option<String>,
+}
+
+impl PasswordEntry {
+    pub fn kind(&self) -> ItemKind {
+        self.details.as_ref().map(ItemDetails::kind).unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +53,284 @@ pub struct Note {
     pub content: String,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub last_used_at: Option<String>, // set when the note is opened or copied
+    #[serde(default)]
+    pub use_count: u32, // read-only; counted by `record_usage`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Entry,
+    Note,
+}
+
+/// A piece of a search snippet; `highlight` marks text that matched the query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlight: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    pub title: String,            // website for entries, title for notes
+    pub username: Option<String>, // entries only
+    pub snippet: Vec<SnippetSegment>,
+    pub score: f64, // higher is more relevant
+}
+
+/// An entry or note for the quick-access list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsedItem {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    pub title: String,            // website for entries, title for notes
+    pub username: Option<String>, // entries only
+    pub favorite: bool,
+    pub use_count: u32,
+    pub last_used_at: Option<String>,
+}
+
+/// An entry as shown in lists: everything except the secrets
+/// (password, notes, item details and custom fields), which are fetched on demand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordSummary {
+    pub id: i64,
+    pub website: String,
+    pub username: String,
+    pub kind: ItemKind,
+    pub uris: Vec<EntryUri>,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+    pub favorite: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub use_count: u32,
+    pub compromised_at: Option<String>,
+    pub compromised_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntrySort {
+    Name, // A to Z
+    #[default]
+    Created, // newest first
+    Updated, // most recently changed first
+    LastUsed, // most recently used first, never used last
+    MostUsed, // highest use count first, then most recently used
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListOptions {
+    pub kind: Option<ItemKind>,
+    pub sort: EntrySort,
+    pub reverse: bool, // flips the sort's natural direction
+    pub offset: u32,
+    pub limit: Option<u32>,
+    pub favorites_only: bool,
+    pub compromised_only: bool,
+}
+
+/// Sorting and filtering for `get_notes`; `EntrySort::Name` sorts by title.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoteListOptions {
+    pub sort: EntrySort,
+    pub reverse: bool,
+    pub favorites_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+/// Entries and notes matching a `query_vault` query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultQueryResults {
+    pub entries: Vec<PasswordEntry>,
+    pub notes: Vec<Note>,
+}
+
+/// How an entry URI is compared against the URL of the page asking for credentials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UriMatchMode {
+    #[default]
+    BaseDomain, // same registrable domain, any subdomain
+    Host,       // same host (and port, if the URI has one)
+    StartsWith, // the URL starts with the URI
+    Regex,      // the URI is a regular expression matched against the URL
+    Never,      // kept for reference, never offered
+}
+
+impl UriMatchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UriMatchMode::BaseDomain => "base_domain",
+            UriMatchMode::Host => "host",
+            UriMatchMode::StartsWith => "starts_with",
+            UriMatchMode::Regex => "regex",
+            UriMatchMode::Never => "never",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "base_domain" => Some(UriMatchMode::BaseDomain),
+            "host" => Some(UriMatchMode::Host),
+            "starts_with" => Some(UriMatchMode::StartsWith),
+            "regex" => Some(UriMatchMode::Regex),
+            "never" => Some(UriMatchMode::Never),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryUri {
+    pub uri: String,
+    #[serde(default)]
+    pub match_mode: UriMatchMode,
+}
+
+/// How closely an entry URI matched a page, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UriMatchQuality {
+    ExactUrl,  // the page URL starts with the URI's path (or a starts_with rule)
+    App,       // same Android app package
+    Host,      // same host and port
+    Regex,     // a user-written regular expression
+    Subdomain, // one host is a subdomain of the other
+    Domain,    // same registrable domain only
+}
+
+/// An entry offered for filling a page, without its secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutofillCandidate {
+    pub entry_id: i64,
+    pub title: String,
+    pub username: String,
+    pub matched_uri: String,
+    pub match_quality: UriMatchQuality,
+    /// The entry was saved for https but the page is plain http
+    pub insecure: bool,
+    pub last_used_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LookalikeKind {
+    Homoglyph,       // same skeleton as a saved domain, different characters (pаypal, paypa1, rnicrosoft)
+    Typo,            // within a small edit distance of a saved domain
+    DifferentSuffix, // same name as a saved domain under another public suffix
+    SubdomainTrick,  // a saved domain's name used as a subdomain of some other site
+    EmbeddedName,    // a saved domain's name inside another domain (paypal-secure.net)
+    MixedScript,     // a label mixing Latin with Cyrillic, Greek or Armenian letters
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+/// A reason to doubt that a page belongs to a site saved in the vault.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhishingWarning {
+    pub kind: LookalikeKind,
+    pub severity: WarningSeverity,
+    pub requested_domain: String, // Unicode form, as a person would read it
+    pub similar_to: Option<String>,
+    pub entry_ids: Vec<i64>, // entries saved for `similar_to`
+    pub message: String,
+}
+
+/// A previous value of an entry's password (or concealed field).
+/// The value itself is never serialized here; it is only handed out on demand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordHistoryEntry {
+    pub id: i64,
+    pub entry_id: i64,
+    pub field_name: String,
+    pub changed_at: Option<String>,
+}
+
+/// A saved snapshot of a note. Revisions are append-only and numbered from 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteRevision {
+    pub id: i64,
+    pub note_id: i64,
+    pub revision: i64,
+    pub title: String,
+    pub content: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub old_line: Option<usize>, // 1-based
+    pub new_line: Option<usize>, // 1-based
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteRevisionDiff {
+    pub note_id: i64,
+    pub from_revision: i64,
+    pub to_revision: i64,
+    pub old_title: String,
+    pub new_title: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// The entry or note an attachment belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum AttachmentParent {
+    Entry(i64),
+    Note(i64),
+}
+
+/// Attachment metadata. File contents live in `attachment_chunks` and are only streamed on export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i64,
+    pub parent: AttachmentParent,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub sha256: String,
+    pub created_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,25 +344,455 @@ pub struct PasswordStrength {
     pub score: u8, // 0-4
     pub feedback: Vec<String>,
     pub warning: Option<String>,
+    pub guesses_log10: f64,
+    pub crack_time_seconds: f64, // offline attack on a slow hash, 10k guesses per second
+    pub crack_time_display: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepeatRule {
+    #[default]
+    Allow,
+    NoAdjacent, // never the same character twice in a row
+    Never,      // every character at most once
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GeneratorOptions {
+    pub length: usize,
     pub include_uppercase: bool,
     pub include_lowercase: bool,
     pub include_numbers: bool,
     pub include_symbols: bool,
     pub exclude_ambiguous: bool,
+    // Each enabled class appears at least this many times
+    pub min_uppercase: usize,
+    pub min_lowercase: usize,
+    pub min_numbers: usize,
+    pub min_symbols: usize,
+    pub include_chars: String, // extra characters added to the pool
+    pub exclude_chars: String, // characters removed from every class
+    pub repeats: RepeatRule,
 }
 
 impl Default for GeneratorOptions {
     fn default() -> Self {
         Self {
+            length: 20,
             include_uppercase: true,
             include_lowercase: true,
             include_numbers: true,
             include_symbols: false,
             exclude_ambiguous: true,
+            min_uppercase: 1,
+            min_lowercase: 1,
+            min_numbers: 1,
+            min_symbols: 1,
+            include_chars: String::new(),
+            exclude_chars: String::new(),
+            repeats: RepeatRule::Allow,
         }
     }
 }
+
+/// A generated secret with an estimate of how many bits of randomness went into it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedPassword {
+    pub password: String,
+    pub entropy_bits: f64,
+}
+
+/// Where passphrase words come from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
+pub enum WordlistSource {
+    #[default]
+    Bip39English, // the embedded 2048-word BIP-39 English list
+    Custom(String), // one word per line, or the EFF `11111<TAB>word` format
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordCase {
+    #[default]
+    Lower,
+    Capitalized,
+    Upper,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PassphraseOptions {
+    pub word_count: usize,
+    pub separator: String,
+    pub case: WordCase,
+    pub include_number: bool, // a digit after one random word
+    pub include_symbol: bool, // a symbol after one random word
+    pub wordlist: WordlistSource,
+}
+
+impl Default for PassphraseOptions {
+    fn default() -> Self {
+        Self {
+            word_count: 7,
+            separator: "-".to_string(),
+            case: WordCase::Lower,
+            include_number: false,
+            include_symbol: false,
+            wordlist: WordlistSource::Bip39English,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordlistInfo {
+    pub path: String,
+    pub word_count: usize, // distinct words, ignoring case
+    pub bits_per_word: f64,
+}
+
+/// Per-vault settings, persisted as key/value pairs in the `config` table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultSettings {
+    pub password_history_depth: u32,
+    pub attachment_max_bytes: u64,
+    #[serde(default)]
+    pub master_password_policy: MasterPasswordPolicy,
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u32,
+    #[serde(default = "default_recovery_code_warning_threshold")]
+    pub recovery_code_warning_threshold: u32, // warn when fewer unused codes than this are left
+}
+
+fn default_expiry_warning_days() -> u32 {
+    14
+}
+
+fn default_recovery_code_warning_threshold() -> u32 {
+    3
+}
+
+impl Default for VaultSettings {
+    fn default() -> Self {
+        Self {
+            password_history_depth: 10,
+            attachment_max_bytes: 25 * 1024 * 1024,
+            master_password_policy: MasterPasswordPolicy::default(),
+            expiry_warning_days: default_expiry_warning_days(),
+            recovery_code_warning_threshold: default_recovery_code_warning_threshold(),
+        }
+    }
+}
+
+/// Rules a new master password has to meet, checked when a vault is created or its password changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MasterPasswordPolicy {
+    /// Minimum strength, in bits, as estimated by the strength checker
+    pub min_entropy_bits: u32,
+    pub block_common_passwords: bool,
+    /// Reject a master password that is also stored as one of the vault's entries
+    pub forbid_entry_reuse: bool,
+}
+
+impl Default for MasterPasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_entropy_bits: 40,
+            block_common_passwords: true,
+            forbid_entry_reuse: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthIssueKind {
+    NoPassword,
+    Compromised,
+    Reused,
+    Weak,
+    Old,
+    InsecureUrl,
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthIssue {
+    pub kind: HealthIssueKind,
+    pub detail: String,
+    pub remediation: String,
+}
+
+/// One entry with everything the health check found wrong with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReportItem {
+    pub entry_id: i64,
+    pub website: String,
+    pub username: String,
+    pub kind: ItemKind,
+    pub password_age_days: Option<u32>,
+    pub issues: Vec<HealthIssue>,
+}
+
+/// Entries sharing one password, identified by the password's SHA-256 (hex) rather than the password itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReusedPasswordGroup {
+    pub password_sha256: String,
+    pub entry_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthCounts {
+    pub no_password: usize,
+    pub compromised: usize,
+    pub reused: usize,
+    pub weak: usize,
+    pub old: usize,
+    pub insecure_url: usize,
+    pub duplicate: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultHealthReport {
+    pub score: u8, // 0-100, higher is healthier
+    pub generated_at: String, // UTC, "YYYY-MM-DD HH:MM"
+    pub total_entries: usize,
+    pub max_password_age_days: u32,
+    pub counts: HealthCounts,
+    pub reused_groups: Vec<ReusedPasswordGroup>,
+    pub duplicate_groups: Vec<Vec<i64>>,
+    /// Entries with at least one issue, most at risk first
+    pub items: Vec<HealthReportItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthExportFormat {
+    Html,
+    Csv,
+}
+
+/// The local copy of the Have I Been Pwned password hashes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PwnedIndexInfo {
+    pub path: String,
+    pub hash_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreachedEntry {
+    pub entry_id: i64,
+    pub website: String,
+    pub username: String,
+}
+
+/// Where an entry's due date comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpirySource {
+    Date,   // the entry's own expiry date
+    Entry,  // the entry's rotation interval
+    Folder, // the rotation interval of its folder or a parent folder
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpiringEntry {
+    pub entry_id: i64,
+    pub website: String,
+    pub username: String,
+    pub kind: ItemKind,
+    pub folder: Option<String>,
+    pub due_at: String, // UTC, "YYYY-MM-DD HH:MM:SS"
+    pub days_left: i64, // negative once overdue
+    pub source: ExpirySource,
+}
+
+/// A default rotation interval for the entries in a folder and its subfolders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderRotation {
+    pub folder: String,
+    pub rotation_days: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationStatus {
+    Active,
+    Completed, // every entry was promoted or rolled back
+    Cancelled,
+}
+
+impl RotationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationStatus::Active => "active",
+            RotationStatus::Completed => "completed",
+            RotationStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(RotationStatus::Active),
+            "completed" => Some(RotationStatus::Completed),
+            "cancelled" => Some(RotationStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationItemStatus {
+    Pending,    // a new password is staged next to the current one
+    Promoted,   // the new password replaced the old one, which went to history
+    RolledBack, // the current password was kept
+}
+
+impl RotationItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationItemStatus::Pending => "pending",
+            RotationItemStatus::Promoted => "promoted",
+            RotationItemStatus::RolledBack => "rolled_back",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(RotationItemStatus::Pending),
+            "promoted" => Some(RotationItemStatus::Promoted),
+            "rolled_back" => Some(RotationItemStatus::RolledBack),
+            _ => None,
+        }
+    }
+}
+
+/// One entry in a rotation session. The staged password is fetched separately with `reveal_pending_password`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationItem {
+    pub entry_id: i64,
+    pub website: String,
+    pub username: String,
+    pub status: RotationItemStatus,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationLogEntry {
+    pub id: i64,
+    pub entry_id: Option<i64>,
+    pub message: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationSession {
+    pub id: i64,
+    pub name: Option<String>,
+    pub status: RotationStatus,
+    pub generator_options: GeneratorOptions,
+    pub created_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub items: Vec<RotationItem>,
+    pub log: Vec<RotationLogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationSessionSummary {
+    pub id: i64,
+    pub name: Option<String>,
+    pub status: RotationStatus,
+    pub created_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub total: i64,
+    pub pending: i64,
+    pub promoted: i64,
+    pub rolled_back: i64,
+}
+
+/// How many of a recovery-codes item's codes are left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCodeStatus {
+    pub item_id: i64,
+    pub title: String,
+    pub entry_id: Option<i64>,
+    pub total: usize,
+    pub remaining: usize,
+    pub low: bool, // fewer than the vault's warning threshold are left
+}
+
+/// A relying party's `navigator.credentials.create()` request, as passed on by a browser bridge.
+/// Binary values are base64url without padding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyCreationOptions {
+    pub rp_id: String,
+    #[serde(default)]
+    pub rp_name: Option<String>,
+    pub user_handle: String,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub user_display_name: Option<String>,
+    #[serde(default)]
+    pub algorithms: Vec<i64>, // COSE ids from `pubKeyCredParams`, most preferred first
+    #[serde(default)]
+    pub exclude_credentials: Vec<String>,
+    pub client_data_hash: String, // SHA-256 of the clientDataJSON
+    #[serde(default)]
+    pub user_verified: bool,
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+/// What the authenticator hands back for `navigator.credentials.create()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyRegistration {
+    pub item_id: Option<i64>, // set once the passkey is saved
+    pub credential_id: String,
+    pub public_key_algorithm: i64,
+    pub public_key: String, // SubjectPublicKeyInfo DER, as from `getPublicKey()`
+    pub authenticator_data: String,
+    pub attestation_object: String,
+}
+
+/// A relying party's `navigator.credentials.get()` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyAssertionRequest {
+    pub rp_id: String,
+    pub client_data_hash: String,
+    #[serde(default)]
+    pub allow_credentials: Vec<String>, // empty when the RP lets the user pick any of their passkeys
+    #[serde(default)]
+    pub item_id: Option<i64>, // the passkey the user picked, when several match
+    #[serde(default)]
+    pub user_verified: bool,
+}
+
+/// What the authenticator hands back for `navigator.credentials.get()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyAssertion {
+    pub item_id: Option<i64>,
+    pub credential_id: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: String,
+}
+
+/// A saved passkey without its private key, for choosing one to sign in with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeySummary {
+    pub item_id: i64,
+    pub title: String,
+    pub credential_id: String,
+    pub rp_id: String,
+    pub user_handle: String,
+    pub user_name: Option<String>,
+    pub user_display_name: Option<String>,
+    pub algorithm: PasskeyAlgorithm,
+    pub sign_count: u32,
+    pub last_used_at: Option<String>,
+}
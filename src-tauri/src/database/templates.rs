@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    Text,
+    Multiline,
+    Concealed,
+    Url,
+    Email,
+    Number,
+    Date, // YYYY-MM-DD
+    Boolean,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateField {
+    pub name: String,
+    pub field_type: FieldType,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// A user-defined set of fields that entries can be created from and validated against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryTemplate {
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub fields: Vec<TemplateField>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// A named value on an entry. Concealed fields are treated like passwords:
+/// their previous values are kept in the password history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub concealed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImportSummary {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
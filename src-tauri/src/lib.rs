@@ -35,22 +35,84 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::get_passwords,
+            commands::list_passwords,
+            commands::reveal_password,
             commands::create_password,
             commands::update_password,
             commands::delete_password,
+            commands::mark_compromised,
+            commands::clear_compromised,
+            commands::list_compromised,
+            commands::record_usage,
+            commands::frequently_used,
+            commands::consume_recovery_code,
+            commands::get_recovery_code_status,
+            commands::list_recovery_codes_for_entry,
+            commands::list_low_recovery_codes,
+            commands::parse_recovery_codes,
+            commands::create_passkey,
+            commands::list_passkeys,
+            commands::get_passkey_assertion,
             commands::search_passwords,
+            commands::query_vault,
+            commands::list_password_history,
+            commands::copy_password_history,
+            commands::find_entries_for_url,
             commands::get_notes,
             commands::create_note,
             commands::update_note,
             commands::delete_note,
+            commands::list_note_revisions,
+            commands::diff_note_revisions,
+            commands::restore_note_revision,
+            commands::add_attachment,
+            commands::list_attachments,
+            commands::export_attachment,
+            commands::verify_attachment,
+            commands::delete_attachment,
+            commands::get_templates,
+            commands::create_template,
+            commands::update_template,
+            commands::delete_template,
+            commands::new_entry_from_template,
+            commands::export_templates,
+            commands::import_templates,
+            commands::get_autofill_candidates,
+            commands::check_url_lookalikes,
+            commands::search_vault,
+            commands::generate_password,
+            commands::generate_passphrase,
+            commands::pick_wordlist,
+            commands::estimate_password_strength,
+            commands::vault_health_report,
+            commands::export_vault_health_report,
+            commands::import_pwned_passwords,
+            commands::get_pwned_index_info,
+            commands::check_breached_passwords,
+            commands::is_password_breached,
+            commands::list_expiring_entries,
+            commands::list_expired_entries,
+            commands::get_folder_rotations,
+            commands::set_folder_rotation,
+            commands::start_rotation_session,
+            commands::list_rotation_sessions,
+            commands::get_rotation_session,
+            commands::reveal_pending_password,
+            commands::regenerate_pending_password,
+            commands::confirm_rotation,
+            commands::roll_back_rotation,
+            commands::cancel_rotation_session,
             commands::initialize_database_with_path,
             commands::create_new_vault,
+            commands::change_master_password,
             commands::get_vault_directory,
             commands::is_vault_file_valid,
             commands::create_session,
             commands::validate_session,
             commands::lock_session,
-            commands::disconnect_database
+            commands::disconnect_database,
+            commands::get_vault_settings,
+            commands::update_vault_settings
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
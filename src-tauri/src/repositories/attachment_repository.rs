@@ -0,0 +1,128 @@
+use libsql::{Connection, Row, Rows};
+use crate::database::{Attachment, AttachmentParent, DatabaseError, Result};
+
+pub struct AttachmentRepository;
+
+impl AttachmentRepository {
+    pub async fn create(conn: &Connection, parent: AttachmentParent, file_name: &str, mime_type: &str) -> Result<i64> {
+        let (entry_id, note_id) = match parent {
+            AttachmentParent::Entry(id) => (Some(id), None),
+            AttachmentParent::Note(id) => (None, Some(id)),
+        };
+
+        conn.execute(
+            "INSERT INTO attachments (entry_id, note_id, file_name, mime_type) VALUES (?, ?, ?, ?)",
+            (entry_id, note_id, file_name, mime_type),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create attachment: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn add_chunk(conn: &Connection, attachment_id: i64, seq: i64, data: &[u8]) -> Result<()> {
+        conn.execute(
+            "INSERT INTO attachment_chunks (attachment_id, seq, data) VALUES (?, ?, ?)",
+            (attachment_id, seq, data),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to store attachment data: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn finalize(conn: &Connection, id: i64, size: i64, sha256: &str) -> Result<()> {
+        conn.execute(
+            "UPDATE attachments SET size = ?, sha256 = ? WHERE id = ?",
+            (size, sha256, id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to update attachment: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn get_for_parent(conn: &Connection, parent: AttachmentParent) -> Result<Vec<Attachment>> {
+        let (column, id) = match parent {
+            AttachmentParent::Entry(id) => ("entry_id", id),
+            AttachmentParent::Note(id) => ("note_id", id),
+        };
+
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT id, entry_id, note_id, file_name, mime_type, size, sha256, created_at FROM attachments WHERE {} = ? ORDER BY created_at DESC",
+                    column
+                ),
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get attachments: {}", e)))?;
+
+        let mut attachments = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read attachment row: {}", e)))? {
+            attachments.push(Self::from_row(&row)?);
+        }
+
+        Ok(attachments)
+    }
+
+    pub async fn get_by_id(conn: &Connection, id: i64) -> Result<Option<Attachment>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, entry_id, note_id, file_name, mime_type, size, sha256, created_at FROM attachments WHERE id = ?",
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get attachment by id: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read attachment row: {}", e)))? {
+            Ok(Some(Self::from_row(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the attachment's chunks in order; read them one at a time with `next_chunk`.
+    pub async fn get_chunks(conn: &Connection, attachment_id: i64) -> Result<Rows> {
+        conn.query(
+            "SELECT data FROM attachment_chunks WHERE attachment_id = ? ORDER BY seq",
+            [attachment_id],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to read attachment data: {}", e)))
+    }
+
+    pub async fn next_chunk(rows: &mut Rows) -> Result<Option<Vec<u8>>> {
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read attachment chunk: {}", e)))? {
+            Some(row) => Ok(Some(row.get::<Vec<u8>>(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn delete(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute("DELETE FROM attachments WHERE id = ?", [id])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to delete attachment: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<Attachment> {
+        let parent = match (row.get::<Option<i64>>(1)?, row.get::<Option<i64>>(2)?) {
+            (Some(entry_id), _) => AttachmentParent::Entry(entry_id),
+            (None, Some(note_id)) => AttachmentParent::Note(note_id),
+            (None, None) => return Err(DatabaseError::Query("Attachment has no parent".to_string())),
+        };
+
+        Ok(Attachment {
+            id: row.get::<i64>(0)?,
+            parent,
+            file_name: row.get::<String>(3)?,
+            mime_type: row.get::<String>(4)?,
+            size: row.get::<i64>(5)?,
+            sha256: row.get::<String>(6)?,
+            created_at: row.get::<Option<String>>(7)?,
+        })
+    }
+}
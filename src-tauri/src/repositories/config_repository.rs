@@ -0,0 +1,30 @@
+use libsql::Connection;
+use crate::database::{DatabaseError, Result};
+
+pub struct ConfigRepository;
+
+impl ConfigRepository {
+    pub async fn get(conn: &Connection, key: &str) -> Result<Option<String>> {
+        let mut rows = conn
+            .query("SELECT value FROM config WHERE key = ?", [key])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get config value: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read config row: {}", e)))? {
+            Ok(Some(row.get::<String>(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn set(conn: &Connection, key: &str, value: &str) -> Result<()> {
+        conn.execute(
+            "INSERT INTO config (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            (key, value),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to set config value: {}", e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use libsql::Connection;
+use crate::database::{DatabaseError, EntryUri, Result, UriMatchMode};
+
+pub struct EntryUriRepository;
+
+impl EntryUriRepository {
+    /// Replaces all URIs of an entry, keeping the given order.
+    pub async fn replace_for_entry(conn: &Connection, entry_id: i64, uris: &[EntryUri]) -> Result<()> {
+        conn.execute("DELETE FROM entry_uris WHERE entry_id = ?", [entry_id])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to clear entry URIs: {}", e)))?;
+
+        for (position, uri) in uris.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO entry_uris (entry_id, position, uri, match_mode) VALUES (?, ?, ?, ?)",
+                (entry_id, position as i64, uri.uri.as_str(), uri.match_mode.as_str()),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to save entry URI: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns every stored URI with the id of the entry it belongs to.
+    pub async fn get_all(conn: &Connection) -> Result<Vec<(i64, EntryUri)>> {
+        let mut rows = conn
+            .query("SELECT entry_id, uri, match_mode FROM entry_uris ORDER BY entry_id, position", ())
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get entry URIs: {}", e)))?;
+
+        let mut uris = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read entry URI row: {}", e)))? {
+            let match_mode = UriMatchMode::parse(&row.get::<String>(2)?).unwrap_or(UriMatchMode::Never);
+            uris.push((
+                row.get::<i64>(0)?,
+                EntryUri {
+                    uri: row.get::<String>(1)?,
+                    match_mode,
+                },
+            ));
+        }
+
+        Ok(uris)
+    }
+}
@@ -0,0 +1,43 @@
+use libsql::Connection;
+use crate::database::{DatabaseError, FolderRotation, Result};
+
+pub struct FolderRotationRepository;
+
+impl FolderRotationRepository {
+    pub async fn get_all(conn: &Connection) -> Result<Vec<FolderRotation>> {
+        let mut rows = conn
+            .query("SELECT folder, rotation_days FROM folder_rotation ORDER BY folder", ())
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get folder rotation intervals: {}", e)))?;
+
+        let mut rotations = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read folder rotation row: {}", e)))? {
+            rotations.push(FolderRotation {
+                folder: row.get::<String>(0)?,
+                rotation_days: row.get::<u32>(1)?,
+            });
+        }
+
+        Ok(rotations)
+    }
+
+    pub async fn set(conn: &Connection, folder: &str, rotation_days: u32) -> Result<()> {
+        conn.execute(
+            "INSERT INTO folder_rotation (folder, rotation_days) VALUES (?, ?)
+             ON CONFLICT(folder) DO UPDATE SET rotation_days = excluded.rotation_days",
+            (folder, rotation_days),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to set folder rotation interval: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete(conn: &Connection, folder: &str) -> Result<()> {
+        conn.execute("DELETE FROM folder_rotation WHERE folder = ?", [folder])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to clear folder rotation interval: {}", e)))?;
+
+        Ok(())
+    }
+}
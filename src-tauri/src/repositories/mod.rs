@@ -1,5 +1,23 @@
 pub mod password_repository;
 pub mod notes_repository;
+pub mod config_repository;
+pub mod password_history_repository;
+pub mod note_revision_repository;
+pub mod attachment_repository;
+pub mod template_repository;
+pub mod entry_uri_repository;
+pub mod search_repository;
+pub mod folder_rotation_repository;
+pub mod rotation_repository;
 
 pub use password_repository::*;
 pub use notes_repository::*;
+pub use config_repository::*;
+pub use password_history_repository::*;
+pub use note_revision_repository::*;
+pub use attachment_repository::*;
+pub use template_repository::*;
+pub use entry_uri_repository::*;
+pub use search_repository::*;
+pub use folder_rotation_repository::*;
+pub use rotation_repository::*;
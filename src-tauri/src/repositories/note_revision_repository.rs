@@ -0,0 +1,87 @@
+use libsql::{Connection, Row};
+use crate::database::{DatabaseError, Result, NoteRevision};
+
+pub struct NoteRevisionRepository;
+
+impl NoteRevisionRepository {
+    /// Appends a revision and returns its number.
+    pub async fn create(conn: &Connection, note_id: i64, title: &str, content: &str) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO note_revisions (note_id, revision, title, content)
+             SELECT ?, COALESCE(MAX(revision), 0) + 1, ?, ? FROM note_revisions WHERE note_id = ?",
+            (note_id, title, content, note_id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create note revision: {}", e)))?;
+
+        let mut rows = conn
+            .query("SELECT revision FROM note_revisions WHERE id = ?", [conn.last_insert_rowid()])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get note revision: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note revision row: {}", e)))? {
+            Some(row) => Ok(row.get::<i64>(0)?),
+            None => Err(DatabaseError::Query("Note revision was not recorded".to_string())),
+        }
+    }
+
+    pub async fn get_for_note(conn: &Connection, note_id: i64) -> Result<Vec<NoteRevision>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, note_id, revision, title, content, created_at FROM note_revisions WHERE note_id = ? ORDER BY revision DESC",
+                [note_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get note revisions: {}", e)))?;
+
+        let mut revisions = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note revision row: {}", e)))? {
+            revisions.push(Self::from_row(&row)?);
+        }
+
+        Ok(revisions)
+    }
+
+    pub async fn get(conn: &Connection, note_id: i64, revision: i64) -> Result<Option<NoteRevision>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, note_id, revision, title, content, created_at FROM note_revisions WHERE note_id = ? AND revision = ?",
+                [note_id, revision],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get note revision: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note revision row: {}", e)))? {
+            Ok(Some(Self::from_row(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn get_latest(conn: &Connection, note_id: i64) -> Result<Option<NoteRevision>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, note_id, revision, title, content, created_at FROM note_revisions WHERE note_id = ? ORDER BY revision DESC LIMIT 1",
+                [note_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get latest note revision: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note revision row: {}", e)))? {
+            Ok(Some(Self::from_row(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_row(row: &Row) -> Result<NoteRevision> {
+        Ok(NoteRevision {
+            id: row.get::<i64>(0)?,
+            note_id: row.get::<i64>(1)?,
+            revision: row.get::<i64>(2)?,
+            title: row.get::<String>(3)?,
+            content: row.get::<String>(4)?,
+            created_at: row.get::<Option<String>>(5)?,
+        })
+    }
+}
@@ -1,39 +1,40 @@
-use libsql::Connection;
-use crate::database::{DatabaseError, Result, Note};
+use libsql::{Connection, Row, Value};
+use crate::database::{DatabaseError, Result, Note, NoteListOptions};
+use super::password_repository::{decode_tags, encode_tags, sort_order};
+
+const NOTE_COLUMNS: &str = "id, title, content, created_at, updated_at, folder, tags, favorite, last_used_at, use_count";
 
 pub struct NotesRepository;
 
 impl NotesRepository {
     pub async fn create(conn: &Connection, note: &Note) -> Result<i64> {
-        let result = conn
-            .execute(
-                "INSERT INTO notes (title, content) VALUES (?, ?)",
-                (note.title.as_str(), note.content.as_str()),
-            )
-            .await
-            .map_err(|e| DatabaseError::Query(format!("Failed to create note: {}", e)))?;
+        let tags = encode_tags(&note.tags)?;
+        conn.execute(
+            "INSERT INTO notes (title, content, folder, tags, favorite) VALUES (?, ?, ?, ?, ?)",
+            (note.title.as_str(), note.content.as_str(), note.folder.as_deref(), tags.as_deref(), note.favorite),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create note: {}", e)))?;
 
-        Ok(result as i64)
+        Ok(conn.last_insert_rowid())
     }
 
-    pub async fn get_all(conn: &Connection) -> Result<Vec<Note>> {
+    pub async fn get_all(conn: &Connection, options: &NoteListOptions) -> Result<Vec<Note>> {
         let mut rows = conn
             .query(
-                "SELECT id, title, content, created_at, updated_at FROM notes ORDER BY created_at DESC",
-                (),
+                &format!(
+                    "SELECT {} FROM notes WHERE (?1 = 0 OR favorite = 1) ORDER BY {}",
+                    NOTE_COLUMNS,
+                    sort_order(options.sort, options.reverse, "title")
+                ),
+                [options.favorites_only],
             )
             .await
             .map_err(|e| DatabaseError::Query(format!("Failed to get notes: {}", e)))?;
 
         let mut notes = Vec::new();
         while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note row: {}", e)))? {
-            notes.push(Note {
-                id: Some(row.get::<i64>(0)?),
-                title: row.get::<String>(1)?,
-                content: row.get::<String>(2)?,
-                created_at: row.get::<Option<String>>(3)?,
-                updated_at: row.get::<Option<String>>(4)?,
-            });
+            notes.push(Self::from_row(&row)?);
         }
 
         Ok(notes)
@@ -42,34 +43,63 @@ impl NotesRepository {
     pub async fn get_by_id(conn: &Connection, id: i64) -> Result<Option<Note>> {
         let mut rows = conn
             .query(
-                "SELECT id, title, content, created_at, updated_at FROM notes WHERE id = ?",
+                &format!("SELECT {} FROM notes WHERE id = ?", NOTE_COLUMNS),
                 [id],
             )
             .await
             .map_err(|e| DatabaseError::Query(format!("Failed to get note by id: {}", e)))?;
 
         if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note row: {}", e)))? {
-            Ok(Some(Note {
-                id: Some(row.get::<i64>(0)?),
-                title: row.get::<String>(1)?,
-                content: row.get::<String>(2)?,
-                created_at: row.get::<Option<String>>(3)?,
-                updated_at: row.get::<Option<String>>(4)?,
-            }))
+            Ok(Some(Self::from_row(&row)?))
         } else {
             Ok(None)
         }
     }
 
-    pub async fn update(conn: &Connection, id: i64, note: &Note) -> Result<()> {
-        conn.execute(
-            "UPDATE notes SET title = ?, content = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
-            (note.title.as_str(), note.content.as_str(), id),
-        )
-        .await
-        .map_err(|e| DatabaseError::Query(format!("Failed to update note: {}", e)))?;
+    /// Notes matching a WHERE clause compiled by the vault query language.
+    pub async fn query(conn: &Connection, filter: &str, params: Vec<Value>) -> Result<Vec<Note>> {
+        let mut rows = conn
+            .query(
+                &format!("SELECT {} FROM notes WHERE {} ORDER BY created_at DESC", NOTE_COLUMNS, filter),
+                params,
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to query notes: {}", e)))?;
 
-        Ok(())
+        let mut notes = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read note row: {}", e)))? {
+            notes.push(Self::from_row(&row)?);
+        }
+
+        Ok(notes)
+    }
+
+    /// Returns false if there is no such note.
+    pub async fn update(conn: &Connection, id: i64, note: &Note) -> Result<bool> {
+        let tags = encode_tags(&note.tags)?;
+        let updated = conn
+            .execute(
+                "UPDATE notes SET title = ?, content = ?, folder = ?, tags = ?, favorite = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                (note.title.as_str(), note.content.as_str(), note.folder.as_deref(), tags.as_deref(), note.favorite, id),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to update note: {}", e)))?;
+
+        Ok(updated > 0)
+    }
+
+    /// Records that the note was just used; doesn't count as a change to `updated_at`.
+    /// Returns false if there is no such note.
+    pub async fn record_usage(conn: &Connection, id: i64) -> Result<bool> {
+        let updated = conn
+            .execute(
+                "UPDATE notes SET last_used_at = CURRENT_TIMESTAMP, use_count = use_count + 1 WHERE id = ?",
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to record usage: {}", e)))?;
+
+        Ok(updated > 0)
     }
 
     pub async fn delete(conn: &Connection, id: i64) -> Result<()> {
@@ -79,4 +109,19 @@ impl NotesRepository {
 
         Ok(())
     }
+
+    fn from_row(row: &Row) -> Result<Note> {
+        Ok(Note {
+            id: Some(row.get::<i64>(0)?),
+            title: row.get::<String>(1)?,
+            content: row.get::<String>(2)?,
+            created_at: row.get::<Option<String>>(3)?,
+            updated_at: row.get::<Option<String>>(4)?,
+            folder: row.get::<Option<String>>(5)?,
+            tags: decode_tags(row.get::<Option<String>>(6)?)?,
+            favorite: row.get::<bool>(7)?,
+            last_used_at: row.get::<Option<String>>(8)?,
+            use_count: row.get::<u32>(9)?,
+        })
+    }
 }
@@ -0,0 +1,67 @@
+use libsql::Connection;
+use crate::database::{DatabaseError, Result, PasswordHistoryEntry};
+
+pub struct PasswordHistoryRepository;
+
+impl PasswordHistoryRepository {
+    pub async fn create(conn: &Connection, entry_id: i64, field_name: &str, value: &str) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO password_history (entry_id, field_name, value) VALUES (?, ?, ?)",
+            (entry_id, field_name, value),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to record password history: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn get_for_entry(conn: &Connection, entry_id: i64) -> Result<Vec<PasswordHistoryEntry>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, entry_id, field_name, changed_at FROM password_history WHERE entry_id = ? ORDER BY changed_at DESC, id DESC",
+                [entry_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get password history: {}", e)))?;
+
+        let mut history = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password history row: {}", e)))? {
+            history.push(PasswordHistoryEntry {
+                id: row.get::<i64>(0)?,
+                entry_id: row.get::<i64>(1)?,
+                field_name: row.get::<String>(2)?,
+                changed_at: row.get::<Option<String>>(3)?,
+            });
+        }
+
+        Ok(history)
+    }
+
+    pub async fn get_value(conn: &Connection, id: i64) -> Result<Option<String>> {
+        let mut rows = conn
+            .query("SELECT value FROM password_history WHERE id = ?", [id])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get password history value: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password history row: {}", e)))? {
+            Ok(Some(row.get::<String>(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Keeps only the `keep` most recent values of a field, dropping the rest.
+    pub async fn prune(conn: &Connection, entry_id: i64, field_name: &str, keep: u32) -> Result<()> {
+        conn.execute(
+            "DELETE FROM password_history WHERE entry_id = ? AND field_name = ? AND id NOT IN (
+                SELECT id FROM password_history WHERE entry_id = ? AND field_name = ?
+                ORDER BY changed_at DESC, id DESC LIMIT ?
+             )",
+            (entry_id, field_name, entry_id, field_name, keep as i64),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to prune password history: {}", e)))?;
+
+        Ok(())
+    }
+}
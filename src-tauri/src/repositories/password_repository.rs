@@ -1,83 +1,362 @@
-use libsql::Connection;
-use crate::database::{DatabaseError, Result, PasswordEntry};
+use std::collections::HashMap;
+
+use libsql::{Connection, Row, Value};
+use crate::database::{
+    DatabaseError, Result, PasswordEntry, PasswordSummary, CustomField, EntrySort, EntryUri, ExpiringEntry, ExpirySource, ItemDetails,
+    ItemKind, ListOptions,
+    ITEM_DATA_VERSION,
+};
+
+macro_rules! uris_column {
+    () => {
+        "(SELECT json_group_array(json_object('uri', u.uri, 'match_mode', u.match_mode))
+          FROM (SELECT uri, match_mode FROM entry_uris WHERE entry_id = passwords.id ORDER BY position) u) AS uris"
+    };
+}
+
+const ENTRY_COLUMNS: &str = concat!(
+    "id, website, username, password, notes, created_at, updated_at, item_type, item_data, item_data_version, template_id, custom_fields,
+     folder, tags, favorite, last_used_at, expires_on, rotation_days, compromised_at, compromised_reason, use_count, ",
+    uris_column!()
+);
+
+const SUMMARY_COLUMNS: &str = concat!(
+    "id, website, username, item_type, folder, tags, favorite, created_at, updated_at, last_used_at, compromised_at, compromised_reason, use_count, ",
+    uris_column!()
+);
+
+/// The `ListOptions` filters, bound as ?1 (kind), ?2 (favorites only) and ?3 (compromised only).
+const LIST_FILTER: &str =
+    "(?1 IS NULL OR item_type = ?1) AND (?2 = 0 OR favorite = 1) AND (?3 = 0 OR compromised_at IS NOT NULL)";
+
+/// Saving a different password clears the compromised flag; the old password is the one that leaked.
+const CLEAR_COMPROMISED_ON_CHANGE: &str = "compromised_at = CASE WHEN password IS ?1 THEN compromised_at END,
+     compromised_reason = CASE WHEN password IS ?1 THEN compromised_reason END";
+
+/// Saving a different password restarts its age.
+const STAMP_PASSWORD_CHANGE: &str =
+    "password_changed_at = CASE WHEN password IS ?1 THEN password_changed_at ELSE CURRENT_TIMESTAMP END";
+
+/// When the current password was set.
+macro_rules! password_set_at {
+    () => {
+        "COALESCE(passwords.password_changed_at, passwords.created_at)"
+    };
+}
+
+/// Password age in days.
+pub const PASSWORD_AGE_SQL: &str = concat!("julianday('now') - julianday(", password_set_at!(), ")");
+
+/// The rotation interval of the entry's folder, or of the nearest parent folder that has one.
+const FOLDER_ROTATION_SQL: &str = "(SELECT f.rotation_days FROM folder_rotation f
+    WHERE passwords.folder = f.folder OR substr(passwords.folder, 1, length(f.folder) + 1) = f.folder || '/'
+    ORDER BY length(f.folder) DESC LIMIT 1)";
+
+/// The fields fuzzy entry search scores, without secrets or item details.
+pub struct EntrySearchFields {
+    pub id: i64,
+    pub website: String,
+    pub username: String,
+    /// The entry's first URI
+    pub url: Option<String>,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+    pub favorite: bool,
+    pub updated_at: Option<String>,
+}
 
 pub struct PasswordRepository;
 
 impl PasswordRepository {
     pub async fn create(conn: &Connection, entry: &PasswordEntry) -> Result<i64> {
-        let result = conn
-            .execute(
-                "INSERT INTO passwords (website, username, password, notes) VALUES (?, ?, ?, ?)",
+        let item_data = Self::encode_details(entry)?;
+        let custom_fields = Self::encode_custom_fields(entry)?;
+        let tags = encode_tags(&entry.tags)?;
+        conn.execute(
+            "INSERT INTO passwords (website, username, password, notes, item_type, item_data, item_data_version, template_id, custom_fields,
+             folder, tags, favorite, expires_on, rotation_days, password_changed_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)",
+            (
+                entry.website.as_str(),
+                entry.username.as_str(),
+                entry.password.as_str(),
+                entry.notes.as_deref(),
+                entry.kind().as_str(),
+                item_data.as_deref(),
+                item_data.as_ref().map(|_| ITEM_DATA_VERSION),
+                entry.template_id,
+                custom_fields.as_deref(),
+                entry.folder.as_deref(),
+                tags.as_deref(),
+                entry.favorite,
+                entry.expires_on.as_deref(),
+                entry.rotation_days,
+            ),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create password entry: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn get_all(conn: &Connection, kind: Option<ItemKind>) -> Result<Vec<PasswordEntry>> {
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {} FROM passwords WHERE (?1 IS NULL OR item_type = ?1) ORDER BY created_at DESC",
+                    ENTRY_COLUMNS
+                ),
+                [kind.map(|k| k.as_str())],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get passwords: {}", e)))?;
+
+        let mut passwords = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            passwords.push(Self::from_row(&row)?);
+        }
+
+        Ok(passwords)
+    }
+
+    /// One page of entries without their secrets.
+    pub async fn list_summaries(conn: &Connection, options: &ListOptions, limit: u32) -> Result<Vec<PasswordSummary>> {
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {} FROM passwords WHERE {} ORDER BY {} LIMIT ?4 OFFSET ?5",
+                    SUMMARY_COLUMNS,
+                    LIST_FILTER,
+                    sort_order(options.sort, options.reverse, "website")
+                ),
                 (
-                    entry.website.as_str(),
-                    entry.username.as_str(),
-                    entry.password.as_str(),
-                    entry.notes.as_deref(),
+                    options.kind.map(|k| k.as_str()),
+                    options.favorites_only,
+                    options.compromised_only,
+                    limit,
+                    options.offset,
                 ),
             )
             .await
-            .map_err(|e| DatabaseError::Query(format!("Failed to create password entry: {}", e)))?;
+            .map_err(|e| DatabaseError::Query(format!("Failed to list passwords: {}", e)))?;
 
-        Ok(result as i64)
+        let mut summaries = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            summaries.push(Self::summary_from_row(&row)?);
+        }
+
+        Ok(summaries)
     }
 
-    pub async fn get_all(conn: &Connection) -> Result<Vec<PasswordEntry>> {
+    /// Entries flagged as compromised, most recently flagged first.
+    pub async fn list_compromised(conn: &Connection) -> Result<Vec<PasswordSummary>> {
         let mut rows = conn
             .query(
-                "SELECT id, website, username, password, notes, created_at, updated_at FROM passwords ORDER BY created_at DESC",
+                &format!(
+                    "SELECT {} FROM passwords WHERE compromised_at IS NOT NULL ORDER BY compromised_at DESC, id DESC",
+                    SUMMARY_COLUMNS
+                ),
                 (),
             )
             .await
-            .map_err(|e| DatabaseError::Query(format!("Failed to get passwords: {}", e)))?;
+            .map_err(|e| DatabaseError::Query(format!("Failed to list compromised passwords: {}", e)))?;
 
-        let mut passwords = Vec::new();
+        let mut summaries = Vec::new();
         while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
-            passwords.push(PasswordEntry {
-                id: Some(row.get::<i64>(0)?),
+            summaries.push(Self::summary_from_row(&row)?);
+        }
+
+        Ok(summaries)
+    }
+
+    pub async fn count(conn: &Connection, options: &ListOptions) -> Result<i64> {
+        let mut rows = conn
+            .query(
+                &format!("SELECT COUNT(*) FROM passwords WHERE {}", LIST_FILTER),
+                (options.kind.map(|k| k.as_str()), options.favorites_only, options.compromised_only),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to count passwords: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read count row: {}", e)))? {
+            Some(row) => Ok(row.get::<i64>(0)?),
+            None => Ok(0),
+        }
+    }
+
+    /// How many entries store exactly this password.
+    pub async fn count_with_password(conn: &Connection, password: &str) -> Result<i64> {
+        let mut rows = conn
+            .query("SELECT COUNT(*) FROM passwords WHERE password = ?", [password])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to count passwords: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read count row: {}", e)))? {
+            Some(row) => Ok(row.get::<i64>(0)?),
+            None => Ok(0),
+        }
+    }
+
+    /// Days since each entry's password was set, by entry id.
+    pub async fn password_ages(conn: &Connection) -> Result<HashMap<i64, f64>> {
+        let mut rows = conn
+            .query(&format!("SELECT id, {} FROM passwords", PASSWORD_AGE_SQL), ())
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get password ages: {}", e)))?;
+
+        let mut ages = HashMap::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password age row: {}", e)))? {
+            ages.insert(row.get::<i64>(0)?, row.get::<Option<f64>>(1)?.unwrap_or_default());
+        }
+
+        Ok(ages)
+    }
+
+    /// Entries whose password falls due between `from_days` and `until_days` from now (negative
+    /// for the past; no lower bound when `from_days` is `None`), soonest first. An entry is due on
+    /// its expiry date, or else its rotation interval, or its folder's, after the password was set.
+    pub async fn list_due(conn: &Connection, from_days: Option<f64>, until_days: f64) -> Result<Vec<ExpiringEntry>> {
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT id, website, username, item_type, folder, due_at, julianday(due_at) - julianday('now') AS days_left,
+                            expires_on IS NOT NULL, rotation_days IS NOT NULL
+                     FROM (
+                         SELECT id, website, username, item_type, folder, expires_on, rotation_days,
+                                CASE WHEN expires_on IS NOT NULL THEN datetime(expires_on)
+                                     ELSE datetime({}, '+' || COALESCE(rotation_days, {}) || ' days') END AS due_at
+                         FROM passwords
+                     )
+                     WHERE due_at IS NOT NULL
+                       AND (?1 IS NULL OR julianday(due_at) - julianday('now') >= ?1)
+                       AND julianday(due_at) - julianday('now') < ?2
+                     ORDER BY due_at, id",
+                    password_set_at!(),
+                    FOLDER_ROTATION_SQL
+                ),
+                (from_days, until_days),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to list due passwords: {}", e)))?;
+
+        let mut due = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            let item_type = row.get::<String>(3)?;
+            let source = if row.get::<bool>(7)? {
+                ExpirySource::Date
+            } else if row.get::<bool>(8)? {
+                ExpirySource::Entry
+            } else {
+                ExpirySource::Folder
+            };
+            due.push(ExpiringEntry {
+                entry_id: row.get::<i64>(0)?,
                 website: row.get::<String>(1)?,
                 username: row.get::<String>(2)?,
-                password: row.get::<String>(3)?,
-                notes: row.get::<Option<String>>(4)?,
-                created_at: row.get::<Option<String>>(5)?,
-                updated_at: row.get::<Option<String>>(6)?,
+                kind: ItemKind::parse(&item_type)
+                    .ok_or_else(|| DatabaseError::Query(format!("Unknown item type '{}'", item_type)))?,
+                folder: row.get::<Option<String>>(4)?,
+                due_at: row.get::<String>(5)?,
+                days_left: row.get::<f64>(6)?.floor() as i64,
+                source,
             });
         }
 
-        Ok(passwords)
+        Ok(due)
+    }
+
+    pub async fn get_password(conn: &Connection, id: i64) -> Result<Option<String>> {
+        let mut rows = conn
+            .query("SELECT password FROM passwords WHERE id = ?", [id])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get password: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            Some(row) => Ok(Some(row.get::<String>(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records that the entry was just used; doesn't count as a change to `updated_at`.
+    /// Returns false if there is no such entry.
+    pub async fn record_usage(conn: &Connection, id: i64) -> Result<bool> {
+        let updated = conn
+            .execute(
+                "UPDATE passwords SET last_used_at = CURRENT_TIMESTAMP, use_count = use_count + 1 WHERE id = ?",
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to record usage: {}", e)))?;
+
+        Ok(updated > 0)
+    }
+
+    /// Flags the entry's current password as compromised, replacing any earlier flag.
+    pub async fn mark_compromised(conn: &Connection, id: i64, reason: Option<&str>) -> Result<()> {
+        conn.execute(
+            "UPDATE passwords SET compromised_at = CURRENT_TIMESTAMP, compromised_reason = ? WHERE id = ?",
+            (reason, id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to flag password as compromised: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn clear_compromised(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute(
+            "UPDATE passwords SET compromised_at = NULL, compromised_reason = NULL WHERE id = ?",
+            [id],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to clear compromised flag: {}", e)))?;
+
+        Ok(())
     }
 
     pub async fn get_by_id(conn: &Connection, id: i64) -> Result<Option<PasswordEntry>> {
         let mut rows = conn
             .query(
-                "SELECT id, website, username, password, notes, created_at, updated_at FROM passwords WHERE id = ?",
+                &format!("SELECT {} FROM passwords WHERE id = ?", ENTRY_COLUMNS),
                 [id],
             )
             .await
             .map_err(|e| DatabaseError::Query(format!("Failed to get password by id: {}", e)))?;
 
         if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
-            Ok(Some(PasswordEntry {
-                id: Some(row.get::<i64>(0)?),
-                website: row.get::<String>(1)?,
-                username: row.get::<String>(2)?,
-                password: row.get::<String>(3)?,
-                notes: row.get::<Option<String>>(4)?,
-                created_at: row.get::<Option<String>>(5)?,
-                updated_at: row.get::<Option<String>>(6)?,
-            }))
+            Ok(Some(Self::from_row(&row)?))
         } else {
             Ok(None)
         }
     }
 
     pub async fn update(conn: &Connection, id: i64, entry: &PasswordEntry) -> Result<()> {
+        let item_data = Self::encode_details(entry)?;
+        let custom_fields = Self::encode_custom_fields(entry)?;
+        let tags = encode_tags(&entry.tags)?;
         conn.execute(
-            "UPDATE passwords SET website = ?, username = ?, password = ?, notes = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            &format!(
+                "UPDATE passwords SET {}, {}, website = ?2, username = ?3, password = ?1, notes = ?4, item_type = ?5, item_data = ?6,
+                 item_data_version = ?7, template_id = ?8, custom_fields = ?9, folder = ?10, tags = ?11, favorite = ?12,
+                 expires_on = ?13, rotation_days = ?14, updated_at = CURRENT_TIMESTAMP WHERE id = ?15",
+                CLEAR_COMPROMISED_ON_CHANGE,
+                STAMP_PASSWORD_CHANGE
+            ),
             (
+                entry.password.as_str(),
                 entry.website.as_str(),
                 entry.username.as_str(),
-                entry.password.as_str(),
                 entry.notes.as_deref(),
+                entry.kind().as_str(),
+                item_data.as_deref(),
+                item_data.as_ref().map(|_| ITEM_DATA_VERSION),
+                entry.template_id,
+                custom_fields.as_deref(),
+                entry.folder.as_deref(),
+                tags.as_deref(),
+                entry.favorite,
+                entry.expires_on.as_deref(),
+                entry.rotation_days,
                 id,
             ),
         )
@@ -87,6 +366,22 @@ impl PasswordRepository {
         Ok(())
     }
 
+    /// Replaces just the password, as when a rotated password is confirmed.
+    pub async fn set_password(conn: &Connection, id: i64, password: &str) -> Result<()> {
+        conn.execute(
+            &format!(
+                "UPDATE passwords SET {}, {}, password = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                CLEAR_COMPROMISED_ON_CHANGE,
+                STAMP_PASSWORD_CHANGE
+            ),
+            (password, id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to update password: {}", e)))?;
+
+        Ok(())
+    }
+
     pub async fn delete(conn: &Connection, id: i64) -> Result<()> {
         conn.execute("DELETE FROM passwords WHERE id = ?", [id])
             .await
@@ -95,31 +390,208 @@ impl PasswordRepository {
         Ok(())
     }
 
-    pub async fn search(conn: &Connection, query: &str) -> Result<Vec<PasswordEntry>> {
+    pub async fn search(conn: &Connection, query: &str, kind: Option<ItemKind>) -> Result<Vec<PasswordEntry>> {
         let search_pattern = format!("%{}%", query);
         let mut rows = conn
             .query(
-                "SELECT id, website, username, password, notes, created_at, updated_at FROM passwords 
-                 WHERE website LIKE ? OR username LIKE ? 
-                 ORDER BY created_at DESC",
-                (search_pattern.as_str(), search_pattern.as_str()),
+                &format!(
+                    "SELECT {} FROM passwords
+                     WHERE (website LIKE ?1 OR username LIKE ?1) AND (?2 IS NULL OR item_type = ?2)
+                     ORDER BY created_at DESC",
+                    ENTRY_COLUMNS
+                ),
+                (search_pattern.as_str(), kind.map(|k| k.as_str())),
             )
             .await
             .map_err(|e| DatabaseError::Query(format!("Failed to search passwords: {}", e)))?;
 
         let mut passwords = Vec::new();
         while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
-            passwords.push(PasswordEntry {
-                id: Some(row.get::<i64>(0)?),
+            passwords.push(Self::from_row(&row)?);
+        }
+
+        Ok(passwords)
+    }
+
+    pub async fn search_fields(conn: &Connection, kind: Option<ItemKind>) -> Result<Vec<EntrySearchFields>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, website, username,
+                        (SELECT uri FROM entry_uris WHERE entry_id = passwords.id ORDER BY position LIMIT 1),
+                        folder, tags, favorite, updated_at
+                 FROM passwords WHERE (?1 IS NULL OR item_type = ?1)",
+                [kind.map(|k| k.as_str())],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get password search fields: {}", e)))?;
+
+        let mut candidates = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            candidates.push(EntrySearchFields {
+                id: row.get::<i64>(0)?,
                 website: row.get::<String>(1)?,
                 username: row.get::<String>(2)?,
-                password: row.get::<String>(3)?,
-                notes: row.get::<Option<String>>(4)?,
-                created_at: row.get::<Option<String>>(5)?,
-                updated_at: row.get::<Option<String>>(6)?,
+                url: row.get::<Option<String>>(3)?,
+                folder: row.get::<Option<String>>(4)?,
+                tags: decode_tags(row.get::<Option<String>>(5)?)?,
+                favorite: row.get::<bool>(6)?,
+                updated_at: row.get::<Option<String>>(7)?,
             });
         }
 
+        Ok(candidates)
+    }
+
+    /// The entries with the given ids, in the order the ids are given.
+    pub async fn get_by_ids(conn: &Connection, ids: &[i64]) -> Result<Vec<PasswordEntry>> {
+        let ids_json =
+            serde_json::to_string(ids).map_err(|e| DatabaseError::Query(format!("Failed to encode entry ids: {}", e)))?;
+        let mut rows = conn
+            .query(
+                &format!("SELECT {} FROM passwords WHERE id IN (SELECT value FROM json_each(?))", ENTRY_COLUMNS),
+                [ids_json],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get passwords by id: {}", e)))?;
+
+        let mut by_id = HashMap::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            let entry = Self::from_row(&row)?;
+            by_id.insert(entry.id, entry);
+        }
+
+        Ok(ids.iter().filter_map(|id| by_id.remove(&Some(*id))).collect())
+    }
+
+    /// Entries matching a WHERE clause compiled by the vault query language.
+    pub async fn query(conn: &Connection, filter: &str, params: Vec<Value>) -> Result<Vec<PasswordEntry>> {
+        let mut rows = conn
+            .query(
+                &format!("SELECT {} FROM passwords WHERE {} ORDER BY created_at DESC", ENTRY_COLUMNS, filter),
+                params,
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to query passwords: {}", e)))?;
+
+        let mut passwords = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read password row: {}", e)))? {
+            passwords.push(Self::from_row(&row)?);
+        }
+
         Ok(passwords)
     }
+
+    fn from_row(row: &Row) -> Result<PasswordEntry> {
+        let details = match row.get::<Option<String>>(8)? {
+            Some(data) => Some(Self::decode_details(&data, row.get::<Option<i64>>(9)?)?),
+            None => None,
+        };
+
+        Ok(PasswordEntry {
+            id: Some(row.get::<i64>(0)?),
+            website: row.get::<String>(1)?,
+            username: row.get::<String>(2)?,
+            password: row.get::<String>(3)?,
+            notes: row.get::<Option<String>>(4)?,
+            created_at: row.get::<Option<String>>(5)?,
+            updated_at: row.get::<Option<String>>(6)?,
+            details,
+            template_id: row.get::<Option<i64>>(10)?,
+            custom_fields: match row.get::<Option<String>>(11)? {
+                Some(data) => serde_json::from_str::<Vec<CustomField>>(&data)
+                    .map_err(|e| DatabaseError::Query(format!("Failed to decode custom fields: {}", e)))?,
+                None => Vec::new(),
+            },
+            uris: decode_uris(&row.get::<String>(21)?)?,
+            folder: row.get::<Option<String>>(12)?,
+            tags: decode_tags(row.get::<Option<String>>(13)?)?,
+            favorite: row.get::<bool>(14)?,
+            last_used_at: row.get::<Option<String>>(15)?,
+            expires_on: row.get::<Option<String>>(16)?,
+            rotation_days: row.get::<Option<u32>>(17)?,
+            compromised_at: row.get::<Option<String>>(18)?,
+            compromised_reason: row.get::<Option<String>>(19)?,
+            use_count: row.get::<u32>(20)?,
+        })
+    }
+
+    fn summary_from_row(row: &Row) -> Result<PasswordSummary> {
+        let item_type = row.get::<String>(3)?;
+        Ok(PasswordSummary {
+            id: row.get::<i64>(0)?,
+            website: row.get::<String>(1)?,
+            username: row.get::<String>(2)?,
+            kind: ItemKind::parse(&item_type).ok_or_else(|| DatabaseError::Query(format!("Unknown item type '{}'", item_type)))?,
+            folder: row.get::<Option<String>>(4)?,
+            tags: decode_tags(row.get::<Option<String>>(5)?)?,
+            favorite: row.get::<bool>(6)?,
+            created_at: row.get::<Option<String>>(7)?,
+            updated_at: row.get::<Option<String>>(8)?,
+            last_used_at: row.get::<Option<String>>(9)?,
+            compromised_at: row.get::<Option<String>>(10)?,
+            compromised_reason: row.get::<Option<String>>(11)?,
+            use_count: row.get::<u32>(12)?,
+            uris: decode_uris(&row.get::<String>(13)?)?,
+        })
+    }
+
+    fn encode_details(entry: &PasswordEntry) -> Result<Option<String>> {
+        entry
+            .details
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DatabaseError::Query(format!("Failed to encode item details: {}", e)))
+    }
+
+    fn encode_custom_fields(entry: &PasswordEntry) -> Result<Option<String>> {
+        if entry.custom_fields.is_empty() {
+            return Ok(None);
+        }
+        serde_json::to_string(&entry.custom_fields)
+            .map(Some)
+            .map_err(|e| DatabaseError::Query(format!("Failed to encode custom fields: {}", e)))
+    }
+
+    fn decode_details(data: &str, version: Option<i64>) -> Result<ItemDetails> {
+        match version.unwrap_or(ITEM_DATA_VERSION) {
+            ITEM_DATA_VERSION => serde_json::from_str(data)
+                .map_err(|e| DatabaseError::Query(format!("Failed to decode item details: {}", e))),
+            other => Err(DatabaseError::Query(format!("Unsupported item data version {}", other))),
+        }
+    }
+}
+
+/// ORDER BY for a list sort, with ties broken by id in the same direction.
+pub(crate) fn sort_order(sort: EntrySort, reverse: bool, name_column: &str) -> String {
+    let direction = |natural_desc: bool| if natural_desc != reverse { "DESC" } else { "ASC" };
+    match sort {
+        EntrySort::Name => format!("{} COLLATE NOCASE {1}, id {1}", name_column, direction(false)),
+        EntrySort::Created => format!("created_at {0}, id {0}", direction(true)),
+        EntrySort::Updated => format!("updated_at {0}, id {0}", direction(true)),
+        // Never-used items stay last either way
+        EntrySort::LastUsed => format!("last_used_at IS NULL, last_used_at {0}, id {0}", direction(true)),
+        EntrySort::MostUsed => format!("use_count {0}, last_used_at IS NULL, last_used_at {0}, id {0}", direction(true)),
+    }
+}
+
+fn decode_uris(data: &str) -> Result<Vec<EntryUri>> {
+    serde_json::from_str(data).map_err(|e| DatabaseError::Query(format!("Failed to decode entry URIs: {}", e)))
+}
+
+/// Tags are stored as a JSON array, or NULL when there are none.
+pub(crate) fn encode_tags(tags: &[String]) -> Result<Option<String>> {
+    if tags.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(tags)
+        .map(Some)
+        .map_err(|e| DatabaseError::Query(format!("Failed to encode tags: {}", e)))
+}
+
+pub(crate) fn decode_tags(data: Option<String>) -> Result<Vec<String>> {
+    match data {
+        Some(data) => serde_json::from_str(&data).map_err(|e| DatabaseError::Query(format!("Failed to decode tags: {}", e))),
+        None => Ok(Vec::new()),
+    }
 }
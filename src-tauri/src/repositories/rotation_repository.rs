@@ -0,0 +1,244 @@
+use libsql::{Connection, Row};
+use crate::database::{
+    DatabaseError, GeneratorOptions, Result, RotationItem, RotationItemStatus, RotationLogEntry, RotationSession, RotationSessionSummary,
+    RotationStatus,
+};
+
+pub struct RotationRepository;
+
+impl RotationRepository {
+    pub async fn create_session(conn: &Connection, name: Option<&str>, options: &GeneratorOptions) -> Result<i64> {
+        let options = serde_json::to_string(options)
+            .map_err(|e| DatabaseError::Query(format!("Failed to encode generator options: {}", e)))?;
+        conn.execute(
+            "INSERT INTO rotation_sessions (name, generator_options) VALUES (?, ?)",
+            (name, options.as_str()),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create rotation session: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// The session without its items and log.
+    pub async fn get_session(conn: &Connection, id: i64) -> Result<Option<RotationSession>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, name, status, generator_options, created_at, finished_at FROM rotation_sessions WHERE id = ?",
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get rotation session: {}", e)))?;
+
+        let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation session row: {}", e)))? else {
+            return Ok(None);
+        };
+        let options = row.get::<String>(3)?;
+        Ok(Some(RotationSession {
+            id: row.get::<i64>(0)?,
+            name: row.get::<Option<String>>(1)?,
+            status: session_status(&row, 2)?,
+            generator_options: serde_json::from_str(&options)
+                .map_err(|e| DatabaseError::Query(format!("Failed to decode generator options: {}", e)))?,
+            created_at: row.get::<Option<String>>(4)?,
+            finished_at: row.get::<Option<String>>(5)?,
+            items: Vec::new(),
+            log: Vec::new(),
+        }))
+    }
+
+    /// Every session with per-status item counts, newest first.
+    pub async fn list_sessions(conn: &Connection) -> Result<Vec<RotationSessionSummary>> {
+        let mut rows = conn
+            .query(
+                "SELECT s.id, s.name, s.status, s.created_at, s.finished_at,
+                        COUNT(i.entry_id),
+                        COUNT(CASE WHEN i.status = 'pending' THEN 1 END),
+                        COUNT(CASE WHEN i.status = 'promoted' THEN 1 END),
+                        COUNT(CASE WHEN i.status = 'rolled_back' THEN 1 END)
+                 FROM rotation_sessions s LEFT JOIN rotation_items i ON i.session_id = s.id
+                 GROUP BY s.id ORDER BY s.created_at DESC, s.id DESC",
+                (),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to list rotation sessions: {}", e)))?;
+
+        let mut sessions = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation session row: {}", e)))? {
+            sessions.push(RotationSessionSummary {
+                id: row.get::<i64>(0)?,
+                name: row.get::<Option<String>>(1)?,
+                status: session_status(&row, 2)?,
+                created_at: row.get::<Option<String>>(3)?,
+                finished_at: row.get::<Option<String>>(4)?,
+                total: row.get::<i64>(5)?,
+                pending: row.get::<i64>(6)?,
+                promoted: row.get::<i64>(7)?,
+                rolled_back: row.get::<i64>(8)?,
+            });
+        }
+
+        Ok(sessions)
+    }
+
+    pub async fn set_session_status(conn: &Connection, id: i64, status: RotationStatus) -> Result<()> {
+        conn.execute(
+            "UPDATE rotation_sessions SET status = ?1,
+             finished_at = CASE WHEN ?1 = 'active' THEN NULL ELSE CURRENT_TIMESTAMP END WHERE id = ?2",
+            (status.as_str(), id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to update rotation session: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn add_item(conn: &Connection, session_id: i64, entry_id: i64, pending_password: &str) -> Result<()> {
+        conn.execute(
+            "INSERT INTO rotation_items (session_id, entry_id, pending_password) VALUES (?, ?, ?)",
+            (session_id, entry_id, pending_password),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to add rotation item: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn get_items(conn: &Connection, session_id: i64) -> Result<Vec<RotationItem>> {
+        let mut rows = conn
+            .query(
+                "SELECT i.entry_id, p.website, p.username, i.status, i.updated_at
+                 FROM rotation_items i JOIN passwords p ON p.id = i.entry_id
+                 WHERE i.session_id = ? ORDER BY p.website COLLATE NOCASE, i.entry_id",
+                [session_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get rotation items: {}", e)))?;
+
+        let mut items = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation item row: {}", e)))? {
+            items.push(RotationItem {
+                entry_id: row.get::<i64>(0)?,
+                website: row.get::<String>(1)?,
+                username: row.get::<String>(2)?,
+                status: item_status(&row, 3)?,
+                updated_at: row.get::<Option<String>>(4)?,
+            });
+        }
+
+        Ok(items)
+    }
+
+    /// An item's status and staged password.
+    pub async fn get_item(conn: &Connection, session_id: i64, entry_id: i64) -> Result<Option<(RotationItemStatus, Option<String>)>> {
+        let mut rows = conn
+            .query(
+                "SELECT status, pending_password FROM rotation_items WHERE session_id = ? AND entry_id = ?",
+                (session_id, entry_id),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get rotation item: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation item row: {}", e)))? {
+            Some(row) => Ok(Some((item_status(&row, 0)?, row.get::<Option<String>>(1)?))),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_item(
+        conn: &Connection,
+        session_id: i64,
+        entry_id: i64,
+        status: RotationItemStatus,
+        pending_password: Option<&str>,
+    ) -> Result<()> {
+        conn.execute(
+            "UPDATE rotation_items SET status = ?, pending_password = ?, updated_at = CURRENT_TIMESTAMP
+             WHERE session_id = ? AND entry_id = ?",
+            (status.as_str(), pending_password, session_id, entry_id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to update rotation item: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Entries among `entry_ids` that already have a password staged in an active session.
+    pub async fn pending_entries(conn: &Connection, entry_ids: &[i64]) -> Result<Vec<i64>> {
+        let ids = serde_json::to_string(entry_ids)
+            .map_err(|e| DatabaseError::Query(format!("Failed to encode entry ids: {}", e)))?;
+        let mut rows = conn
+            .query(
+                "SELECT DISTINCT i.entry_id FROM rotation_items i JOIN rotation_sessions s ON s.id = i.session_id
+                 WHERE s.status = 'active' AND i.status = 'pending' AND i.entry_id IN (SELECT value FROM json_each(?))",
+                [ids],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to check rotation items: {}", e)))?;
+
+        let mut pending = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation item row: {}", e)))? {
+            pending.push(row.get::<i64>(0)?);
+        }
+
+        Ok(pending)
+    }
+
+    pub async fn count_pending(conn: &Connection, session_id: i64) -> Result<i64> {
+        let mut rows = conn
+            .query(
+                "SELECT COUNT(*) FROM rotation_items WHERE session_id = ? AND status = 'pending'",
+                [session_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to count rotation items: {}", e)))?;
+
+        match rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read count row: {}", e)))? {
+            Some(row) => Ok(row.get::<i64>(0)?),
+            None => Ok(0),
+        }
+    }
+
+    pub async fn add_log(conn: &Connection, session_id: i64, entry_id: Option<i64>, message: &str) -> Result<()> {
+        conn.execute(
+            "INSERT INTO rotation_log (session_id, entry_id, message) VALUES (?, ?, ?)",
+            (session_id, entry_id, message),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to write rotation log: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn get_log(conn: &Connection, session_id: i64) -> Result<Vec<RotationLogEntry>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, entry_id, message, created_at FROM rotation_log WHERE session_id = ? ORDER BY id",
+                [session_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get rotation log: {}", e)))?;
+
+        let mut log = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read rotation log row: {}", e)))? {
+            log.push(RotationLogEntry {
+                id: row.get::<i64>(0)?,
+                entry_id: row.get::<Option<i64>>(1)?,
+                message: row.get::<String>(2)?,
+                created_at: row.get::<Option<String>>(3)?,
+            });
+        }
+
+        Ok(log)
+    }
+}
+
+fn session_status(row: &Row, index: i32) -> Result<RotationStatus> {
+    let status = row.get::<String>(index)?;
+    RotationStatus::parse(&status).ok_or_else(|| DatabaseError::Query(format!("Unknown rotation status '{}'", status)))
+}
+
+fn item_status(row: &Row, index: i32) -> Result<RotationItemStatus> {
+    let status = row.get::<String>(index)?;
+    RotationItemStatus::parse(&status).ok_or_else(|| DatabaseError::Query(format!("Unknown rotation item status '{}'", status)))
+}
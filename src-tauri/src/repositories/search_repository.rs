@@ -0,0 +1,135 @@
+use libsql::Connection;
+use crate::database::{DatabaseError, Result, SearchHit, SearchHitKind, SnippetSegment, UsedItem};
+
+/// Private-use characters FTS5 wraps around matched terms in snippets (`char(57344)` and `char(57345)` below).
+const HIGHLIGHT_START: char = '\u{E000}';
+const HIGHLIGHT_END: char = '\u{E001}';
+
+/// The fields fuzzy search scores, for every entry and note.
+pub struct SearchCandidate {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    pub title: String,
+    pub username: Option<String>,
+    pub updated_at: Option<String>,
+    pub favorite: bool,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Runs an FTS5 MATCH expression against entries and notes, best hits first.
+    /// Website and title matches weigh more than usernames, which weigh more than note text.
+    pub async fn search(conn: &Connection, match_query: &str, limit: u32) -> Result<Vec<SearchHit>> {
+        let mut rows = conn
+            .query(
+                "SELECT 'entry', p.id, p.website, p.username,
+                        snippet(passwords_fts, -1, char(57344), char(57345), '…', 12),
+                        bm25(passwords_fts, 10.0, 5.0, 1.0) AS rank
+                 FROM passwords_fts JOIN passwords p ON p.id = passwords_fts.rowid
+                 WHERE passwords_fts MATCH ?1
+                 UNION ALL
+                 SELECT 'note', n.id, n.title, NULL,
+                        snippet(notes_fts, -1, char(57344), char(57345), '…', 12),
+                        bm25(notes_fts, 10.0, 1.0) AS rank
+                 FROM notes_fts JOIN notes n ON n.id = notes_fts.rowid
+                 WHERE notes_fts MATCH ?1
+                 ORDER BY rank
+                 LIMIT ?2",
+                (match_query, limit),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to search vault: {}", e)))?;
+
+        let mut hits = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read search row: {}", e)))? {
+            hits.push(SearchHit {
+                kind: if row.get::<String>(0)? == "note" { SearchHitKind::Note } else { SearchHitKind::Entry },
+                id: row.get::<i64>(1)?,
+                title: row.get::<String>(2)?,
+                username: row.get::<Option<String>>(3)?,
+                snippet: Self::split_snippet(&row.get::<String>(4)?),
+                score: -row.get::<f64>(5)?, // bm25 is lower for better matches
+            });
+        }
+
+        Ok(hits)
+    }
+
+    pub async fn get_candidates(conn: &Connection) -> Result<Vec<SearchCandidate>> {
+        let mut rows = conn
+            .query(
+                "SELECT 'entry', id, website, username, updated_at, favorite FROM passwords
+                 UNION ALL
+                 SELECT 'note', id, title, NULL, updated_at, favorite FROM notes",
+                (),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get search candidates: {}", e)))?;
+
+        let mut candidates = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read search row: {}", e)))? {
+            candidates.push(SearchCandidate {
+                kind: if row.get::<String>(0)? == "note" { SearchHitKind::Note } else { SearchHitKind::Entry },
+                id: row.get::<i64>(1)?,
+                title: row.get::<String>(2)?,
+                username: row.get::<Option<String>>(3)?,
+                updated_at: row.get::<Option<String>>(4)?,
+                favorite: row.get::<bool>(5)?,
+            });
+        }
+
+        Ok(candidates)
+    }
+
+    /// Entries and notes that have been used at least once, most used first.
+    pub async fn frequently_used(conn: &Connection, limit: u32) -> Result<Vec<UsedItem>> {
+        let mut rows = conn
+            .query(
+                "SELECT 'entry', id, website, username, favorite, use_count, last_used_at FROM passwords WHERE use_count > 0
+                 UNION ALL
+                 SELECT 'note', id, title, NULL, favorite, use_count, last_used_at FROM notes WHERE use_count > 0
+                 ORDER BY use_count DESC, last_used_at DESC
+                 LIMIT ?",
+                [limit],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get frequently used items: {}", e)))?;
+
+        let mut items = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read usage row: {}", e)))? {
+            items.push(UsedItem {
+                kind: if row.get::<String>(0)? == "note" { SearchHitKind::Note } else { SearchHitKind::Entry },
+                id: row.get::<i64>(1)?,
+                title: row.get::<String>(2)?,
+                username: row.get::<Option<String>>(3)?,
+                favorite: row.get::<bool>(4)?,
+                use_count: row.get::<u32>(5)?,
+                last_used_at: row.get::<Option<String>>(6)?,
+            });
+        }
+
+        Ok(items)
+    }
+
+    fn split_snippet(snippet: &str) -> Vec<SnippetSegment> {
+        let mut segments = Vec::new();
+        let mut current = String::new();
+        for c in snippet.chars() {
+            if c == HIGHLIGHT_START || c == HIGHLIGHT_END {
+                if !current.is_empty() {
+                    segments.push(SnippetSegment {
+                        text: std::mem::take(&mut current),
+                        highlight: c == HIGHLIGHT_END,
+                    });
+                }
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            segments.push(SnippetSegment { text: current, highlight: false });
+        }
+        segments
+    }
+}
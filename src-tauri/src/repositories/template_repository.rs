@@ -0,0 +1,104 @@
+use libsql::{Connection, Row};
+use crate::database::{DatabaseError, EntryTemplate, Result, TemplateField};
+
+pub struct TemplateRepository;
+
+impl TemplateRepository {
+    pub async fn create(conn: &Connection, template: &EntryTemplate) -> Result<i64> {
+        let fields = Self::encode_fields(&template.fields)?;
+        conn.execute(
+            "INSERT INTO entry_templates (name, description, fields) VALUES (?, ?, ?)",
+            (template.name.as_str(), template.description.as_deref(), fields.as_str()),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to create template: {}", e)))?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub async fn get_all(conn: &Connection) -> Result<Vec<EntryTemplate>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, name, description, fields, created_at, updated_at FROM entry_templates ORDER BY name COLLATE NOCASE",
+                (),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get templates: {}", e)))?;
+
+        let mut templates = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read template row: {}", e)))? {
+            templates.push(Self::from_row(&row)?);
+        }
+
+        Ok(templates)
+    }
+
+    pub async fn get_by_id(conn: &Connection, id: i64) -> Result<Option<EntryTemplate>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, name, description, fields, created_at, updated_at FROM entry_templates WHERE id = ?",
+                [id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get template by id: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read template row: {}", e)))? {
+            Ok(Some(Self::from_row(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn get_by_name(conn: &Connection, name: &str) -> Result<Option<EntryTemplate>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, name, description, fields, created_at, updated_at FROM entry_templates WHERE name = ?",
+                [name],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to get template by name: {}", e)))?;
+
+        if let Some(row) = rows.next().await.map_err(|e| DatabaseError::Query(format!("Failed to read template row: {}", e)))? {
+            Ok(Some(Self::from_row(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn update(conn: &Connection, id: i64, template: &EntryTemplate) -> Result<()> {
+        let fields = Self::encode_fields(&template.fields)?;
+        conn.execute(
+            "UPDATE entry_templates SET name = ?, description = ?, fields = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            (template.name.as_str(), template.description.as_deref(), fields.as_str(), id),
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(format!("Failed to update template: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute("DELETE FROM entry_templates WHERE id = ?", [id])
+            .await
+            .map_err(|e| DatabaseError::Query(format!("Failed to delete template: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<EntryTemplate> {
+        let fields = row.get::<String>(3)?;
+        Ok(EntryTemplate {
+            id: Some(row.get::<i64>(0)?),
+            name: row.get::<String>(1)?,
+            description: row.get::<Option<String>>(2)?,
+            fields: serde_json::from_str(&fields)
+                .map_err(|e| DatabaseError::Query(format!("Failed to decode template fields: {}", e)))?,
+            created_at: row.get::<Option<String>>(4)?,
+            updated_at: row.get::<Option<String>>(5)?,
+        })
+    }
+
+    fn encode_fields(fields: &[TemplateField]) -> Result<String> {
+        serde_json::to_string(fields).map_err(|e| DatabaseError::Query(format!("Failed to encode template fields: {}", e)))
+    }
+}
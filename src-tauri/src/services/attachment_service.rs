@@ -0,0 +1,174 @@
+use std::path::Path;
+
+use libsql::Connection;
+use sha2::{Digest, Sha256};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::database::{Attachment, AttachmentParent, DatabaseError, DatabaseManager, Result};
+use crate::repositories::{AttachmentRepository, NotesRepository, PasswordRepository};
+use crate::services::SettingsService;
+
+/// Files are split into chunks of this size so neither import nor export holds a whole file in memory.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+pub struct AttachmentService;
+
+impl AttachmentService {
+    pub async fn add_attachment(db_manager: &DatabaseManager, parent: AttachmentParent, source: &Path) -> Result<Attachment> {
+        let settings = SettingsService::get_settings(db_manager).await?;
+        let conn = db_manager.get_connection().await?;
+        Self::require_parent(&conn, parent).await?;
+
+        let file_name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| DatabaseError::Validation("Attachment path has no file name".to_string()))?;
+
+        let max_bytes = settings.attachment_max_bytes;
+        if tokio::fs::metadata(source).await?.len() > max_bytes {
+            return Err(Self::too_large(max_bytes));
+        }
+
+        let mut file = File::open(source).await?;
+        let tx = conn.transaction().await?;
+        let id = AttachmentRepository::create(&tx, parent, &file_name, guess_mime_type(&file_name)).await?;
+
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut size: u64 = 0;
+        let mut seq = 0;
+        loop {
+            let filled = read_chunk(&mut file, &mut buffer).await?;
+            if filled == 0 {
+                break;
+            }
+
+            // The file may have grown since its metadata was checked
+            size += filled as u64;
+            if size > max_bytes {
+                return Err(Self::too_large(max_bytes));
+            }
+
+            hasher.update(&buffer[..filled]);
+            AttachmentRepository::add_chunk(&tx, id, seq, &buffer[..filled]).await?;
+            seq += 1;
+        }
+
+        let sha256 = hex::encode(hasher.finalize());
+        AttachmentRepository::finalize(&tx, id, size as i64, &sha256).await?;
+        tx.commit().await?;
+
+        Self::require_attachment(&conn, id).await
+    }
+
+    pub async fn get_attachments(db_manager: &DatabaseManager, parent: AttachmentParent) -> Result<Vec<Attachment>> {
+        let conn = db_manager.get_connection().await?;
+        AttachmentRepository::get_for_parent(&conn, parent).await
+    }
+
+    pub async fn get_attachment(db_manager: &DatabaseManager, id: i64) -> Result<Attachment> {
+        let conn = db_manager.get_connection().await?;
+        Self::require_attachment(&conn, id).await
+    }
+
+    /// Streams an attachment to `destination`, checking its SHA-256 on the way.
+    /// A file that fails the integrity check is removed rather than left half-trusted.
+    pub async fn export_attachment(db_manager: &DatabaseManager, id: i64, destination: &Path) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        let attachment = Self::require_attachment(&conn, id).await?;
+
+        let mut file = File::create(destination).await?;
+        let mut hasher = Sha256::new();
+        let mut chunks = AttachmentRepository::get_chunks(&conn, id).await?;
+        while let Some(chunk) = AttachmentRepository::next_chunk(&mut chunks).await? {
+            hasher.update(&chunk);
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        drop(file);
+
+        if hex::encode(hasher.finalize()) != attachment.sha256 {
+            let _ = tokio::fs::remove_file(destination).await;
+            return Err(Self::integrity_failure(&attachment));
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes an attachment's SHA-256 from the stored chunks and compares it with the recorded one.
+    pub async fn verify_attachment(db_manager: &DatabaseManager, id: i64) -> Result<bool> {
+        let conn = db_manager.get_connection().await?;
+        let attachment = Self::require_attachment(&conn, id).await?;
+
+        let mut hasher = Sha256::new();
+        let mut size: i64 = 0;
+        let mut chunks = AttachmentRepository::get_chunks(&conn, id).await?;
+        while let Some(chunk) = AttachmentRepository::next_chunk(&mut chunks).await? {
+            size += chunk.len() as i64;
+            hasher.update(&chunk);
+        }
+
+        Ok(size == attachment.size && hex::encode(hasher.finalize()) == attachment.sha256)
+    }
+
+    pub async fn delete_attachment(db_manager: &DatabaseManager, id: i64) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        AttachmentRepository::delete(&conn, id).await
+    }
+
+    async fn require_parent(conn: &Connection, parent: AttachmentParent) -> Result<()> {
+        let exists = match parent {
+            AttachmentParent::Entry(id) => PasswordRepository::get_by_id(conn, id).await?.is_some(),
+            AttachmentParent::Note(id) => NotesRepository::get_by_id(conn, id).await?.is_some(),
+        };
+
+        if exists {
+            Ok(())
+        } else {
+            Err(DatabaseError::NotFound(format!("Attachment parent {:?}", parent)))
+        }
+    }
+
+    async fn require_attachment(conn: &Connection, id: i64) -> Result<Attachment> {
+        AttachmentRepository::get_by_id(conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Attachment {}", id)))
+    }
+
+    fn too_large(max_bytes: u64) -> DatabaseError {
+        DatabaseError::Validation(format!("Attachment exceeds the size limit of {} bytes", max_bytes))
+    }
+
+    fn integrity_failure(attachment: &Attachment) -> DatabaseError {
+        DatabaseError::Validation(format!("Attachment '{}' failed its integrity check", attachment.file_name))
+    }
+}
+
+/// Fills `buffer` as far as the file allows, returning the number of bytes read (0 at end of file).
+async fn read_chunk(file: &mut File, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let read = file.read(&mut buffer[filled..]).await?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+fn guess_mime_type(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "txt" | "pem" | "pub" | "key" | "asc" => "text/plain",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
@@ -78,21 +78,6 @@ impl AuthService {
         Ok(())
     }
 
-    fn validate_password_strength(&self, password: &str) -> Result<()> {
-        if password.len() < 8 {
-            return Err(DatabaseError::Migration("Password must be at least 8 characters long".to_string()));
-        }
-
-        let has_letter = password.chars().any(|c| c.is_alphabetic());
-        let has_number = password.chars().any(|c| c.is_numeric());
-
-        if !has_letter || !has_number {
-            return Err(DatabaseError::Migration("Password must contain both letters and numbers".to_string()));
-        }
-
-        Ok(())
-    }
-
     fn generate_session_token(&self) -> Result<String> {
         let rng = SystemRandom::new();
         let mut token_bytes = [0u8; 32];
@@ -0,0 +1,63 @@
+use std::cmp::Reverse;
+use url::Url;
+
+use crate::database::{AutofillCandidate, DatabaseError, DatabaseManager, PasswordEntry, Result};
+use crate::repositories::PasswordRepository;
+use crate::services::{match_quality, parse_url};
+
+pub struct AutofillService;
+
+impl AutofillService {
+    /// Entries that can fill the given page, best match first.
+    /// `field_names` are the name/id/autocomplete attributes of the form's inputs, if known.
+    pub async fn get_candidates(
+        db_manager: &DatabaseManager,
+        page_url: &str,
+        field_names: &[String],
+    ) -> Result<Vec<AutofillCandidate>> {
+        let target = parse_url(page_url)
+            .ok_or_else(|| DatabaseError::Validation(format!("'{}' is not a valid URL", page_url)))?;
+        let conn = db_manager.get_connection().await?;
+        let entries = PasswordRepository::get_all(&conn, None).await?;
+        Ok(rank_candidates(&target, field_names, &entries))
+    }
+}
+
+/// Ranks entries for a page: by how closely their best URI matches, then https over a
+/// downgraded http page, then usernames that suit the form, then the most recently used,
+/// and among entries used equally recently (or never) the most recently updated.
+pub fn rank_candidates(target: &Url, field_names: &[String], entries: &[PasswordEntry]) -> Vec<AutofillCandidate> {
+    let wants_email = field_names.iter().any(|name| {
+        let name = name.to_ascii_lowercase();
+        name.contains("email") || name.contains("e-mail")
+    });
+
+    let mut candidates: Vec<AutofillCandidate> = entries
+        .iter()
+        .filter_map(|entry| {
+            let (quality, uri) = entry
+                .uris
+                .iter()
+                .filter_map(|uri| match_quality(uri, target).map(|quality| (quality, uri)))
+                .min_by_key(|(quality, _)| *quality)?;
+            let stored_scheme = parse_url(&uri.uri).map(|url| url.scheme().to_string());
+            Some(AutofillCandidate {
+                entry_id: entry.id?,
+                title: entry.website.clone(),
+                username: entry.username.clone(),
+                matched_uri: uri.uri.clone(),
+                match_quality: quality,
+                insecure: target.scheme() == "http" && stored_scheme.as_deref() == Some("https"),
+                last_used_at: entry.last_used_at.clone(),
+                updated_at: entry.updated_at.clone(),
+            })
+        })
+        .collect();
+
+    // SQLite timestamps (`YYYY-MM-DD HH:MM:SS`) sort correctly as text
+    candidates.sort_by_key(|c| {
+        let username_fits = !wants_email || c.username.contains('@');
+        (c.match_quality, c.insecure, !username_fits, Reverse(c.last_used_at.clone()), Reverse(c.updated_at.clone()))
+    });
+    candidates
+}
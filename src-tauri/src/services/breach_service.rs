@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use ring::digest::{digest, SHA1_FOR_LEGACY_USE_ONLY};
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
+
+use crate::database::{BreachedEntry, DatabaseError, DatabaseManager, PwnedIndexInfo, Result};
+use crate::repositories::PasswordRepository;
+
+/// The index lives next to the vaults; it holds only public hash prefixes, so it isn't encrypted.
+const INDEX_FILE_NAME: &str = "pwned-passwords.idx";
+
+/// Index layout: this magic, the record count as a little-endian u64, then the records.
+/// Each record is the first 8 bytes of a breached password's SHA-1, sorted ascending.
+/// 64 bits per hash keeps the index at 8 bytes per password; with about a billion hashes
+/// in the dump, a false match comes up about once in 10^10 lookups.
+const INDEX_MAGIC: &[u8; 8] = b"VLTPWND1";
+const HEADER_LEN: u64 = 16;
+const RECORD_LEN: u64 = 8;
+
+/// Hex digits of a SHA-1 hash, and of the prefix that names a range file (`21BD1.txt`).
+const SHA1_HEX_LEN: usize = 40;
+const RANGE_PREFIX_LEN: usize = 5;
+
+/// Progress is reported after this many dump lines.
+const PROGRESS_INTERVAL: u64 = 1_000_000;
+
+/// Recorded on entries the breach check flags as compromised.
+pub const BREACH_REASON: &str = "Found in the Have I Been Pwned breached-password list";
+
+pub struct BreachService;
+
+impl BreachService {
+    pub fn index_path() -> Result<PathBuf> {
+        Ok(DatabaseManager::get_vault_directory()?.join(INDEX_FILE_NAME))
+    }
+
+    /// The installed index, if one has been imported.
+    pub async fn index_info() -> Result<Option<PwnedIndexInfo>> {
+        let path = Self::index_path()?;
+        if !tokio::fs::try_exists(&path).await? {
+            return Ok(None);
+        }
+        let mut file = File::open(&path).await?;
+        let hash_count = read_header(&mut file).await?;
+        Ok(Some(PwnedIndexInfo { path: path.to_string_lossy().to_string(), hash_count }))
+    }
+
+    /// Builds the local index from a Have I Been Pwned SHA-1 dump: either a folder of range files
+    /// as written by the official downloader (`00000.txt` … `FFFFF.txt`, lines of `SUFFIX:COUNT`),
+    /// or the single "ordered by hash" file (lines of `HASH:COUNT`). `on_progress` gets the
+    /// number of hashes read so far. The old index stays in place until the new one is complete.
+    pub async fn import_dump(source: &Path, on_progress: impl Fn(u64)) -> Result<PwnedIndexInfo> {
+        let path = Self::index_path()?;
+        let partial = path.with_extension("idx.partial");
+        let mut writer = IndexWriter::create(&partial).await?;
+
+        let imported = if tokio::fs::metadata(source).await?.is_dir() {
+            import_range_files(source, &mut writer, &on_progress).await
+        } else {
+            import_hash_file(source, &mut writer, &on_progress).await
+        };
+        let hash_count = match imported {
+            Ok(()) => writer.finish().await?,
+            Err(e) => {
+                drop(writer);
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(e);
+            }
+        };
+        if hash_count == 0 {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(DatabaseError::Validation(format!(
+                "No SHA-1 hashes found in {}",
+                source.display()
+            )));
+        }
+
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(PwnedIndexInfo { path: path.to_string_lossy().to_string(), hash_count })
+    }
+
+    /// Entries whose password appears in the breach index. Those not already flagged are
+    /// marked compromised; an existing flag keeps its own date and reason.
+    pub async fn check_breached_passwords(db_manager: &DatabaseManager) -> Result<Vec<BreachedEntry>> {
+        let mut index = PwnedIndex::open().await?;
+        let conn = db_manager.get_connection().await?;
+        let entries = PasswordRepository::get_all(&conn, None).await?;
+
+        let mut seen: HashMap<&str, bool> = HashMap::new();
+        let mut breached = Vec::new();
+        for entry in &entries {
+            let Some(entry_id) = entry.id.filter(|_| !entry.password.is_empty()) else {
+                continue;
+            };
+            let found = match seen.get(entry.password.as_str()) {
+                Some(found) => *found,
+                None => {
+                    let found = index.contains(&entry.password).await?;
+                    seen.insert(&entry.password, found);
+                    found
+                }
+            };
+            if found {
+                if entry.compromised_at.is_none() {
+                    PasswordRepository::mark_compromised(&conn, entry_id, Some(BREACH_REASON)).await?;
+                }
+                breached.push(BreachedEntry {
+                    entry_id,
+                    website: entry.website.clone(),
+                    username: entry.username.clone(),
+                });
+            }
+        }
+
+        Ok(breached)
+    }
+
+    /// Whether a single password, such as one about to be saved, appears in the breach index.
+    pub async fn is_password_breached(password: &str) -> Result<bool> {
+        if password.is_empty() {
+            return Ok(false);
+        }
+        PwnedIndex::open().await?.contains(password).await
+    }
+
+    /// `is_password_breached` for a password being saved: without an imported index there is
+    /// nothing to check against, which isn't a reason to refuse the save.
+    pub async fn is_saved_password_breached(password: &str) -> Result<bool> {
+        match Self::is_password_breached(password).await {
+            Err(DatabaseError::NotFound(_)) => Ok(false),
+            result => result,
+        }
+    }
+}
+
+struct PwnedIndex {
+    file: File,
+    hash_count: u64,
+}
+
+impl PwnedIndex {
+    async fn open() -> Result<Self> {
+        let path = BreachService::index_path()?;
+        let mut file = File::open(&path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                DatabaseError::NotFound("Breached-password index; import a Have I Been Pwned dump first".to_string())
+            }
+            _ => DatabaseError::Io(e),
+        })?;
+        let hash_count = read_header(&mut file).await?;
+        Ok(Self { file, hash_count })
+    }
+
+    /// Binary search over the sorted records, one seek per step.
+    async fn contains(&mut self, password: &str) -> Result<bool> {
+        let target = sha1_prefix(password);
+        let (mut low, mut high) = (0, self.hash_count);
+        let mut record = [0u8; RECORD_LEN as usize];
+        while low < high {
+            let middle = low + (high - low) / 2;
+            self.file.seek(SeekFrom::Start(HEADER_LEN + middle * RECORD_LEN)).await?;
+            self.file.read_exact(&mut record).await?;
+            match u64::from_be_bytes(record).cmp(&target) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Ok(true),
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Appends records in ascending order, dropping repeats, and fills in the header at the end.
+struct IndexWriter {
+    out: BufWriter<File>,
+    last: Option<u64>,
+    count: u64,
+}
+
+impl IndexWriter {
+    async fn create(path: &Path) -> Result<Self> {
+        let mut out = BufWriter::new(File::create(path).await?);
+        out.write_all(INDEX_MAGIC).await?;
+        out.write_all(&0u64.to_le_bytes()).await?;
+        Ok(Self { out, last: None, count: 0 })
+    }
+
+    async fn push(&mut self, prefix: u64) -> Result<()> {
+        match self.last {
+            Some(last) if prefix == last => return Ok(()),
+            Some(last) if prefix < last => {
+                return Err(DatabaseError::Validation(
+                    "The dump isn't sorted by hash; use the \"ordered by hash\" download or a folder of range files".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        self.out.write_all(&prefix.to_be_bytes()).await?;
+        self.last = Some(prefix);
+        self.count += 1;
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<u64> {
+        self.out.flush().await?;
+        let mut file = self.out.into_inner();
+        file.seek(SeekFrom::Start(INDEX_MAGIC.len() as u64)).await?;
+        file.write_all(&self.count.to_le_bytes()).await?;
+        file.sync_all().await?;
+        Ok(self.count)
+    }
+}
+
+/// Range files partition the hashes by their first five hex digits, so sorting each file and
+/// taking the files in name order yields one sorted sequence.
+async fn import_range_files(folder: &Path, writer: &mut IndexWriter, on_progress: &impl Fn(u64)) -> Result<()> {
+    let mut ranges = Vec::new();
+    let mut dir = tokio::fs::read_dir(folder).await?;
+    while let Some(item) = dir.next_entry().await? {
+        let path = item.path();
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if stem.len() == RANGE_PREFIX_LEN && stem.chars().all(|c| c.is_ascii_hexdigit()) {
+            ranges.push((stem.to_ascii_uppercase(), path));
+        }
+    }
+    if ranges.is_empty() {
+        return Err(DatabaseError::Validation(format!(
+            "{} has no range files such as 00000.txt",
+            folder.display()
+        )));
+    }
+    ranges.sort();
+
+    let mut read = 0;
+    for (range, path) in &ranges {
+        let mut lines = BufReader::new(File::open(path).await?).lines();
+        let mut prefixes = Vec::new();
+        while let Some(line) = lines.next_line().await? {
+            if let Some(prefix) = parse_dump_line(&line, range) {
+                prefixes.push(prefix);
+            }
+        }
+        prefixes.sort_unstable();
+        for prefix in prefixes {
+            writer.push(prefix).await?;
+            read += 1;
+            if read % PROGRESS_INTERVAL == 0 {
+                on_progress(read);
+            }
+        }
+    }
+    on_progress(read);
+    Ok(())
+}
+
+async fn import_hash_file(source: &Path, writer: &mut IndexWriter, on_progress: &impl Fn(u64)) -> Result<()> {
+    let mut lines = BufReader::new(File::open(source).await?).lines();
+    let mut read = 0;
+    while let Some(line) = lines.next_line().await? {
+        if let Some(prefix) = parse_dump_line(&line, "") {
+            writer.push(prefix).await?;
+            read += 1;
+            if read % PROGRESS_INTERVAL == 0 {
+                on_progress(read);
+            }
+        }
+    }
+    on_progress(read);
+    Ok(())
+}
+
+/// The 64-bit prefix of the SHA-1 on a dump line (`HASH:COUNT`, or `SUFFIX:COUNT` in a range
+/// file named after the first five digits). Blank lines and anything else, such as an NTLM
+/// dump's shorter hashes, give `None`.
+fn parse_dump_line(line: &str, range: &str) -> Option<u64> {
+    let hash = line.split(':').next()?.trim();
+    if range.len() + hash.len() != SHA1_HEX_LEN || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    range
+        .chars()
+        .chain(hash.chars())
+        .take(16)
+        .try_fold(0u64, |prefix, c| Some(prefix << 4 | u64::from(c.to_digit(16)?)))
+}
+
+fn sha1_prefix(password: &str) -> u64 {
+    let hash = digest(&SHA1_FOR_LEGACY_USE_ONLY, password.as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&hash.as_ref()[..8]);
+    u64::from_be_bytes(prefix)
+}
+
+async fn read_header(file: &mut File) -> Result<u64> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    file.read_exact(&mut header).await?;
+    if &header[..8] != INDEX_MAGIC {
+        return Err(DatabaseError::Validation("Not a breached-password index".to_string()));
+    }
+    let mut count = [0u8; 8];
+    count.copy_from_slice(&header[8..]);
+    let hash_count = u64::from_le_bytes(count);
+    if file.metadata().await?.len() < HEADER_LEN + hash_count * RECORD_LEN {
+        return Err(DatabaseError::Validation("The breached-password index is truncated".to_string()));
+    }
+    Ok(hash_count)
+}
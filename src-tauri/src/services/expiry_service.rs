@@ -0,0 +1,42 @@
+use crate::database::{DatabaseError, DatabaseManager, ExpiringEntry, FolderRotation, Result};
+use crate::repositories::{FolderRotationRepository, PasswordRepository};
+use crate::services::{normalize_labels, SettingsService};
+
+pub struct ExpiryService;
+
+impl ExpiryService {
+    /// Entries due within `within_days` (the vault's warning window by default) that haven't expired yet.
+    pub async fn list_expiring_entries(db_manager: &DatabaseManager, within_days: Option<u32>) -> Result<Vec<ExpiringEntry>> {
+        let within_days = match within_days {
+            Some(days) => days,
+            None => SettingsService::get_settings(db_manager).await?.expiry_warning_days,
+        };
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::list_due(&conn, Some(0.0), f64::from(within_days)).await
+    }
+
+    pub async fn list_expired_entries(db_manager: &DatabaseManager) -> Result<Vec<ExpiringEntry>> {
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::list_due(&conn, None, 0.0).await
+    }
+
+    pub async fn get_folder_rotations(db_manager: &DatabaseManager) -> Result<Vec<FolderRotation>> {
+        let conn = db_manager.get_connection().await?;
+        FolderRotationRepository::get_all(&conn).await
+    }
+
+    /// Sets the default rotation interval for a folder and its subfolders, or clears it with `None`.
+    /// Entries with their own interval or expiry date keep it.
+    pub async fn set_folder_rotation(db_manager: &DatabaseManager, folder: &str, rotation_days: Option<u32>) -> Result<()> {
+        let mut folder = Some(folder.to_string());
+        normalize_labels(&mut folder, &mut Vec::new());
+        let folder = folder.ok_or_else(|| DatabaseError::Validation("Folder name cannot be empty".to_string()))?;
+
+        let conn = db_manager.get_connection().await?;
+        match rotation_days {
+            Some(0) => Err(DatabaseError::Validation("Rotation interval must be at least one day".to_string())),
+            Some(days) => FolderRotationRepository::set(&conn, &folder, days).await,
+            None => FolderRotationRepository::delete(&conn, &folder).await,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Scoring constants from fzf's v1 algorithm
+const SCORE_MATCH: i64 = 16;
+const SCORE_GAP_START: i64 = -3;
+const SCORE_GAP_EXTENSION: i64 = -1;
+const BONUS_BOUNDARY: i64 = SCORE_MATCH / 2;
+const BONUS_CAMEL: i64 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i64 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+/// Typo matches ("gihtub" for "github") only count for words at least this long.
+const MIN_TYPO_WORD_LEN: usize = 4;
+
+#[derive(Debug, Clone)]
+struct FuzzyMatch {
+    score: i64,
+    /// Character indices in the text that matched, for highlighting
+    positions: Vec<usize>,
+}
+
+/// Scores a multi-word query against a record's fields. Every word has to match one of
+/// the fields; the first field is the record's name and counts fully, later ones count 3/4.
+/// Returns the total score and the matched positions in each field.
+pub fn fuzzy_match_fields(query: &str, fields: &[&str]) -> Option<(i64, Vec<Vec<usize>>)> {
+    let words: Vec<Vec<char>> = query.split_whitespace().map(fold_pattern).collect();
+    if words.is_empty() {
+        return None;
+    }
+    let fields: Vec<FoldedText> = fields.iter().map(|text| FoldedText::new(text)).collect();
+
+    let mut total = 0;
+    let mut positions = vec![Vec::new(); fields.len()];
+    for word in &words {
+        let (field, best) = fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, text)| {
+                match_folded(word, text).map(|mut m| {
+                    if i > 0 {
+                        m.score = m.score * 3 / 4;
+                    }
+                    (i, m)
+                })
+            })
+            .max_by_key(|(_, m)| m.score)?;
+        total += best.score;
+        positions[field].extend(best.positions);
+    }
+
+    for field_positions in &mut positions {
+        field_positions.sort_unstable();
+        field_positions.dedup();
+    }
+    Some((total, positions))
+}
+
+/// A text with a lowercased copy whose character positions line up with the original.
+struct FoldedText {
+    original: Vec<char>,
+    lowered: Vec<char>,
+}
+
+impl FoldedText {
+    fn new(text: &str) -> Self {
+        let original: Vec<char> = text.chars().collect();
+        let lowered = original.iter().copied().map(fold_case).collect();
+        Self { original, lowered }
+    }
+}
+
+fn fold_pattern(pattern: &str) -> Vec<char> {
+    pattern.chars().map(fold_case).collect()
+}
+
+/// Matches one search word against a text, case-insensitively: first as an fzf-style
+/// subsequence ("gh" in "GitHub"), then, for longer words, as a word in the text within
+/// one or two typos. Typo matches score about half as much as a clean subsequence match.
+fn match_folded(pattern: &[char], text: &FoldedText) -> Option<FuzzyMatch> {
+    if pattern.is_empty() {
+        return None;
+    }
+    subsequence_match(pattern, &text.original, &text.lowered).or_else(|| typo_match(pattern, &text.lowered))
+}
+
+/// fzf v1: find the first window containing the pattern as a subsequence, shrink it
+/// from the right end back to the tightest start, then score that window.
+fn subsequence_match(pattern: &[char], text: &[char], lowered: &[char]) -> Option<FuzzyMatch> {
+    let mut p = 0;
+    let mut end = None;
+    for (i, c) in lowered.iter().enumerate() {
+        if *c == pattern[p] {
+            p += 1;
+            if p == pattern.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    let mut p = pattern.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if lowered[i] == pattern[p - 1] {
+            p -= 1;
+            if p == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut score = 0;
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut p = 0;
+    let mut in_gap = false;
+    let mut consecutive = 0;
+    let mut first_bonus = 0;
+    for (i, c) in lowered.iter().enumerate().take(end + 1).skip(start) {
+        if p < pattern.len() && *c == pattern[p] {
+            let mut bonus = char_bonus(text, i);
+            if consecutive == 0 {
+                first_bonus = bonus;
+            } else {
+                // A run keeps the bonus of the boundary it started on
+                if bonus == BONUS_BOUNDARY {
+                    first_bonus = bonus;
+                }
+                bonus = bonus.max(first_bonus).max(BONUS_CONSECUTIVE);
+            }
+            score += SCORE_MATCH + if p == 0 { bonus * BONUS_FIRST_CHAR_MULTIPLIER } else { bonus };
+            positions.push(i);
+            p += 1;
+            consecutive += 1;
+            in_gap = false;
+        } else {
+            score += if in_gap { SCORE_GAP_EXTENSION } else { SCORE_GAP_START };
+            in_gap = true;
+            consecutive = 0;
+            first_bonus = 0;
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Compares the pattern with the start of each word in the (lowercased) text, allowing one
+/// typo (two for patterns of eight or more characters); transposed letters count as one.
+fn typo_match(pattern: &[char], text: &[char]) -> Option<FuzzyMatch> {
+    if pattern.len() < MIN_TYPO_WORD_LEN {
+        return None;
+    }
+    let allowed = if pattern.len() >= 8 { 2 } else { 1 };
+
+    let mut best: Option<(usize, usize, usize)> = None; // (distance, word start, matched length)
+    let mut i = 0;
+    while i < text.len() {
+        if !text[i].is_alphanumeric() {
+            i += 1;
+            continue;
+        }
+        let word_start = i;
+        while i < text.len() && text[i].is_alphanumeric() {
+            i += 1;
+        }
+
+        // Compare against prefixes around the pattern's length, so "gihtub" finds "github" in "githubusercontent"
+        let longest = (pattern.len() + allowed).min(i - word_start);
+        let shortest = pattern.len().saturating_sub(allowed).max(1);
+        if longest < shortest {
+            continue;
+        }
+        // Every pattern character missing from the word needs its own edit; skip hopeless words cheaply
+        let window = &text[word_start..word_start + longest];
+        if pattern.iter().filter(|c| !window.contains(c)).count() > allowed {
+            continue;
+        }
+        let distances = prefix_distances(pattern, window);
+        for (len, distance) in distances.into_iter().enumerate().skip(shortest) {
+            if distance <= allowed && best.is_none_or(|(d, _, _)| distance < d) {
+                best = Some((distance, word_start, len));
+            }
+        }
+    }
+
+    let (distance, start, len) = best?;
+    Some(FuzzyMatch {
+        score: (SCORE_MATCH * pattern.len() as i64 / 2 - SCORE_MATCH * distance as i64).max(1),
+        positions: (start..start + len).collect(),
+    })
+}
+
+/// Optimal string alignment distance from the pattern to every prefix of `word`
+/// (index = prefix length), from a single dynamic-programming table.
+fn prefix_distances(pattern: &[char], word: &[char]) -> Vec<usize> {
+    let width = word.len() + 1;
+    let mut table = vec![0usize; (pattern.len() + 1) * width];
+    for (j, cell) in table.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+    for i in 1..=pattern.len() {
+        table[i * width] = i;
+        for j in 1..width {
+            let cost = usize::from(pattern[i - 1] != word[j - 1]);
+            let mut best = (table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1)
+                .min(table[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && pattern[i - 1] == word[j - 2] && pattern[i - 2] == word[j - 1] {
+                best = best.min(table[(i - 2) * width + j - 2] + 1);
+            }
+            table[i * width + j] = best;
+        }
+    }
+    table.split_off(pattern.len() * width)
+}
+
+/// Bonus for matching at a position: the start of the text or of a word, or a camelCase hump.
+fn char_bonus(text: &[char], i: usize) -> i64 {
+    let Some(previous) = i.checked_sub(1).map(|p| text[p]) else {
+        return BONUS_BOUNDARY;
+    };
+    let current = text[i];
+    if !previous.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if (previous.is_lowercase() && current.is_uppercase()) || (!previous.is_numeric() && current.is_numeric()) {
+        BONUS_CAMEL
+    } else {
+        0
+    }
+}
+
+/// Lowercases one character into one character, so positions in the folded text line up with the original.
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
@@ -0,0 +1,494 @@
+use std::collections::HashSet;
+use std::path::Path;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::database::{
+    DatabaseError, GeneratedPassword, GeneratorOptions, PassphraseOptions, RepeatRule, Result, WordCase, WordlistInfo,
+    WordlistSource,
+};
+
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const NUMBERS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?~";
+
+/// Symbols for passphrases; leaves out the usual separators so the words stay readable.
+const PASSPHRASE_SYMBOLS: &str = "!@#$%^&*?~";
+
+const BIP39_ENGLISH: &str = include_str!("../../data/bip39_english.txt");
+
+/// Custom wordlists need enough words to be worth using, and shouldn't be whole dictionaries.
+const MIN_WORDLIST_LEN: usize = 100;
+const MAX_WORDLIST_BYTES: u64 = 4 * 1024 * 1024;
+
+const MAX_WORDS: usize = 64;
+
+/// Characters that are easily mistaken for one another in common fonts.
+const AMBIGUOUS: &str = "0Oo1lI|";
+
+const MAX_LENGTH: usize = 1024;
+
+/// `NoAdjacent` passwords are drawn again when a repeat can't be patched within its class.
+const MAX_ATTEMPTS: usize = 1000;
+
+pub struct GeneratorService;
+
+impl GeneratorService {
+    /// Draws uniformly from every password that satisfies the class options; the reported
+    /// entropy is the log2 of the number of such passwords. With `NoAdjacent`, repeated
+    /// neighbours are then redrawn within their class, so the draw is no longer exactly
+    /// uniform and the entropy is an approximation.
+    pub fn generate_password(options: &GeneratorOptions) -> Result<GeneratedPassword> {
+        let classes = character_classes(options);
+        validate(options, &classes)?;
+
+        let unique = options.repeats == RepeatRule::Never;
+        let log_factorials = log_factorials(classes.iter().map(|c| c.chars.len()).sum::<usize>().max(options.length));
+        let table = count_table(&classes, options.length, unique, &log_factorials);
+        let total = table[classes.len()][options.length];
+        if total == f64::NEG_INFINITY {
+            return Err(DatabaseError::Validation(
+                "No password of this length can satisfy these options".to_string(),
+            ));
+        }
+
+        let pool_size = classes.iter().map(|c| c.chars.len()).sum::<usize>() as f64;
+        let entropy_bits = match options.repeats {
+            // Approximate: each character after the first loses one choice. `validate` only lets
+            // a one-character pool through for a single character, which has no neighbours.
+            RepeatRule::NoAdjacent if options.length > 1 => total - (options.length - 1) as f64 * (pool_size / (pool_size - 1.0)).log2(),
+            _ => total,
+        };
+
+        let mut random = Randomness::new();
+        for _ in 0..MAX_ATTEMPTS {
+            let (mut password, labels) = draw(&mut random, &classes, &table, options.length, unique, &log_factorials)?;
+            if options.repeats == RepeatRule::NoAdjacent && !separate_repeats(&mut random, &mut password, &labels, &classes)? {
+                continue;
+            }
+            return Ok(GeneratedPassword {
+                password: password.into_iter().collect(),
+                entropy_bits,
+            });
+        }
+
+        Err(DatabaseError::Validation(
+            "Too few characters to avoid repeating neighbours; allow more characters".to_string(),
+        ))
+    }
+
+    /// Diceware-style passphrase: words drawn uniformly and independently from the wordlist.
+    pub async fn generate_passphrase(options: &PassphraseOptions) -> Result<GeneratedPassword> {
+        if options.word_count == 0 || options.word_count > MAX_WORDS {
+            return Err(DatabaseError::Validation(format!("Word count must be between 1 and {}", MAX_WORDS)));
+        }
+        let words = match &options.wordlist {
+            WordlistSource::Bip39English => parse_wordlist(BIP39_ENGLISH)?,
+            WordlistSource::Custom(path) => read_wordlist(Path::new(path)).await?,
+        };
+
+        let mut random = Randomness::new();
+        let mut chosen = Vec::with_capacity(options.word_count);
+        for _ in 0..options.word_count {
+            let word = &words[random.below(words.len())?];
+            chosen.push(match options.case {
+                WordCase::Lower => word.clone(),
+                WordCase::Upper => word.to_uppercase(),
+                WordCase::Capitalized => capitalize(word),
+            });
+        }
+
+        let mut entropy_bits = options.word_count as f64 * (words.len() as f64).log2();
+        let extras = [(options.include_number, NUMBERS), (options.include_symbol, PASSPHRASE_SYMBOLS)];
+        for (_, alphabet) in extras.into_iter().filter(|(include, _)| *include) {
+            let alphabet: Vec<char> = alphabet.chars().collect();
+            let position = random.below(chosen.len())?;
+            chosen[position].push(alphabet[random.below(alphabet.len())?]);
+            entropy_bits += ((alphabet.len() * chosen.len()) as f64).log2();
+        }
+
+        Ok(GeneratedPassword {
+            password: chosen.join(&options.separator),
+            entropy_bits,
+        })
+    }
+
+    /// Checks that a file can be used as a custom wordlist.
+    pub async fn load_wordlist(path: &Path) -> Result<WordlistInfo> {
+        let words = read_wordlist(path).await?;
+        Ok(WordlistInfo {
+            path: path.to_string_lossy().to_string(),
+            word_count: words.len(),
+            bits_per_word: (words.len() as f64).log2(),
+        })
+    }
+}
+
+async fn read_wordlist(path: &Path) -> Result<Vec<String>> {
+    if tokio::fs::metadata(path).await?.len() > MAX_WORDLIST_BYTES {
+        return Err(DatabaseError::Validation(format!(
+            "Wordlists can be at most {} MB",
+            MAX_WORDLIST_BYTES / (1024 * 1024)
+        )));
+    }
+    let data = tokio::fs::read_to_string(path).await?;
+    let words = parse_wordlist(&data)?;
+    if words.len() < MIN_WORDLIST_LEN {
+        return Err(DatabaseError::Validation(format!(
+            "The wordlist has {} different words; at least {} are needed",
+            words.len(),
+            MIN_WORDLIST_LEN
+        )));
+    }
+    Ok(words)
+}
+
+/// One word per line, lowercased and without duplicates. Lines may start with a dice-roll
+/// column as in the EFF lists (`11111<TAB>abacus`); blank lines and `#` comments are skipped.
+/// Any other line, such as one holding several words, is reported rather than guessed at.
+fn parse_wordlist(data: &str) -> Result<Vec<String>> {
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+    for (index, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let word = match columns.as_slice() {
+            [roll, word] if is_dice_roll(roll) && !is_dice_roll(word) => *word,
+            [word] if !is_dice_roll(word) => *word,
+            _ => {
+                return Err(DatabaseError::Validation(format!(
+                    "Line {} of the wordlist should hold one word, optionally after its dice roll: '{}'",
+                    index + 1,
+                    line
+                )))
+            }
+        };
+        let word = word.to_lowercase();
+        if seen.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+fn is_dice_roll(column: &str) -> bool {
+    !column.is_empty() && column.chars().all(|c| ('1'..='6').contains(&c))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Replaces each character that repeats its left neighbour with another one from the same
+/// class, so the class counts still hold. Returns false when a class has nothing else to offer.
+fn separate_repeats(random: &mut Randomness, password: &mut [char], labels: &[usize], classes: &[CharClass]) -> Result<bool> {
+    for i in 1..password.len() {
+        if password[i] != password[i - 1] {
+            continue;
+        }
+        let next = password.get(i + 1).copied();
+        let options: Vec<char> = classes[labels[i]]
+            .chars
+            .iter()
+            .copied()
+            .filter(|c| *c != password[i - 1] && Some(*c) != next)
+            .collect();
+        if options.is_empty() {
+            return Ok(false);
+        }
+        password[i] = options[random.below(options.len())?];
+    }
+    Ok(true)
+}
+
+struct CharClass {
+    name: &'static str,
+    chars: Vec<char>,
+    min: usize,
+}
+
+/// The enabled classes minus excluded characters, plus a class for the custom
+/// characters that aren't already in one. Classes never share a character.
+fn character_classes(options: &GeneratorOptions) -> Vec<CharClass> {
+    let excluded = |c: &char| options.exclude_chars.contains(*c);
+    let ambiguous = |c: &char| options.exclude_ambiguous && AMBIGUOUS.contains(*c);
+    let standard = [
+        (options.include_uppercase, "uppercase letters", UPPERCASE, options.min_uppercase),
+        (options.include_lowercase, "lowercase letters", LOWERCASE, options.min_lowercase),
+        (options.include_numbers, "numbers", NUMBERS, options.min_numbers),
+        (options.include_symbols, "symbols", SYMBOLS, options.min_symbols),
+    ];
+
+    let mut classes: Vec<CharClass> = standard
+        .into_iter()
+        .filter(|(enabled, ..)| *enabled)
+        .map(|(_, name, chars, min)| CharClass {
+            name,
+            chars: chars
+                .chars()
+                .filter(|c| !excluded(c) && !ambiguous(c))
+                .collect(),
+            min,
+        })
+        .collect();
+
+    let mut custom: Vec<char> = Vec::new();
+    for c in options.include_chars.chars() {
+        let taken = classes.iter().any(|class| class.chars.contains(&c)) || custom.contains(&c);
+        if !c.is_control() && !c.is_whitespace() && !excluded(&c) && !taken {
+            custom.push(c);
+        }
+    }
+    if !custom.is_empty() {
+        classes.push(CharClass { name: "custom characters", chars: custom, min: 0 });
+    }
+    classes
+}
+
+fn validate(options: &GeneratorOptions, classes: &[CharClass]) -> Result<()> {
+    if options.length == 0 || options.length > MAX_LENGTH {
+        return Err(DatabaseError::Validation(format!("Length must be between 1 and {}", MAX_LENGTH)));
+    }
+    let pool_size: usize = classes.iter().map(|c| c.chars.len()).sum();
+    if pool_size == 0 {
+        return Err(DatabaseError::Validation("No characters left to choose from".to_string()));
+    }
+    if let Some(class) = classes.iter().find(|c| c.min > 0 && c.chars.is_empty()) {
+        return Err(DatabaseError::Validation(format!("All {} are excluded", class.name)));
+    }
+
+    let required: usize = classes.iter().map(|c| c.min).sum();
+    if required > options.length {
+        return Err(DatabaseError::Validation(format!(
+            "The minimum counts add up to {}, more than the length of {}",
+            required, options.length
+        )));
+    }
+    if options.repeats == RepeatRule::Never && pool_size < options.length {
+        return Err(DatabaseError::Validation(format!(
+            "Only {} different characters are available for a length of {} without repeats",
+            pool_size, options.length
+        )));
+    }
+    if options.repeats == RepeatRule::NoAdjacent && pool_size == 1 && options.length > 1 {
+        return Err(DatabaseError::Validation("A single character can't avoid repeating itself".to_string()));
+    }
+    Ok(())
+}
+
+/// `table[c][j]` is log2 of the number of ways to fill `j` positions from the first `c`
+/// classes with every class at or above its minimum (without reuse when `unique`).
+fn count_table(classes: &[CharClass], length: usize, unique: bool, log_factorials: &[f64]) -> Vec<Vec<f64>> {
+    let mut table = Vec::with_capacity(classes.len() + 1);
+    let mut base = vec![f64::NEG_INFINITY; length + 1];
+    base[0] = 0.0;
+    table.push(base);
+
+    for class in classes {
+        let previous = table.last().expect("table starts with the base row");
+        let row = (0..=length)
+            .map(|j| log2_sum((class.min..=j).map(|k| placement_weight(class, j, k, unique, log_factorials) + previous[j - k])))
+            .collect();
+        table.push(row);
+    }
+    table
+}
+
+/// log2 of the ways to place `k` characters of a class among `j` positions.
+fn placement_weight(class: &CharClass, j: usize, k: usize, unique: bool, log_factorials: &[f64]) -> f64 {
+    let n = class.chars.len();
+    let choices = if k == 0 {
+        0.0
+    } else if n == 0 || (unique && k > n) {
+        return f64::NEG_INFINITY;
+    } else if unique {
+        log_factorials[n] - log_factorials[n - k]
+    } else {
+        k as f64 * (n as f64).log2()
+    };
+    log_factorials[j] - log_factorials[k] - log_factorials[j - k] + choices
+}
+
+/// Picks how many characters each class gets, weighted by the number of passwords with
+/// those counts, then which positions and characters they are. Also returns each
+/// position's class.
+fn draw(
+    random: &mut Randomness,
+    classes: &[CharClass],
+    table: &[Vec<f64>],
+    length: usize,
+    unique: bool,
+    log_factorials: &[f64],
+) -> Result<(Vec<char>, Vec<usize>)> {
+    let mut labels = Vec::with_capacity(length);
+    let mut remaining = length;
+    for (c, class) in classes.iter().enumerate().rev() {
+        let total = table[c + 1][remaining];
+        let mut target = random.unit()?;
+        let mut count = class.min;
+        for k in class.min..=remaining {
+            let weight = placement_weight(class, remaining, k, unique, log_factorials) + table[c][remaining - k];
+            if weight == f64::NEG_INFINITY {
+                continue;
+            }
+            count = k;
+            let probability = (weight - total).exp2();
+            if target < probability {
+                break;
+            }
+            target -= probability;
+        }
+        labels.extend(std::iter::repeat_n(c, count));
+        remaining -= count;
+    }
+    random.shuffle(&mut labels)?;
+
+    let mut available: Vec<Vec<char>> = classes.iter().map(|c| c.chars.clone()).collect();
+    let mut password = Vec::with_capacity(length);
+    for &class in &labels {
+        let chars = &mut available[class];
+        let i = random.below(chars.len())?;
+        password.push(if unique { chars.swap_remove(i) } else { chars[i] });
+    }
+    Ok((password, labels))
+}
+
+fn log_factorials(max: usize) -> Vec<f64> {
+    let mut table = Vec::with_capacity(max + 1);
+    table.push(0.0);
+    for i in 1..=max {
+        table.push(table[i - 1] + (i as f64).log2());
+    }
+    table
+}
+
+fn log2_sum(values: impl Iterator<Item = f64>) -> f64 {
+    let values: Vec<f64> = values.collect();
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + values.iter().map(|v| (v - max).exp2()).sum::<f64>().log2()
+}
+
+/// Reads the system CSPRNG a block at a time.
+struct Randomness {
+    rng: SystemRandom,
+    buffer: [u8; 256],
+    used: usize,
+}
+
+impl Randomness {
+    fn new() -> Self {
+        Self { rng: SystemRandom::new(), buffer: [0; 256], used: 256 }
+    }
+
+    fn next_u64(&mut self) -> Result<u64> {
+        if self.used + 8 > self.buffer.len() {
+            self.rng
+                .fill(&mut self.buffer)
+                .map_err(|_| DatabaseError::Io(std::io::Error::other("System random number generator failed")))?;
+            self.used = 0;
+        }
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.buffer[self.used..self.used + 8]);
+        self.used += 8;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// A uniform index below `n`, rejecting the values that would bias a plain modulo.
+    fn below(&mut self, n: usize) -> Result<usize> {
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64()?;
+            if value < zone {
+                return Ok((value % n) as usize);
+            }
+        }
+    }
+
+    /// A uniform float in [0, 1).
+    fn unit(&mut self) -> Result<f64> {
+        Ok((self.next_u64()? >> 11) as f64 / (1u64 << 53) as f64)
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) -> Result<()> {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1)?;
+            items.swap(i, j);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wordlist_strips_dice_rolls() {
+        let words = parse_wordlist("# EFF large\n11111\tabacus\n11112\tabdomen\n\n11113 Abdominal\n").unwrap();
+        assert_eq!(words, ["abacus", "abdomen", "abdominal"]);
+    }
+
+    #[test]
+    fn wordlist_takes_plain_words_and_drops_duplicates() {
+        let words = parse_wordlist("apple\nBanana\n  cherry  \napple\nbanana\n").unwrap();
+        assert_eq!(words, ["apple", "banana", "cherry"]);
+    }
+
+    #[test]
+    fn wordlist_reports_lines_it_cannot_parse() {
+        for line in ["ice cream", "11111 ice cream", "11111", "11111 22222", "7 apple"] {
+            let error = parse_wordlist(&format!("apple\n{}\n", line)).unwrap_err().to_string();
+            assert!(error.contains("Line 2"), "{}: {}", line, error);
+        }
+    }
+
+    #[test]
+    fn embedded_wordlist_parses() {
+        assert_eq!(parse_wordlist(BIP39_ENGLISH).unwrap().len(), 2048);
+    }
+
+    fn single_character_options(length: usize) -> GeneratorOptions {
+        GeneratorOptions {
+            length,
+            include_uppercase: false,
+            include_lowercase: false,
+            include_numbers: true,
+            include_symbols: false,
+            min_numbers: 0,
+            exclude_chars: "012345678".to_string(),
+            repeats: RepeatRule::NoAdjacent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn no_adjacent_single_character_has_zero_entropy() {
+        let generated = GeneratorService::generate_password(&single_character_options(1)).unwrap();
+        assert_eq!(generated.password, "9");
+        assert_eq!(generated.entropy_bits, 0.0);
+
+        assert!(GeneratorService::generate_password(&single_character_options(2)).is_err());
+    }
+
+    #[test]
+    fn no_adjacent_entropy_is_finite_and_below_the_unrestricted_count() {
+        let options = GeneratorOptions { length: 16, repeats: RepeatRule::NoAdjacent, ..Default::default() };
+        let restricted = GeneratorService::generate_password(&options).unwrap();
+        let unrestricted =
+            GeneratorService::generate_password(&GeneratorOptions { repeats: RepeatRule::Allow, ..options }).unwrap();
+        assert!(restricted.entropy_bits.is_finite());
+        assert!(restricted.entropy_bits < unrestricted.entropy_bits);
+        assert!(restricted.password.chars().zip(restricted.password.chars().skip(1)).all(|(a, b)| a != b));
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use crate::database::{HealthExportFormat, HealthIssueKind, VaultHealthReport};
+
+/// Renders a health report for filing. Exports list entry names, usernames, issue types and
+/// password ages; they never include passwords or the reused-password hashes.
+pub fn export_health_report(report: &VaultHealthReport, format: HealthExportFormat) -> String {
+    match format {
+        HealthExportFormat::Html => health_report_html(report),
+        HealthExportFormat::Csv => health_report_csv(report),
+    }
+}
+
+pub fn health_export_file_name(format: HealthExportFormat) -> &'static str {
+    match format {
+        HealthExportFormat::Html => "vault-health-report.html",
+        HealthExportFormat::Csv => "vault-health-report.csv",
+    }
+}
+
+/// One row per issue, so the file sorts and filters cleanly in a spreadsheet.
+fn health_report_csv(report: &VaultHealthReport) -> String {
+    let mut csv = String::from("entry_id,name,username,type,issue,detail,password_age_days,remediation\r\n");
+    for item in &report.items {
+        for issue in &item.issues {
+            let fields = [
+                item.entry_id.to_string(),
+                item.website.clone(),
+                item.username.clone(),
+                item.kind.as_str().to_string(),
+                issue_label(issue.kind).to_string(),
+                issue.detail.clone(),
+                item.password_age_days.map(|days| days.to_string()).unwrap_or_default(),
+                issue.remediation.clone(),
+            ];
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push_str("\r\n");
+        }
+    }
+    csv
+}
+
+/// Quotes a field when needed, and defuses values a spreadsheet would run as a formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// A single HTML file with inline styles and no scripts or external resources.
+fn health_report_html(report: &VaultHealthReport) -> String {
+    let counts = &report.counts;
+    let summary = [
+        (HealthIssueKind::Compromised, counts.compromised),
+        (HealthIssueKind::Weak, counts.weak),
+        (HealthIssueKind::Reused, counts.reused),
+        (HealthIssueKind::Old, counts.old),
+        (HealthIssueKind::NoPassword, counts.no_password),
+        (HealthIssueKind::InsecureUrl, counts.insecure_url),
+        (HealthIssueKind::Duplicate, counts.duplicate),
+    ];
+
+    let mut html = String::new();
+    html.push_str(concat!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Vault health report</title>\n<style>\n",
+        "body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2937; }\n",
+        "h1 { margin-bottom: 0.25rem; }\n",
+        ".meta { color: #6b7280; margin-top: 0; }\n",
+        ".score { font-size: 2.5rem; font-weight: 700; }\n",
+        "table { border-collapse: collapse; width: 100%; margin-top: 1rem; }\n",
+        "th, td { border: 1px solid #e5e7eb; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }\n",
+        "th { background: #f3f4f6; }\n",
+        ".summary td:last-child { text-align: right; }\n",
+        "ul { margin: 0; padding-left: 1.1rem; }\n",
+        "</style>\n</head>\n<body>\n",
+    ));
+
+    let _ = writeln!(html, "<h1>Vault health report</h1>");
+    let _ = writeln!(
+        html,
+        "<p class=\"meta\">Generated {} &middot; {} entries &middot; passwords older than {} days count as old</p>",
+        escape_html(&report.generated_at),
+        report.total_entries,
+        report.max_password_age_days
+    );
+    let _ = writeln!(html, "<p class=\"score\">{} / 100</p>", report.score);
+
+    html.push_str("<table class=\"summary\">\n<tr><th>Issue</th><th>Entries</th></tr>\n");
+    for (kind, count) in summary {
+        let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", issue_label(kind), count);
+    }
+    html.push_str("</table>\n");
+
+    if report.items.is_empty() {
+        html.push_str("<p>No issues found.</p>\n");
+    } else {
+        html.push_str("<h2>Entries to fix</h2>\n<table>\n");
+        html.push_str("<tr><th>Name</th><th>Username</th><th>Type</th><th>Password age</th><th>Issues</th><th>Remediation</th></tr>\n");
+        for item in &report.items {
+            let issues: Vec<String> = item
+                .issues
+                .iter()
+                .map(|issue| format!("<li><strong>{}</strong>: {}</li>", issue_label(issue.kind), escape_html(&issue.detail)))
+                .collect();
+            let remediation: Vec<String> =
+                item.issues.iter().map(|issue| format!("<li>{}</li>", escape_html(&issue.remediation))).collect();
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><ul>{}</ul></td><td><ul>{}</ul></td></tr>",
+                escape_html(&item.website),
+                escape_html(&item.username),
+                item.kind.as_str(),
+                item.password_age_days.map(|days| format!("{} days", days)).unwrap_or_default(),
+                issues.join(""),
+                remediation.join("")
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn issue_label(kind: HealthIssueKind) -> &'static str {
+    match kind {
+        HealthIssueKind::NoPassword => "No password",
+        HealthIssueKind::Compromised => "Compromised password",
+        HealthIssueKind::Reused => "Reused password",
+        HealthIssueKind::Weak => "Weak password",
+        HealthIssueKind::Old => "Old password",
+        HealthIssueKind::InsecureUrl => "Insecure address",
+        HealthIssueKind::Duplicate => "Duplicate entry",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, HashMap};
+use sha2::{Digest, Sha256};
+
+use crate::database::{
+    DatabaseManager, HealthCounts, HealthIssue, HealthIssueKind, HealthReportItem, ItemKind, PasswordEntry, PasswordStrength,
+    ReusedPasswordGroup, Result, VaultHealthReport,
+};
+use crate::repositories::PasswordRepository;
+use crate::services::{base_domain, parse_url, utc_timestamp, website_uris, StrengthService};
+
+/// Passwords older than this many days are flagged when the caller doesn't choose a limit.
+pub const DEFAULT_MAX_PASSWORD_AGE_DAYS: u32 = 365;
+
+/// Strength scores below this (zxcvbn's "weak" and "fair") are flagged.
+const MIN_STRENGTH_SCORE: u8 = 3;
+
+pub struct HealthService;
+
+impl HealthService {
+    pub async fn vault_health_report(db_manager: &DatabaseManager, max_age_days: u32) -> Result<VaultHealthReport> {
+        let conn = db_manager.get_connection().await?;
+        let entries = PasswordRepository::get_all(&conn, None).await?;
+        let ages = PasswordRepository::password_ages(&conn).await?;
+        Ok(health_report(&entries, &ages, max_age_days))
+    }
+}
+
+/// The strength estimate of a login's password when it scores as weak. `weak:` queries use
+/// this too, so they agree with the health report.
+pub fn weak_password_strength(entry: &PasswordEntry) -> Option<PasswordStrength> {
+    if entry.kind() != ItemKind::Login || entry.password.is_empty() {
+        return None;
+    }
+    let user_inputs = [entry.website.clone(), entry.username.clone()];
+    let strength = StrengthService::estimate(&entry.password, &user_inputs);
+    (strength.score < MIN_STRENGTH_SCORE).then_some(strength)
+}
+
+/// Audits every entry. Password checks (empty, compromised, weak, reused, old) apply to logins, the only
+/// kind whose `password` is a password; address and duplicate checks apply to every kind.
+pub fn health_report(entries: &[PasswordEntry], ages: &HashMap<i64, f64>, max_age_days: u32) -> VaultHealthReport {
+    let entries: Vec<(i64, &PasswordEntry)> = entries.iter().filter_map(|e| e.id.map(|id| (id, e))).collect();
+
+    let mut by_hash: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    for (id, entry) in &entries {
+        if entry.kind() == ItemKind::Login && !entry.password.is_empty() {
+            by_hash.entry(hex::encode(Sha256::digest(entry.password.as_bytes()))).or_default().push(*id);
+        }
+    }
+    let reused_groups: Vec<ReusedPasswordGroup> = by_hash
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(password_sha256, entry_ids)| ReusedPasswordGroup { password_sha256, entry_ids })
+        .collect();
+
+    let mut by_account: BTreeMap<(&str, String, String), Vec<i64>> = BTreeMap::new();
+    for (id, entry) in &entries {
+        let site = site_key(entry);
+        let username = entry.username.trim().to_lowercase();
+        if !site.is_empty() || !username.is_empty() {
+            by_account.entry((entry.kind().as_str(), site, username)).or_default().push(*id);
+        }
+    }
+    let duplicate_groups: Vec<Vec<i64>> = by_account.into_values().filter(|ids| ids.len() > 1).collect();
+
+    let mut counts = HealthCounts::default();
+    let mut items = Vec::new();
+    let mut total_risk = 0.0;
+    for (id, entry) in &entries {
+        let age = ages.get(id).map(|days| days.max(0.0) as u32);
+        let mut issues = Vec::new();
+
+        if entry.kind() == ItemKind::Login {
+            if entry.password.is_empty() {
+                issues.push(HealthIssue {
+                    kind: HealthIssueKind::NoPassword,
+                    detail: "No password is saved".to_string(),
+                    remediation: "Add the password, or delete the entry if the account is gone".to_string(),
+                });
+            } else {
+                if let Some(flagged_at) = &entry.compromised_at {
+                    issues.push(HealthIssue {
+                        kind: HealthIssueKind::Compromised,
+                        detail: match &entry.compromised_reason {
+                            Some(reason) => format!("Flagged {}: {}", flagged_at, reason),
+                            None => format!("Flagged {}", flagged_at),
+                        },
+                        remediation: "Change the password now, and anywhere else it was used".to_string(),
+                    });
+                }
+
+                if let Some(group) = reused_groups.iter().find(|g| g.entry_ids.contains(id)) {
+                    let others = group.entry_ids.len() - 1;
+                    issues.push(HealthIssue {
+                        kind: HealthIssueKind::Reused,
+                        detail: format!("Same password as {} other {}", others, if others == 1 { "entry" } else { "entries" }),
+                        remediation: "Change it to a unique generated password".to_string(),
+                    });
+                }
+
+                if let Some(strength) = weak_password_strength(entry) {
+                    issues.push(HealthIssue {
+                        kind: HealthIssueKind::Weak,
+                        detail: strength
+                            .warning
+                            .unwrap_or_else(|| format!("Could be cracked in {}", strength.crack_time_display)),
+                        remediation: "Replace it with a longer generated password".to_string(),
+                    });
+                }
+
+                if let Some(days) = age.filter(|days| *days > max_age_days) {
+                    issues.push(HealthIssue {
+                        kind: HealthIssueKind::Old,
+                        detail: format!("Unchanged for {} days", days),
+                        remediation: "Rotate the password".to_string(),
+                    });
+                }
+            }
+        }
+
+        if let Some(address) = insecure_address(entry) {
+            issues.push(HealthIssue {
+                kind: HealthIssueKind::InsecureUrl,
+                detail: format!("{} is not encrypted", address),
+                remediation: "Switch the address to https:// if the site supports it".to_string(),
+            });
+        }
+
+        if let Some(group) = duplicate_groups.iter().find(|g| g.contains(id)) {
+            let others: Vec<String> = group.iter().filter(|other| *other != id).map(|other| format!("#{}", other)).collect();
+            issues.push(HealthIssue {
+                kind: HealthIssueKind::Duplicate,
+                detail: format!("Same account as {}", others.join(", ")),
+                remediation: "Merge the copies into one entry".to_string(),
+            });
+        }
+
+        if issues.is_empty() {
+            continue;
+        }
+        for issue in &issues {
+            match issue.kind {
+                HealthIssueKind::NoPassword => counts.no_password += 1,
+                HealthIssueKind::Compromised => counts.compromised += 1,
+                HealthIssueKind::Reused => counts.reused += 1,
+                HealthIssueKind::Weak => counts.weak += 1,
+                HealthIssueKind::Old => counts.old += 1,
+                HealthIssueKind::InsecureUrl => counts.insecure_url += 1,
+                HealthIssueKind::Duplicate => counts.duplicate += 1,
+            }
+        }
+        total_risk += risk(&issues);
+        items.push(HealthReportItem {
+            entry_id: *id,
+            website: entry.website.clone(),
+            username: entry.username.clone(),
+            kind: entry.kind(),
+            password_age_days: age,
+            issues,
+        });
+    }
+
+    items.sort_by(|a, b| risk(&b.issues).total_cmp(&risk(&a.issues)).then(a.entry_id.cmp(&b.entry_id)));
+    let score = if entries.is_empty() {
+        100
+    } else {
+        (100.0 * (1.0 - total_risk / entries.len() as f64)).round() as u8
+    };
+
+    VaultHealthReport {
+        score,
+        generated_at: utc_now(),
+        total_entries: entries.len(),
+        max_password_age_days: max_age_days,
+        counts,
+        reused_groups,
+        duplicate_groups,
+        items,
+    }
+}
+
+/// How much an entry's issues put it at risk, from 0 to 1. A missing, leaked or guessable
+/// password is as bad as it gets; the rest add up.
+fn risk(issues: &[HealthIssue]) -> f64 {
+    let total: f64 = issues
+        .iter()
+        .map(|issue| match issue.kind {
+            HealthIssueKind::NoPassword | HealthIssueKind::Compromised | HealthIssueKind::Weak => 1.0,
+            HealthIssueKind::Reused => 0.8,
+            HealthIssueKind::InsecureUrl => 0.4,
+            HealthIssueKind::Old => 0.3,
+            HealthIssueKind::Duplicate => 0.1,
+        })
+        .sum();
+    total.min(1.0)
+}
+
+/// The site an entry belongs to, for spotting duplicates: the base domain of its first
+/// address, or its trimmed, lowercased name when it has none.
+fn site_key(entry: &PasswordEntry) -> String {
+    let uris = if entry.uris.is_empty() { website_uris(&entry.website) } else { entry.uris.clone() };
+    uris.iter()
+        .filter_map(|uri| parse_url(&uri.uri))
+        .find_map(|url| url.host_str().map(base_domain))
+        .unwrap_or_else(|| entry.website.trim().to_lowercase())
+}
+
+/// The first plain-http address the entry is used on, other than the local machine.
+fn insecure_address(entry: &PasswordEntry) -> Option<String> {
+    let addresses = entry.uris.iter().map(|uri| uri.uri.as_str()).chain(entry.website.split_whitespace());
+    addresses
+        .filter_map(parse_url)
+        .find(|url| {
+            url.scheme() == "http"
+                && !matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"))
+        })
+        .map(|url| url.to_string())
+}
+
+/// The current UTC time as "YYYY-MM-DD HH:MM".
+fn utc_now() -> String {
+    let mut now = utc_timestamp();
+    now.truncate(16);
+    now
+}
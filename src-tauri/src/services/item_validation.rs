@@ -0,0 +1,395 @@
+use crate::database::{
+    ApiCredential, DatabaseError, Identity, ItemDetails, Passkey, PasswordEntry, PaymentCard, RecoveryCodes, Result,
+    SoftwareLicense, SshKey, WifiNetwork, WifiSecurity,
+};
+use crate::services::passkey::{check_passkey_key, decode_base64url};
+use crate::services::recovery_codes::normalize_recovery_code;
+use crate::services::ssh_key::{parse_public_key, public_key_from_private};
+
+/// Validates an entry's typed details and normalizes them in place
+/// (trimmed fields, derived values such as card brand and SSH fingerprint).
+/// Plain logins carry no details and are accepted as before.
+pub fn validate_entry(entry: &mut PasswordEntry) -> Result<()> {
+    clean(&mut entry.expires_on);
+    check_date(&entry.expires_on, "Expiry date")?;
+    if entry.rotation_days == Some(0) {
+        return Err(invalid("Rotation interval must be at least one day"));
+    }
+
+    let Some(details) = entry.details.as_mut() else {
+        return Ok(());
+    };
+
+    if entry.website.trim().is_empty() {
+        return Err(invalid("Items need a title"));
+    }
+
+    match details {
+        ItemDetails::PaymentCard(card) => validate_payment_card(card),
+        ItemDetails::Identity(identity) => validate_identity(identity),
+        ItemDetails::SshKey(key) => validate_ssh_key(key),
+        ItemDetails::ApiCredential(credential) => validate_api_credential(credential),
+        ItemDetails::WifiNetwork(network) => validate_wifi_network(network),
+        ItemDetails::SoftwareLicense(license) => validate_software_license(license),
+        ItemDetails::RecoveryCodes(codes) => validate_recovery_codes(codes),
+        ItemDetails::Passkey(passkey) => validate_passkey(passkey),
+    }
+}
+
+fn validate_payment_card(card: &mut PaymentCard) -> Result<()> {
+    let number: String = card.number.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    if !(12..=19).contains(&number.len()) || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid("Card number must be 12 to 19 digits"));
+    }
+    if !luhn_valid(&number) {
+        return Err(invalid("Card number failed the Luhn check; check for typos"));
+    }
+
+    if !(1..=12).contains(&card.expiry_month) {
+        return Err(invalid("Card expiry month must be between 1 and 12"));
+    }
+    if card.expiry_year < 100 {
+        card.expiry_year += 2000;
+    }
+    if !(2000..=2099).contains(&card.expiry_year) {
+        return Err(invalid("Card expiry year is out of range"));
+    }
+
+    let brand = card_brand(&number);
+    clean(&mut card.security_code);
+    if let Some(code) = &card.security_code {
+        let expected = if brand == Some("American Express") { 4 } else { 3 };
+        if code.len() != expected || !code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid(&format!("Security code must be {} digits", expected)));
+        }
+    }
+    clean(&mut card.pin);
+    if let Some(pin) = &card.pin {
+        if !(4..=12).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid("PIN must be 4 to 12 digits"));
+        }
+    }
+
+    card.cardholder_name = card.cardholder_name.trim().to_string();
+    card.number = number;
+    card.brand = brand.map(str::to_string);
+    Ok(())
+}
+
+fn validate_identity(identity: &mut Identity) -> Result<()> {
+    identity.first_name = identity.first_name.trim().to_string();
+    identity.last_name = identity.last_name.trim().to_string();
+    if identity.first_name.is_empty() && identity.last_name.is_empty() {
+        return Err(invalid("Identity needs a first or last name"));
+    }
+
+    for field in [
+        &mut identity.title,
+        &mut identity.middle_name,
+        &mut identity.email,
+        &mut identity.phone,
+        &mut identity.date_of_birth,
+        &mut identity.address_line1,
+        &mut identity.address_line2,
+        &mut identity.city,
+        &mut identity.region,
+        &mut identity.postal_code,
+        &mut identity.country,
+        &mut identity.passport_number,
+        &mut identity.national_id,
+    ] {
+        clean(field);
+    }
+
+    if let Some(email) = &identity.email {
+        if !is_valid_email(email) {
+            return Err(invalid("Email address is not valid"));
+        }
+    }
+    if let Some(phone) = &identity.phone {
+        let digits = phone.chars().filter(|c| c.is_ascii_digit()).count();
+        if digits < 5 || !phone.chars().all(|c| c.is_ascii_digit() || " +-().".contains(c)) {
+            return Err(invalid("Phone number is not valid"));
+        }
+    }
+    check_date(&identity.date_of_birth, "Date of birth")
+}
+
+fn validate_ssh_key(key: &mut SshKey) -> Result<()> {
+    clean(&mut key.private_key);
+    clean(&mut key.public_key);
+    clean(&mut key.passphrase);
+    clean(&mut key.comment);
+
+    if key.public_key.is_none() {
+        if let Some(private_key) = &key.private_key {
+            key.public_key = public_key_from_private(private_key).ok();
+        }
+    }
+
+    let Some(public_key) = &key.public_key else {
+        if key.private_key.is_none() {
+            return Err(invalid("SSH key needs a public or private key"));
+        }
+        // Non-OpenSSH private keys (e.g. PEM) are kept as-is without a fingerprint
+        key.key_type = None;
+        key.fingerprint = None;
+        return Ok(());
+    };
+
+    let info = parse_public_key(public_key).map_err(|e| invalid(&e))?;
+    key.key_type = Some(match info.bits {
+        Some(bits) => format!("{} ({} bits)", info.key_type, bits),
+        None => info.key_type,
+    });
+    key.fingerprint = Some(info.fingerprint);
+    if key.comment.is_none() {
+        key.comment = info.comment;
+    }
+    Ok(())
+}
+
+fn validate_api_credential(credential: &mut ApiCredential) -> Result<()> {
+    if credential.secret.trim().is_empty() {
+        return Err(invalid("API credential needs a secret"));
+    }
+    clean(&mut credential.key_id);
+    clean(&mut credential.endpoint);
+    credential.scopes = credential
+        .scopes
+        .iter()
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| !scope.is_empty())
+        .collect();
+    clean(&mut credential.expires_on);
+    check_date(&credential.expires_on, "Expiry date")
+}
+
+fn validate_wifi_network(network: &mut WifiNetwork) -> Result<()> {
+    if network.ssid.is_empty() || network.ssid.len() > 32 {
+        return Err(invalid("Network name (SSID) must be 1 to 32 bytes"));
+    }
+    clean(&mut network.passphrase);
+
+    let is_hex = |s: &str| s.chars().all(|c| c.is_ascii_hexdigit());
+    match (network.security, network.passphrase.as_deref()) {
+        (WifiSecurity::Open, _) => network.passphrase = None,
+        (WifiSecurity::Enterprise, _) => {}
+        (_, None) => return Err(invalid("Secured networks need a passphrase")),
+        (WifiSecurity::Wep, Some(key)) => {
+            let valid = (matches!(key.len(), 5 | 13) && key.is_ascii()) || (matches!(key.len(), 10 | 26) && is_hex(key));
+            if !valid {
+                return Err(invalid("WEP keys are 5 or 13 characters, or 10 or 26 hex digits"));
+            }
+        }
+        (_, Some(key)) => {
+            let valid = ((8..=63).contains(&key.len()) && key.chars().all(|c| (' '..='~').contains(&c)))
+                || (key.len() == 64 && is_hex(key));
+            if !valid {
+                return Err(invalid("WPA passphrases are 8 to 63 printable ASCII characters, or 64 hex digits"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_software_license(license: &mut SoftwareLicense) -> Result<()> {
+    license.product = license.product.trim().to_string();
+    license.license_key = license.license_key.trim().to_string();
+    if license.product.is_empty() {
+        return Err(invalid("License needs a product name"));
+    }
+    if license.license_key.is_empty() {
+        return Err(invalid("License needs a license key"));
+    }
+
+    clean(&mut license.version);
+    clean(&mut license.licensed_to);
+    clean(&mut license.email);
+    clean(&mut license.purchase_date);
+    clean(&mut license.expires_on);
+    if let Some(email) = &license.email {
+        if !is_valid_email(email) {
+            return Err(invalid("Email address is not valid"));
+        }
+    }
+    if license.seats == Some(0) {
+        return Err(invalid("Seat count must be at least 1"));
+    }
+    check_date(&license.purchase_date, "Purchase date")?;
+    check_date(&license.expires_on, "Expiry date")
+}
+
+fn validate_recovery_codes(item: &mut RecoveryCodes) -> Result<()> {
+    let mut seen: Vec<String> = Vec::new();
+    for code in item.codes.iter_mut() {
+        code.code = code.code.trim().to_string();
+        let normalized = normalize_recovery_code(&code.code);
+        if normalized.is_empty() {
+            return Err(invalid("Recovery codes can't be blank"));
+        }
+        if seen.contains(&normalized) {
+            return Err(invalid(&format!("Recovery code {} appears more than once", code.code)));
+        }
+        seen.push(normalized);
+        if !code.used {
+            code.used_at = None;
+        }
+    }
+    if item.codes.is_empty() {
+        return Err(invalid("Add at least one recovery code"));
+    }
+    Ok(())
+}
+
+fn validate_passkey(passkey: &mut Passkey) -> Result<()> {
+    passkey.rp_id = passkey.rp_id.trim().trim_end_matches('.').to_ascii_lowercase();
+    if !is_valid_rp_id(&passkey.rp_id) {
+        return Err(invalid("Passkey needs the site's domain as its relying party id, e.g. example.com"));
+    }
+    clean(&mut passkey.rp_name);
+    clean(&mut passkey.user_name);
+    clean(&mut passkey.user_display_name);
+
+    // Stored without padding, the way WebAuthn clients pass them around
+    for (value, label, max_len) in [
+        (&mut passkey.credential_id, "Credential id", 1023),
+        (&mut passkey.user_handle, "User handle", 64),
+    ] {
+        let bytes = decode_base64url(value, label).map_err(|e| invalid(&e))?;
+        if bytes.is_empty() || bytes.len() > max_len {
+            return Err(invalid(&format!("{} must be 1 to {} bytes", label, max_len)));
+        }
+        *value = value.trim().trim_end_matches('=').to_string();
+    }
+    passkey.private_key = passkey.private_key.trim().trim_end_matches('=').to_string();
+    check_passkey_key(passkey).map_err(|e| invalid(&e))
+}
+
+/// Tidies the folder and tags shared by entries and notes: trims them, drops empty
+/// folder segments (`" clients / acme/"` becomes `clients/acme`) and repeated tags.
+pub fn normalize_labels(folder: &mut Option<String>, tags: &mut Vec<String>) {
+    *folder = folder
+        .take()
+        .map(|f| f.split('/').map(str::trim).filter(|s| !s.is_empty()).collect::<Vec<_>>().join("/"))
+        .filter(|f| !f.is_empty());
+
+    let mut seen: Vec<String> = Vec::new();
+    tags.retain_mut(|tag| {
+        *tag = tag.trim().to_string();
+        let duplicate = seen.iter().any(|s| s.eq_ignore_ascii_case(tag));
+        if !tag.is_empty() && !duplicate {
+            seen.push(tag.clone());
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Luhn (mod 10) checksum over a string of ASCII digits.
+pub fn luhn_valid(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let d = (b - b'0') as u32;
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn card_brand(number: &str) -> Option<&'static str> {
+    let prefix = |n: usize| number.get(..n).and_then(|p| p.parse::<u32>().ok()).unwrap_or(0);
+    let len = number.len();
+
+    if number.starts_with('4') && matches!(len, 13 | 16 | 19) {
+        Some("Visa")
+    } else if matches!(prefix(2), 34 | 37) && len == 15 {
+        Some("American Express")
+    } else if ((51..=55).contains(&prefix(2)) || (2221..=2720).contains(&prefix(4))) && len == 16 {
+        Some("Mastercard")
+    } else if (prefix(4) == 6011 || prefix(2) == 65 || (644..=649).contains(&prefix(3))) && len >= 16 {
+        Some("Discover")
+    } else if (3528..=3589).contains(&prefix(4)) && len >= 16 {
+        Some("JCB")
+    } else if (matches!(prefix(2), 36 | 38) || (300..=305).contains(&prefix(3))) && len >= 14 {
+        Some("Diners Club")
+    } else if prefix(2) == 62 && len >= 16 {
+        Some("UnionPay")
+    } else if matches!(prefix(2), 50 | 56..=58) || matches!(prefix(4), 6304 | 6759 | 6761..=6763) {
+        Some("Maestro")
+    } else {
+        None
+    }
+}
+
+/// Checks for a calendar date in `YYYY-MM-DD` form.
+pub fn is_valid_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return false;
+    }
+    let (Ok(year), Ok(month), Ok(day)) = (year.parse::<u32>(), month.parse::<u32>(), day.parse::<u32>()) else {
+        return false;
+    };
+
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+pub fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// A bare host name: WebAuthn scopes a passkey to a domain, not a URL.
+fn is_valid_rp_id(rp_id: &str) -> bool {
+    !rp_id.is_empty()
+        && rp_id.len() <= 253
+        && rp_id
+            .split('.')
+            .all(|label| !label.is_empty() && label.chars().all(|c| c.is_alphanumeric() || c == '-'))
+}
+
+fn check_date(value: &Option<String>, label: &str) -> Result<()> {
+    match value {
+        Some(date) if !is_valid_date(date) => Err(invalid(&format!("{} must be a date in YYYY-MM-DD form", label))),
+        _ => Ok(()),
+    }
+}
+
+/// Trims an optional field and turns blank values into `None`.
+fn clean(field: &mut Option<String>) {
+    *field = field.take().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+}
+
+fn invalid(message: &str) -> DatabaseError {
+    DatabaseError::Validation(message.to_string())
+}
@@ -0,0 +1,196 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use icu_normalizer::DecomposingNormalizerBorrowed;
+use url::Url;
+
+use crate::database::{DatabaseError, DatabaseManager, EntryUri, LookalikeKind, PhishingWarning, Result, UriMatchMode, WarningSeverity};
+use crate::repositories::EntryUriRepository;
+use crate::services::{base_domain, parse_url};
+
+const CONFUSABLES: &str = include_str!("../../data/confusables.txt");
+
+/// Names shorter than this are too generic for typo and embedded-name checks (`bbc`, `x.com`).
+const MIN_NAME_LEN: usize = 4;
+
+pub struct LookalikeService;
+
+impl LookalikeService {
+    /// Compares a requested URL against every domain saved in the vault.
+    pub async fn check_url(db_manager: &DatabaseManager, url: &str) -> Result<Vec<PhishingWarning>> {
+        let target = parse_url(url).ok_or_else(|| DatabaseError::Validation(format!("'{}' is not a valid URL", url)))?;
+        let conn = db_manager.get_connection().await?;
+        let known = EntryUriRepository::get_all(&conn).await?;
+        Ok(lookalike_warnings(&target, &known))
+    }
+}
+
+/// Warnings for a page whose domain imitates one of the saved domains, most severe first.
+/// Pages on a saved domain itself get none.
+pub fn lookalike_warnings(target: &Url, known: &[(i64, EntryUri)]) -> Vec<PhishingWarning> {
+    let Some(host) = target.domain().map(str::to_ascii_lowercase) else {
+        return Vec::new();
+    };
+    let domain = base_domain(&host);
+
+    let mut known_domains: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    for (entry_id, uri) in known {
+        if uri.match_mode == UriMatchMode::Regex {
+            continue;
+        }
+        let Some(url) = parse_url(&uri.uri).filter(|url| matches!(url.scheme(), "http" | "https")) else {
+            continue;
+        };
+        if let Some(known_host) = url.domain() {
+            let ids = known_domains.entry(base_domain(known_host)).or_default();
+            if !ids.contains(entry_id) {
+                ids.push(*entry_id);
+            }
+        }
+    }
+    if known_domains.contains_key(&domain) {
+        return Vec::new();
+    }
+
+    let display = to_unicode(&domain);
+    let name = first_label(&display);
+    let name_skeleton = skeleton(name);
+    let host_display = to_unicode(&host);
+    let subdomain_labels = host_display
+        .strip_suffix(display.as_str())
+        .unwrap_or_default()
+        .trim_end_matches('.');
+
+    let mut warnings = Vec::new();
+    for (known_domain, entry_ids) in &known_domains {
+        let known_display = to_unicode(known_domain);
+        let known_name = first_label(&known_display);
+        let known_skeleton = skeleton(known_name);
+        let long_enough = known_skeleton.chars().count() >= MIN_NAME_LEN;
+
+        let (kind, severity) = if name == known_name {
+            (LookalikeKind::DifferentSuffix, WarningSeverity::Low)
+        } else if name_skeleton == known_skeleton {
+            (LookalikeKind::Homoglyph, WarningSeverity::High)
+        } else if long_enough && contains_token(subdomain_labels, &known_skeleton) {
+            (LookalikeKind::SubdomainTrick, WarningSeverity::High)
+        } else if long_enough && contains_token(name, &known_skeleton) {
+            (LookalikeKind::EmbeddedName, WarningSeverity::Medium)
+        } else if long_enough {
+            match edit_distance(&name_skeleton, &known_skeleton) {
+                1 => (LookalikeKind::Typo, WarningSeverity::High),
+                2 if known_skeleton.chars().count() >= 8 => (LookalikeKind::Typo, WarningSeverity::Medium),
+                _ => continue,
+            }
+        } else {
+            continue;
+        };
+
+        let message = match kind {
+            LookalikeKind::DifferentSuffix => format!("{} is not {}, which is saved in your vault", display, known_display),
+            LookalikeKind::SubdomainTrick => format!(
+                "{} only mentions {} in its subdomain; the site is really {}",
+                host_display, known_name, display
+            ),
+            _ => format!("{} looks like {}, which is saved in your vault", display, known_display),
+        };
+        warnings.push(PhishingWarning {
+            kind,
+            severity,
+            requested_domain: display.clone(),
+            similar_to: Some(known_display),
+            entry_ids: entry_ids.clone(),
+            message,
+        });
+    }
+
+    if warnings.is_empty() && host_display.split('.').any(mixes_scripts) {
+        warnings.push(PhishingWarning {
+            kind: LookalikeKind::MixedScript,
+            severity: WarningSeverity::Medium,
+            requested_domain: display.clone(),
+            similar_to: None,
+            entry_ids: Vec::new(),
+            message: format!("{} mixes letters from different alphabets", host_display),
+        });
+    }
+
+    warnings.sort_by_key(|w| std::cmp::Reverse(w.severity));
+    warnings
+}
+
+/// The UTS #39 skeleton of a string: NFD, confusable characters replaced by their
+/// prototypes, NFD again. Two strings with the same skeleton are visually confusable.
+pub fn skeleton(text: &str) -> String {
+    let nfd = DecomposingNormalizerBorrowed::new_nfd();
+    let table = confusables();
+    let mut mapped = String::with_capacity(text.len());
+    for c in nfd.normalize(text).chars() {
+        match table.get(&c) {
+            Some(prototype) => mapped.push_str(prototype),
+            None => mapped.push(c),
+        }
+    }
+    nfd.normalize(&mapped).into_owned()
+}
+
+fn confusables() -> &'static HashMap<char, String> {
+    static TABLE: OnceLock<HashMap<char, String>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let code_point = |hex: &str| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+        CONFUSABLES
+            .lines()
+            .filter_map(|line| {
+                let (source, target) = line.split('#').next()?.split_once(';')?;
+                let source = code_point(source.trim())?;
+                let target = target.split_whitespace().map(code_point).collect::<Option<String>>()?;
+                Some((source, target))
+            })
+            .collect()
+    })
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions and
+/// transpositions of neighbouring characters each cost one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Whether any `.` or `-` separated part of `labels` has the given skeleton.
+fn contains_token(labels: &str, target_skeleton: &str) -> bool {
+    labels
+        .split(['.', '-'])
+        .any(|token| !token.is_empty() && skeleton(token) == target_skeleton)
+}
+
+fn mixes_scripts(label: &str) -> bool {
+    let latin = label.chars().any(|c| c.is_ascii_alphabetic());
+    let other = label.chars().any(|c| matches!(c, '\u{0370}'..='\u{03FF}' | '\u{0400}'..='\u{052F}' | '\u{0530}'..='\u{058F}'));
+    latin && other
+}
+
+fn to_unicode(domain: &str) -> String {
+    idna::domain_to_unicode(domain).0
+}
+
+fn first_label(domain: &str) -> &str {
+    domain.split('.').next().unwrap_or_default()
+}
@@ -0,0 +1,63 @@
+use crate::database::{DatabaseError, DatabaseManager, MasterPasswordPolicy, PasswordPolicyError, Result};
+use crate::repositories::PasswordRepository;
+use crate::services::{SettingsService, StrengthService};
+
+pub struct MasterPasswordService;
+
+impl MasterPasswordService {
+    /// Checks a proposed master password against the parts of the policy that don't
+    /// need an open vault: emptiness, the common-password list and estimated strength.
+    pub fn check(password: &str, policy: &MasterPasswordPolicy) -> std::result::Result<(), PasswordPolicyError> {
+        if password.is_empty() {
+            return Err(PasswordPolicyError::Empty);
+        }
+        if policy.block_common_passwords && StrengthService::is_common_password(password) {
+            return Err(PasswordPolicyError::Common);
+        }
+
+        let strength = StrengthService::estimate(password, &[]);
+        let estimated_bits = (strength.guesses_log10 * std::f64::consts::LOG2_10).floor() as u32;
+        if estimated_bits < policy.min_entropy_bits {
+            return Err(PasswordPolicyError::TooWeak { estimated_bits, required_bits: policy.min_entropy_bits });
+        }
+        Ok(())
+    }
+
+    /// Re-encrypts the vault under a new master password after checking the current one and
+    /// the vault's policy. Returns a manager for the re-keyed vault to replace the old one, or
+    /// `None` when the vault was re-keyed but couldn't be reopened: the change still stands,
+    /// and the vault has to be unlocked again with the new password.
+    pub async fn change_master_password(
+        db_manager: &DatabaseManager,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<Option<DatabaseManager>> {
+        let vault_path = db_manager.path().to_string_lossy().to_string();
+        if !DatabaseManager::verify_master_password(current_password, &vault_path).await {
+            return Err(DatabaseError::InvalidMasterPassword);
+        }
+        if new_password == current_password {
+            return Err(PasswordPolicyError::Unchanged.into());
+        }
+
+        let policy = SettingsService::get_settings(db_manager).await?.master_password_policy;
+        Self::check(new_password, &policy)?;
+        if policy.forbid_entry_reuse {
+            let conn = db_manager.get_connection().await?;
+            let count = PasswordRepository::count_with_password(&conn, new_password).await?;
+            if count > 0 {
+                return Err(PasswordPolicyError::ReusedByEntries { count: count as usize }.into());
+            }
+        }
+
+        db_manager.rekey(new_password).await?;
+        // The file is under the new key from here on, so a failed reopen can't undo the change
+        match DatabaseManager::new_with_encryption_and_path(new_password, &vault_path).await {
+            Ok(rekeyed) => Ok(Some(rekeyed)),
+            Err(e) => {
+                eprintln!("Failed to reopen the vault after changing the master password: {}", e);
+                Ok(None)
+            }
+        }
+    }
+}
@@ -1,7 +1,57 @@
 pub mod password_service;
 pub mod notes_service;
 pub mod auth_service;
+pub mod settings_service;
+pub mod text_diff;
+pub mod attachment_service;
+pub mod ssh_key;
+pub mod item_validation;
+pub mod template_service;
+pub mod uri_matching;
+pub mod public_suffix;
+pub mod autofill;
+pub mod lookalike;
+pub mod search_service;
+pub mod vault_query;
+pub mod fuzzy;
+pub mod generator;
+pub mod strength;
+pub mod master_password;
+pub mod health_service;
+pub mod health_export;
+pub mod breach_service;
+pub mod expiry_service;
+pub mod rotation_service;
+pub mod usage_service;
+pub mod recovery_codes;
+pub mod recovery_code_service;
+pub mod passkey;
+pub mod passkey_service;
+pub mod util;
 
 pub use password_service::*;
 pub use notes_service::*;
 pub use auth_service::*;
+pub use settings_service::*;
+pub use text_diff::*;
+pub use attachment_service::*;
+pub use item_validation::*;
+pub use template_service::*;
+pub use uri_matching::*;
+pub use autofill::*;
+pub use lookalike::*;
+pub use search_service::*;
+pub use vault_query::*;
+pub use fuzzy::*;
+pub use generator::*;
+pub use strength::*;
+pub use master_password::*;
+pub use health_service::*;
+pub use health_export::*;
+pub use breach_service::*;
+pub use expiry_service::*;
+pub use rotation_service::*;
+pub use usage_service::*;
+pub use recovery_code_service::*;
+pub use passkey_service::*;
+pub use util::*;
@@ -1,26 +1,102 @@
-use crate::database::{DatabaseManager, Note, Result};
-use crate::repositories::NotesRepository;
+use crate::database::{DatabaseError, DatabaseManager, Note, NoteListOptions, NoteRevision, NoteRevisionDiff, Result};
+use crate::repositories::{NoteRevisionRepository, NotesRepository};
+use crate::services::{diff_lines, normalize_labels};
 
 pub struct NotesService;
 
 impl NotesService {
-    pub async fn create_note(db_manager: &DatabaseManager, note: Note) -> Result<i64> {
+    pub async fn create_note(db_manager: &DatabaseManager, mut note: Note) -> Result<i64> {
+        normalize_labels(&mut note.folder, &mut note.tags);
         let conn = db_manager.get_connection().await?;
-        NotesRepository::create(&conn, &note).await
+        let tx = conn.transaction().await?;
+
+        let id = NotesRepository::create(&tx, &note).await?;
+        NoteRevisionRepository::create(&tx, id, &note.title, &note.content).await?;
+
+        tx.commit().await?;
+        Ok(id)
     }
 
-    pub async fn get_notes(db_manager: &DatabaseManager) -> Result<Vec<Note>> {
+    pub async fn get_notes(db_manager: &DatabaseManager, options: NoteListOptions) -> Result<Vec<Note>> {
         let conn = db_manager.get_connection().await?;
-        NotesRepository::get_all(&conn).await
+        NotesRepository::get_all(&conn, &options).await
     }
 
-    pub async fn update_note(db_manager: &DatabaseManager, id: i64, note: Note) -> Result<()> {
+    pub async fn update_note(db_manager: &DatabaseManager, id: i64, mut note: Note) -> Result<()> {
+        normalize_labels(&mut note.folder, &mut note.tags);
         let conn = db_manager.get_connection().await?;
-        NotesRepository::update(&conn, id, &note).await
+        let tx = conn.transaction().await?;
+
+        if !NotesRepository::update(&tx, id, &note).await? {
+            return Err(DatabaseError::NotFound(format!("Note {}", id)));
+        }
+
+        // Saving without changes shouldn't grow the history
+        let latest = NoteRevisionRepository::get_latest(&tx, id).await?;
+        let unchanged = latest.is_some_and(|r| r.title == note.title && r.content == note.content);
+        if !unchanged {
+            NoteRevisionRepository::create(&tx, id, &note.title, &note.content).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
     }
 
     pub async fn delete_note(db_manager: &DatabaseManager, id: i64) -> Result<()> {
         let conn = db_manager.get_connection().await?;
         NotesRepository::delete(&conn, id).await
     }
+
+    pub async fn get_note_revisions(db_manager: &DatabaseManager, note_id: i64) -> Result<Vec<NoteRevision>> {
+        let conn = db_manager.get_connection().await?;
+        NoteRevisionRepository::get_for_note(&conn, note_id).await
+    }
+
+    pub async fn diff_note_revisions(
+        db_manager: &DatabaseManager,
+        note_id: i64,
+        from_revision: i64,
+        to_revision: i64,
+    ) -> Result<NoteRevisionDiff> {
+        let conn = db_manager.get_connection().await?;
+        let from = Self::require_revision(&conn, note_id, from_revision).await?;
+        let to = Self::require_revision(&conn, note_id, to_revision).await?;
+
+        Ok(NoteRevisionDiff {
+            note_id,
+            from_revision,
+            to_revision,
+            lines: diff_lines(&from.content, &to.content),
+            old_title: from.title,
+            new_title: to.title,
+        })
+    }
+
+    /// Brings a note back to an earlier revision by saving it again as the newest one.
+    /// Returns the number of the revision that was created.
+    pub async fn restore_note_revision(db_manager: &DatabaseManager, note_id: i64, revision: i64) -> Result<i64> {
+        let conn = db_manager.get_connection().await?;
+        let target = Self::require_revision(&conn, note_id, revision).await?;
+
+        let mut note = NotesRepository::get_by_id(&conn, note_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Note {}", note_id)))?;
+        note.title = target.title;
+        note.content = target.content;
+
+        let tx = conn.transaction().await?;
+        if !NotesRepository::update(&tx, note_id, &note).await? {
+            return Err(DatabaseError::NotFound(format!("Note {}", note_id)));
+        }
+        let new_revision = NoteRevisionRepository::create(&tx, note_id, &note.title, &note.content).await?;
+        tx.commit().await?;
+
+        Ok(new_revision)
+    }
+
+    async fn require_revision(conn: &libsql::Connection, note_id: i64, revision: i64) -> Result<NoteRevision> {
+        NoteRevisionRepository::get(conn, note_id, revision)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Revision {} of note {}", revision, note_id)))
+    }
 }
@@ -0,0 +1,451 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+use sha2::{Digest, Sha256};
+
+use crate::database::{Passkey, PasskeyAlgorithm, PasskeyAssertion, PasskeyRegistration};
+
+// Authenticator data flags (WebAuthn §6.1)
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+const FLAG_BACKED_UP: u8 = 0x10;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// The vault has no attestation certificate to vouch for, so it reports the all-zero AAGUID
+/// and signs registrations with the credential's own key ("packed" self attestation).
+const AAGUID: [u8; 16] = [0; 16];
+
+const CREDENTIAL_ID_LEN: usize = 16;
+const CLIENT_DATA_HASH_LEN: usize = 32;
+
+// DER SubjectPublicKeyInfo prefixes for a P-256 point and an Ed25519 key
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d,
+    0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+const ED25519_SPKI_PREFIX: &[u8] = &[0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// Generates a private key for a new passkey, as base64url PKCS#8.
+pub fn generate_passkey_key(algorithm: PasskeyAlgorithm) -> Result<String, String> {
+    let rng = SystemRandom::new();
+    let pkcs8 = match algorithm {
+        PasskeyAlgorithm::Es256 => EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng),
+        PasskeyAlgorithm::EdDsa => Ed25519KeyPair::generate_pkcs8(&rng),
+    }
+    .map_err(|_| "Failed to generate a passkey".to_string())?;
+    Ok(URL_SAFE_NO_PAD.encode(pkcs8.as_ref()))
+}
+
+pub fn generate_credential_id() -> Result<String, String> {
+    let mut id = [0u8; CREDENTIAL_ID_LEN];
+    SystemRandom::new()
+        .fill(&mut id)
+        .map_err(|_| "Failed to generate a credential id".to_string())?;
+    Ok(URL_SAFE_NO_PAD.encode(id))
+}
+
+/// Decodes a base64url value, tolerating the padding some exporters add.
+pub fn decode_base64url(value: &str, label: &str) -> Result<Vec<u8>, String> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim().trim_end_matches('='))
+        .map_err(|_| format!("{} is not valid base64url", label))
+}
+
+/// Checks that the passkey's key material loads for its algorithm.
+pub fn check_passkey_key(passkey: &Passkey) -> Result<(), String> {
+    SigningKey::load(passkey).map(|_| ())
+}
+
+/// The response to `navigator.credentials.create()` for a newly made passkey.
+pub fn registration_response(passkey: &Passkey, client_data_hash: &[u8], user_verified: bool) -> Result<PasskeyRegistration, String> {
+    check_client_data_hash(client_data_hash)?;
+    let key = SigningKey::load(passkey)?;
+    let credential_id = decode_base64url(&passkey.credential_id, "Credential id")?;
+    let credential_id_len =
+        u16::try_from(credential_id.len()).map_err(|_| "Credential id is too long".to_string())?;
+
+    let mut auth_data = authenticator_data(&passkey.rp_id, user_verified, passkey.sign_count);
+    auth_data[32] |= FLAG_ATTESTED_CREDENTIAL_DATA;
+    auth_data.extend_from_slice(&AAGUID);
+    auth_data.extend_from_slice(&credential_id_len.to_be_bytes());
+    auth_data.extend_from_slice(&credential_id);
+    auth_data.extend_from_slice(&key.cose_public_key());
+
+    let signature = key.sign(&[auth_data.as_slice(), client_data_hash].concat())?;
+    let attestation_statement = Cbor::Map(vec![
+        (Cbor::Text("alg"), Cbor::Int(passkey.algorithm.cose_id())),
+        (Cbor::Text("sig"), Cbor::Bytes(&signature)),
+    ]);
+    let attestation_object = Cbor::Map(vec![
+        (Cbor::Text("fmt"), Cbor::Text("packed")),
+        (Cbor::Text("attStmt"), attestation_statement),
+        (Cbor::Text("authData"), Cbor::Bytes(&auth_data)),
+    ]);
+
+    Ok(PasskeyRegistration {
+        item_id: None,
+        credential_id: URL_SAFE_NO_PAD.encode(&credential_id),
+        public_key_algorithm: passkey.algorithm.cose_id(),
+        public_key: URL_SAFE_NO_PAD.encode(key.spki_public_key()),
+        authenticator_data: URL_SAFE_NO_PAD.encode(&auth_data),
+        attestation_object: URL_SAFE_NO_PAD.encode(attestation_object.encode()),
+    })
+}
+
+/// The response to `navigator.credentials.get()`. The caller bumps and saves `sign_count`
+/// before signing, so the RP always sees a counter higher than the last one.
+pub fn assertion_response(passkey: &Passkey, client_data_hash: &[u8], user_verified: bool) -> Result<PasskeyAssertion, String> {
+    check_client_data_hash(client_data_hash)?;
+    let key = SigningKey::load(passkey)?;
+    let auth_data = authenticator_data(&passkey.rp_id, user_verified, passkey.sign_count);
+    let signature = key.sign(&[auth_data.as_slice(), client_data_hash].concat())?;
+
+    Ok(PasskeyAssertion {
+        item_id: None,
+        credential_id: passkey.credential_id.clone(),
+        authenticator_data: URL_SAFE_NO_PAD.encode(&auth_data),
+        signature: URL_SAFE_NO_PAD.encode(signature),
+        user_handle: passkey.user_handle.clone(),
+    })
+}
+
+/// rpIdHash, flags and signCount; registrations append the attested credential data.
+fn authenticator_data(rp_id: &str, user_verified: bool, sign_count: u32) -> Vec<u8> {
+    // Vault passkeys are synced with the vault, so they are always backup eligible and backed up
+    let mut flags = FLAG_USER_PRESENT | FLAG_BACKUP_ELIGIBLE | FLAG_BACKED_UP;
+    if user_verified {
+        flags |= FLAG_USER_VERIFIED;
+    }
+
+    let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+    data.push(flags);
+    data.extend_from_slice(&sign_count.to_be_bytes());
+    data
+}
+
+fn check_client_data_hash(hash: &[u8]) -> Result<(), String> {
+    if hash.len() != CLIENT_DATA_HASH_LEN {
+        return Err(format!("Client data hash must be {} bytes, got {}", CLIENT_DATA_HASH_LEN, hash.len()));
+    }
+    Ok(())
+}
+
+enum SigningKey {
+    Es256(EcdsaKeyPair),
+    EdDsa(Ed25519KeyPair),
+}
+
+impl SigningKey {
+    fn load(passkey: &Passkey) -> Result<Self, String> {
+        let pkcs8 = decode_base64url(&passkey.private_key, "Passkey private key")?;
+        match passkey.algorithm {
+            PasskeyAlgorithm::Es256 => EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &pkcs8)
+                .map(SigningKey::Es256)
+                .map_err(|e| format!("Passkey private key is not a P-256 PKCS#8 key ({})", e)),
+            // Accepts keys exported without the optional public key, as some managers do
+            PasskeyAlgorithm::EdDsa => Ed25519KeyPair::from_pkcs8_maybe_unchecked(&pkcs8)
+                .map(SigningKey::EdDsa)
+                .map_err(|e| format!("Passkey private key is not an Ed25519 PKCS#8 key ({})", e)),
+        }
+    }
+
+    /// ES256 signatures are DER-encoded, EdDSA ones raw, as WebAuthn expects.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            SigningKey::Es256(key) => key
+                .sign(&SystemRandom::new(), message)
+                .map(|signature| signature.as_ref().to_vec())
+                .map_err(|_| "Failed to sign with the passkey".to_string()),
+            SigningKey::EdDsa(key) => Ok(key.sign(message).as_ref().to_vec()),
+        }
+    }
+
+    /// The public key as a COSE_Key (RFC 9053): EC2 with x and y for ES256, OKP for EdDSA.
+    fn cose_public_key(&self) -> Vec<u8> {
+        match self {
+            SigningKey::Es256(key) => {
+                // ring gives the uncompressed point: 0x04 || x || y
+                let point = key.public_key().as_ref();
+                Cbor::Map(vec![
+                    (Cbor::Int(1), Cbor::Int(2)), // kty: EC2
+                    (Cbor::Int(3), Cbor::Int(-7)), // alg: ES256
+                    (Cbor::Int(-1), Cbor::Int(1)), // crv: P-256
+                    (Cbor::Int(-2), Cbor::Bytes(&point[1..33])),
+                    (Cbor::Int(-3), Cbor::Bytes(&point[33..65])),
+                ])
+                .encode()
+            }
+            SigningKey::EdDsa(key) => Cbor::Map(vec![
+                (Cbor::Int(1), Cbor::Int(1)), // kty: OKP
+                (Cbor::Int(3), Cbor::Int(-8)), // alg: EdDSA
+                (Cbor::Int(-1), Cbor::Int(6)), // crv: Ed25519
+                (Cbor::Int(-2), Cbor::Bytes(key.public_key().as_ref())),
+            ])
+            .encode(),
+        }
+    }
+
+    fn spki_public_key(&self) -> Vec<u8> {
+        match self {
+            SigningKey::Es256(key) => [P256_SPKI_PREFIX, key.public_key().as_ref()].concat(),
+            SigningKey::EdDsa(key) => [ED25519_SPKI_PREFIX, key.public_key().as_ref()].concat(),
+        }
+    }
+}
+
+/// The few CBOR types WebAuthn structures need. Map entries are written in the order given,
+/// so callers list keys in CTAP2 canonical order (shorter encodings first, then bytewise).
+enum Cbor<'a> {
+    Int(i64),
+    Bytes(&'a [u8]),
+    Text(&'a str),
+    Map(Vec<(Cbor<'a>, Cbor<'a>)>),
+}
+
+impl Cbor<'_> {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Int(value) if *value >= 0 => write_head(out, 0, *value as u64),
+            Cbor::Int(value) => write_head(out, 1, (-1 - *value) as u64),
+            Cbor::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Cbor::Map(entries) => {
+                write_head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    key.write(out);
+                    value.write(out);
+                }
+            }
+        }
+    }
+}
+
+/// Writes a major type with its argument in the shortest form.
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        out.push(major | value as u8);
+    } else if value <= u8::MAX as u64 {
+        out.extend_from_slice(&[major | 24, value as u8]);
+    } else if value <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1, ED25519};
+
+    // P-256 key and its public encodings, generated with Python's `cryptography` package
+    const P256_PKCS8: &str = "MIGHAgEAMBMGByqGSM49AgEGCCqGSM49AwEHBG0wawIBAQQgdLXXsjELnmdUKTMG2xjpuxocDe7HC7nLmQTmWPXzzVehRANCAAQ4ySgYL3b9ht58g65oVNEmRUrSZmd73a3MjzMW9kMaYbtEjEkj6I-EZ4zTjM51ZCRRy7aQw02wRuyQkbvS_aWN";
+    const P256_X: &str = "38c928182f76fd86de7c83ae6854d126454ad266677bddadcc8f3316f6431a61";
+    const P256_Y: &str = "bb448c4923e88f84678cd38cce75642451cbb690c34db046ec9091bbd2fda58d";
+    const P256_SPKI: &str = "3059301306072a8648ce3d020106082a8648ce3d0301070342000438c928182f76fd86de7c83ae6854d126454ad266677bddadcc8f3316f6431a61bb448c4923e88f84678cd38cce75642451cbb690c34db046ec9091bbd2fda58d";
+
+    // RFC 8410 §10.3 private key; §10.1 gives its public key
+    const ED25519_PKCS8: &str = "MC4CAQAwBQYDK2VwBCIEINTuctv5E1hK1bbY8fdp-K06_nwoy_HU--CXqI9EdVhC";
+    const ED25519_SPKI_B64: &str = "MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE";
+    const ED25519_PUBLIC: &str = "19bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1";
+
+    // SHA-256 of "example.com", the rpIdHash in the WebAuthn spec's examples
+    const RP_ID_HASH: &str = "a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce1947";
+    const CLIENT_DATA_JSON: &str =
+        r#"{"type":"webauthn.create","challenge":"dGVzdC1jaGFsbGVuZ2U","origin":"https://example.com","crossOrigin":false}"#;
+    const CLIENT_DATA_HASH: &str = "854eca4f4900a9d7ac66e82212abd2f49921dfb9e4bafbb63bf386e3e50eb11b";
+    const CREDENTIAL_ID: &str = "000102030405060708090a0b0c0d0e0f";
+
+    // Ed25519 signatures are deterministic, so these are byte-exact; computed independently
+    // with Python's `cryptography` over the authenticator data laid out by hand
+    const ED25519_ATTESTATION_OBJECT: &str = concat!(
+        "a363666d74667061636b65646761747453746d74a263616c6727637369675840",
+        "a160bd2a4ece171d8b4223a5df42df3dd1d78b6c988e1fafdadae8bd08738445569eabdaf6f2a737f09cb74a9f4450043fa5a02013fa5e20925cb0d4d33e7000",
+        "6861757468446174615871",
+        "a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19475d0000000000000000000000000000000000000000",
+        "0010000102030405060708090a0b0c0d0e0fa401010327200621582019bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1",
+    );
+    const ED25519_ASSERTION_SIGNATURE: &str = "f46a472099c75a6fbc08b559e7acd359440f4b573af6bc7a6648acb5eba845364f60cc8e0e602ca5f6c03fde7df85cf994c368a615652542cf158a5f94367d0e";
+
+    fn passkey(algorithm: PasskeyAlgorithm, private_key: &str) -> Passkey {
+        Passkey {
+            credential_id: URL_SAFE_NO_PAD.encode(hex::decode(CREDENTIAL_ID).unwrap()),
+            rp_id: "example.com".to_string(),
+            rp_name: None,
+            user_handle: URL_SAFE_NO_PAD.encode(b"user-1"),
+            user_name: Some("alice".to_string()),
+            user_display_name: None,
+            algorithm,
+            private_key: private_key.to_string(),
+            sign_count: 0,
+        }
+    }
+
+    fn client_data_hash() -> Vec<u8> {
+        let hash = Sha256::digest(CLIENT_DATA_JSON.as_bytes()).to_vec();
+        assert_eq!(hex::encode(&hash), CLIENT_DATA_HASH);
+        hash
+    }
+
+    fn b64(value: &str) -> Vec<u8> {
+        URL_SAFE_NO_PAD.decode(value).unwrap()
+    }
+
+    fn p256_cose_key() -> String {
+        format!("a5010203262001215820{}225820{}", P256_X, P256_Y)
+    }
+
+    /// rpIdHash ‖ flags ‖ signCount ‖ AAGUID ‖ credentialIdLength ‖ credentialId ‖ COSE_Key
+    fn expected_attested_data(flags: &str, cose_key: &str) -> String {
+        format!("{}{}00000000{}0010{}{}", RP_ID_HASH, flags, "00".repeat(16), CREDENTIAL_ID, cose_key)
+    }
+
+    #[test]
+    fn cbor_encoding_matches_rfc_8949_examples() {
+        let cases: Vec<(Cbor, &str)> = vec![
+            (Cbor::Int(0), "00"),
+            (Cbor::Int(10), "0a"),
+            (Cbor::Int(23), "17"),
+            (Cbor::Int(24), "1818"),
+            (Cbor::Int(100), "1864"),
+            (Cbor::Int(1000), "1903e8"),
+            (Cbor::Int(1000000), "1a000f4240"),
+            (Cbor::Int(1000000000000), "1b000000e8d4a51000"),
+            (Cbor::Int(-1), "20"),
+            (Cbor::Int(-10), "29"),
+            (Cbor::Int(-100), "3863"),
+            (Cbor::Int(-1000), "3903e7"),
+            (Cbor::Bytes(&[]), "40"),
+            (Cbor::Bytes(&[1, 2, 3, 4]), "4401020304"),
+            (Cbor::Text(""), "60"),
+            (Cbor::Text("a"), "6161"),
+            (Cbor::Text("IETF"), "6449455446"),
+            (Cbor::Map(vec![]), "a0"),
+            (Cbor::Map(vec![(Cbor::Int(1), Cbor::Int(2)), (Cbor::Int(3), Cbor::Int(4))]), "a201020304"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(hex::encode(value.encode()), expected);
+        }
+    }
+
+    #[test]
+    fn ed25519_keys_match_rfc_vectors() {
+        // RFC 8032 §7.1 test 1, as a PKCS#8 v1 key without the public half
+        let seed = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+        let pkcs8 = hex::decode(format!("302e020100300506032b657004220420{}", seed)).unwrap();
+        let key = SigningKey::load(&passkey(PasskeyAlgorithm::EdDsa, &URL_SAFE_NO_PAD.encode(pkcs8))).unwrap();
+        assert_eq!(
+            hex::encode(key.sign(b"").unwrap()),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert_eq!(
+            hex::encode(key.cose_public_key()),
+            "a4010103272006215820d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+
+        let key = SigningKey::load(&passkey(PasskeyAlgorithm::EdDsa, ED25519_PKCS8)).unwrap();
+        assert_eq!(key.spki_public_key(), b64(ED25519_SPKI_B64));
+    }
+
+    #[test]
+    fn p256_public_key_encodings_match_reference() {
+        let key = SigningKey::load(&passkey(PasskeyAlgorithm::Es256, P256_PKCS8)).unwrap();
+        assert_eq!(hex::encode(key.cose_public_key()), p256_cose_key());
+        assert_eq!(hex::encode(key.spki_public_key()), P256_SPKI);
+    }
+
+    #[test]
+    fn es256_registration_is_well_formed_and_verifies() {
+        let hash = client_data_hash();
+        for (user_verified, flags) in [(true, "5d"), (false, "59")] {
+            let registration = registration_response(&passkey(PasskeyAlgorithm::Es256, P256_PKCS8), &hash, user_verified).unwrap();
+            let auth_data = b64(&registration.authenticator_data);
+            assert_eq!(hex::encode(&auth_data), expected_attested_data(flags, &p256_cose_key()));
+            assert_eq!(registration.public_key_algorithm, -7);
+            assert_eq!(hex::encode(b64(&registration.public_key)), P256_SPKI);
+            assert_eq!(registration.credential_id, URL_SAFE_NO_PAD.encode(hex::decode(CREDENTIAL_ID).unwrap()));
+
+            // {"fmt": "packed", "attStmt": {"alg": -7, "sig": <DER>}, "authData": <bytes>}
+            let object = b64(&registration.attestation_object);
+            let head = hex::decode("a363666d74667061636b65646761747453746d74a263616c6726637369675840").unwrap();
+            let signature_len = *object.get(head.len() - 1).unwrap() as usize;
+            assert_eq!(object[..head.len() - 1], head[..head.len() - 1]);
+            let signature = &object[head.len()..head.len() + signature_len];
+            assert_eq!(auth_data.len(), 0x94);
+            let tail = [hex::decode("6861757468446174615894").unwrap(), auth_data.clone()].concat();
+            assert_eq!(object[head.len() + signature_len..], tail[..]);
+
+            let public_key = hex::decode(format!("04{}{}", P256_X, P256_Y)).unwrap();
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &public_key)
+                .verify(&[auth_data.as_slice(), &hash].concat(), signature)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn es256_assertion_verifies() {
+        let hash = client_data_hash();
+        let mut key = passkey(PasskeyAlgorithm::Es256, P256_PKCS8);
+        key.sign_count = 5;
+        for (user_verified, flags) in [(true, "1d"), (false, "19")] {
+            let assertion = assertion_response(&key, &hash, user_verified).unwrap();
+            let auth_data = b64(&assertion.authenticator_data);
+            assert_eq!(hex::encode(&auth_data), format!("{}{}00000005", RP_ID_HASH, flags));
+            assert_eq!(b64(&assertion.user_handle), b"user-1");
+
+            let public_key = hex::decode(format!("04{}{}", P256_X, P256_Y)).unwrap();
+            UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &public_key)
+                .verify(&[auth_data.as_slice(), &hash].concat(), &b64(&assertion.signature))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn eddsa_responses_match_reference() {
+        let hash = client_data_hash();
+        let mut key = passkey(PasskeyAlgorithm::EdDsa, ED25519_PKCS8);
+        let registration = registration_response(&key, &hash, true).unwrap();
+        let cose_key = format!("a4010103272006215820{}", ED25519_PUBLIC);
+        assert_eq!(hex::encode(b64(&registration.authenticator_data)), expected_attested_data("5d", &cose_key));
+        assert_eq!(hex::encode(b64(&registration.attestation_object)), ED25519_ATTESTATION_OBJECT);
+        assert_eq!(registration.public_key, ED25519_SPKI_B64);
+
+        key.sign_count = 5;
+        let assertion = assertion_response(&key, &hash, false).unwrap();
+        let auth_data = b64(&assertion.authenticator_data);
+        assert_eq!(hex::encode(&auth_data), format!("{}1900000005", RP_ID_HASH));
+        assert_eq!(hex::encode(b64(&assertion.signature)), ED25519_ASSERTION_SIGNATURE);
+        UnparsedPublicKey::new(&ED25519, hex::decode(ED25519_PUBLIC).unwrap())
+            .verify(&[auth_data.as_slice(), &hash].concat(), &b64(&assertion.signature))
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let key = passkey(PasskeyAlgorithm::Es256, P256_PKCS8);
+        assert!(registration_response(&key, &[0; 31], true).is_err());
+        assert!(assertion_response(&key, &[0; 33], true).is_err());
+        assert!(check_passkey_key(&passkey(PasskeyAlgorithm::Es256, ED25519_PKCS8)).is_err());
+        assert!(check_passkey_key(&passkey(PasskeyAlgorithm::EdDsa, "not base64!")).is_err());
+    }
+}
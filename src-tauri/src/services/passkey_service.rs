@@ -0,0 +1,149 @@
+use crate::database::{
+    DatabaseError, DatabaseManager, ItemDetails, ItemKind, Passkey, PasskeyAlgorithm, PasskeyAssertion, PasskeyAssertionRequest,
+    PasskeyCreationOptions, PasskeyRegistration, PasskeySummary, PasswordEntry, Result,
+};
+use crate::repositories::PasswordRepository;
+use crate::services::passkey::{assertion_response, decode_base64url, generate_credential_id, generate_passkey_key, registration_response};
+use crate::services::{validate_entry, PasswordService};
+
+pub struct PasskeyService;
+
+impl PasskeyService {
+    /// Makes a passkey for the relying party, saves it as a vault item and returns the
+    /// registration response for the browser to hand back to the site.
+    pub async fn create_passkey(db_manager: &DatabaseManager, options: PasskeyCreationOptions) -> Result<PasskeyRegistration> {
+        let client_data_hash = decode_base64url(&options.client_data_hash, "Client data hash").map_err(DatabaseError::Validation)?;
+        // An empty `pubKeyCredParams` means the RP accepts the WebAuthn default, ES256
+        let algorithm = if options.algorithms.is_empty() {
+            PasskeyAlgorithm::Es256
+        } else {
+            options
+                .algorithms
+                .iter()
+                .find_map(|id| PasskeyAlgorithm::from_cose_id(*id))
+                .ok_or_else(|| {
+                    DatabaseError::Validation("The site asked for a key type the vault can't make; ES256 and EdDSA are supported".to_string())
+                })?
+        };
+
+        let rp_id = options.rp_id.trim().to_ascii_lowercase();
+        let excluded: Vec<&str> = options.exclude_credentials.iter().map(|id| trim_base64url(id)).collect();
+        if Self::saved_passkeys(db_manager)
+            .await?
+            .iter()
+            .any(|(_, _, passkey)| passkey.rp_id == rp_id && excluded.contains(&passkey.credential_id.as_str()))
+        {
+            return Err(DatabaseError::Validation("This account already has a passkey in the vault".to_string()));
+        }
+
+        let passkey = Passkey {
+            credential_id: generate_credential_id().map_err(DatabaseError::Validation)?,
+            rp_id: options.rp_id,
+            rp_name: options.rp_name,
+            user_handle: options.user_handle,
+            user_name: options.user_name,
+            user_display_name: options.user_display_name,
+            algorithm,
+            private_key: generate_passkey_key(algorithm).map_err(DatabaseError::Validation)?,
+            sign_count: 0,
+        };
+        let mut entry = PasswordEntry {
+            website: passkey.rp_name.clone().unwrap_or_else(|| passkey.rp_id.clone()),
+            username: passkey.user_name.clone().unwrap_or_default(),
+            folder: options.folder,
+            details: Some(ItemDetails::Passkey(passkey)),
+            ..Default::default()
+        };
+        validate_entry(&mut entry)?;
+        let Some(ItemDetails::Passkey(passkey)) = &entry.details else {
+            unreachable!("the entry was built as a passkey");
+        };
+
+        // Build the response before saving, so a passkey the site never hears about isn't kept
+        let mut registration =
+            registration_response(passkey, &client_data_hash, options.user_verified).map_err(DatabaseError::Validation)?;
+        registration.item_id = Some(PasswordService::create_password(db_manager, entry).await?);
+        Ok(registration)
+    }
+
+    /// Saved passkeys, without their private keys, optionally only those for one relying party.
+    pub async fn list_passkeys(db_manager: &DatabaseManager, rp_id: Option<&str>) -> Result<Vec<PasskeySummary>> {
+        let rp_id = rp_id.map(|id| id.trim().to_ascii_lowercase());
+        Ok(Self::saved_passkeys(db_manager)
+            .await?
+            .into_iter()
+            .filter(|(_, _, passkey)| rp_id.as_ref().is_none_or(|id| passkey.rp_id == *id))
+            .map(|(item_id, entry, passkey)| PasskeySummary {
+                item_id,
+                title: entry.website,
+                credential_id: passkey.credential_id,
+                rp_id: passkey.rp_id,
+                user_handle: passkey.user_handle,
+                user_name: passkey.user_name,
+                user_display_name: passkey.user_display_name,
+                algorithm: passkey.algorithm,
+                sign_count: passkey.sign_count,
+                last_used_at: entry.last_used_at,
+            })
+            .collect())
+    }
+
+    /// Signs the site's challenge with a matching passkey. The signature counter is bumped
+    /// and saved with the signature, and the sign-in counts as a use of the item.
+    pub async fn get_assertion(db_manager: &DatabaseManager, request: PasskeyAssertionRequest) -> Result<PasskeyAssertion> {
+        let client_data_hash = decode_base64url(&request.client_data_hash, "Client data hash").map_err(DatabaseError::Validation)?;
+        let rp_id = request.rp_id.trim().to_ascii_lowercase();
+        let allowed: Vec<&str> = request.allow_credentials.iter().map(|id| trim_base64url(id)).collect();
+
+        let mut candidates: Vec<(i64, PasswordEntry, Passkey)> = Self::saved_passkeys(db_manager)
+            .await?
+            .into_iter()
+            .filter(|(_, _, passkey)| passkey.rp_id == rp_id)
+            .filter(|(_, _, passkey)| allowed.is_empty() || allowed.contains(&passkey.credential_id.as_str()))
+            .collect();
+        let (item_id, mut entry, mut passkey) = match (request.item_id, candidates.len()) {
+            (_, 0) => return Err(DatabaseError::NotFound(format!("Passkey for {}", rp_id))),
+            (Some(item_id), _) => {
+                let index = candidates.iter().position(|(id, _, _)| *id == item_id).ok_or_else(|| {
+                    DatabaseError::Validation(format!("Item {} isn't a passkey this site accepts", item_id))
+                })?;
+                candidates.swap_remove(index)
+            }
+            (None, 1) => candidates.remove(0),
+            (None, count) => {
+                return Err(DatabaseError::Validation(format!("{} passkeys match {}; choose one to sign in with", count, rp_id)))
+            }
+        };
+
+        passkey.sign_count = passkey.sign_count.saturating_add(1);
+        let mut assertion =
+            assertion_response(&passkey, &client_data_hash, request.user_verified).map_err(DatabaseError::Validation)?;
+        assertion.item_id = Some(item_id);
+        entry.details = Some(ItemDetails::Passkey(passkey));
+
+        let conn = db_manager.get_connection().await?;
+        let tx = conn.transaction().await?;
+        PasswordRepository::update(&tx, item_id, &entry).await?;
+        PasswordRepository::record_usage(&tx, item_id).await?;
+        tx.commit().await?;
+
+        Ok(assertion)
+    }
+
+    async fn saved_passkeys(db_manager: &DatabaseManager) -> Result<Vec<(i64, PasswordEntry, Passkey)>> {
+        let conn = db_manager.get_connection().await?;
+        Ok(PasswordRepository::get_all(&conn, Some(ItemKind::Passkey))
+            .await?
+            .into_iter()
+            .filter_map(|entry| match (entry.id, entry.details.clone()) {
+                (Some(id), Some(ItemDetails::Passkey(passkey))) => Some((id, entry, passkey)),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+/// Credential ids are stored without padding; allow lists may carry it.
+fn trim_base64url(value: &str) -> &str {
+    value.trim().trim_end_matches('=')
+}
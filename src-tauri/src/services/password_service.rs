@@ -1,31 +1,301 @@
-use crate::database::{DatabaseManager, PasswordEntry, Result};
-use crate::repositories::PasswordRepository;
+use std::collections::HashSet;
+
+use crate::database::{DatabaseError, DatabaseManager, EntryUri, ItemKind, ListOptions, Page, PasswordEntry, PasswordHistoryEntry, PasswordSummary, Result};
+use crate::repositories::{EntrySearchFields, EntryUriRepository, PasswordHistoryRepository, PasswordRepository};
+use crate::services::{fuzzy_match_fields, normalize_labels, normalize_uri, parse_url, uri_matches, validate_entry, website_uris, BreachService, RecoveryCodeService, SettingsService, TemplateService, BREACH_REASON, FAVORITE_BONUS};
+
+/// Page size for `list_passwords` when the caller doesn't ask for one, and the most it may ask for.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Most entries a fuzzy `search_passwords` returns.
+const FUZZY_SEARCH_LIMIT: usize = 100;
+
+/// Field name under which the main password is recorded in `password_history`.
+pub const PASSWORD_FIELD: &str = "password";
+
+/// History field name for a concealed custom field; prefixed so it can't collide with `PASSWORD_FIELD`.
+pub fn custom_field_history_name(name: &str) -> String {
+    format!("custom:{}", name)
+}
 
 pub struct PasswordService;
 
 impl PasswordService {
-    pub async fn create_password(db_manager: &DatabaseManager, entry: PasswordEntry) -> Result<i64> {
+    pub async fn create_password(db_manager: &DatabaseManager, mut entry: PasswordEntry) -> Result<i64> {
+        validate_entry(&mut entry)?;
+        Self::normalize_uris(&mut entry)?;
+        normalize_labels(&mut entry.folder, &mut entry.tags);
         let conn = db_manager.get_connection().await?;
-        PasswordRepository::create(&conn, &entry).await
+        TemplateService::validate_custom_fields(&conn, &mut entry).await?;
+        RecoveryCodeService::validate_link(&conn, None, &entry).await?;
+        let breached = entry.kind() == ItemKind::Login && BreachService::is_saved_password_breached(&entry.password).await?;
+
+        let tx = conn.transaction().await?;
+        let id = PasswordRepository::create(&tx, &entry).await?;
+        EntryUriRepository::replace_for_entry(&tx, id, &entry.uris).await?;
+        if breached {
+            PasswordRepository::mark_compromised(&tx, id, Some(BREACH_REASON)).await?;
+        }
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    pub async fn get_passwords(db_manager: &DatabaseManager, kind: Option<ItemKind>) -> Result<Vec<PasswordEntry>> {
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::get_all(&conn, kind).await
+    }
+
+    /// One page of entries without their secrets, for list views.
+    pub async fn list_passwords(db_manager: &DatabaseManager, options: ListOptions) -> Result<Page<PasswordSummary>> {
+        let limit = options.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let conn = db_manager.get_connection().await?;
+        let items = PasswordRepository::list_summaries(&conn, &options, limit).await?;
+        let total = PasswordRepository::count(&conn, &options).await?;
+
+        Ok(Page { items, total, offset: options.offset, limit })
     }
 
-    pub async fn get_passwords(db_manager: &DatabaseManager) -> Result<Vec<PasswordEntry>> {
+    /// The entry's password, fetched only when the user asks to see or copy it.
+    pub async fn reveal_password(db_manager: &DatabaseManager, id: i64) -> Result<String> {
         let conn = db_manager.get_connection().await?;
-        PasswordRepository::get_all(&conn).await
+        let password = PasswordRepository::get_password(&conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", id)))?;
+        PasswordRepository::record_usage(&conn, id).await?;
+
+        Ok(password)
     }
 
-    pub async fn update_password(db_manager: &DatabaseManager, id: i64, entry: PasswordEntry) -> Result<()> {
+    pub async fn update_password(db_manager: &DatabaseManager, id: i64, mut entry: PasswordEntry) -> Result<()> {
+        validate_entry(&mut entry)?;
+        Self::normalize_uris(&mut entry)?;
+        normalize_labels(&mut entry.folder, &mut entry.tags);
+        let settings = SettingsService::get_settings(db_manager).await?;
         let conn = db_manager.get_connection().await?;
-        PasswordRepository::update(&conn, id, &entry).await
+        TemplateService::validate_custom_fields(&conn, &mut entry).await?;
+        RecoveryCodeService::validate_link(&conn, Some(id), &entry).await?;
+        let existing = PasswordRepository::get_by_id(&conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", id)))?;
+        // Saving a new password clears the compromised flag, so a breached one is flagged again
+        let breached = entry.kind() == ItemKind::Login
+            && existing.password != entry.password
+            && BreachService::is_saved_password_breached(&entry.password).await?;
+
+        // Keep the values being replaced so they can be recovered if the site rejects the change
+        let mut replaced = Vec::new();
+        if existing.password != entry.password && !existing.password.is_empty() {
+            replaced.push((PASSWORD_FIELD.to_string(), existing.password.clone()));
+        }
+        for old in existing.custom_fields.iter().filter(|f| f.concealed && !f.value.is_empty()) {
+            let still_current = entry.custom_fields.iter().any(|f| f.name == old.name && f.value == old.value);
+            if !still_current {
+                replaced.push((custom_field_history_name(&old.name), old.value.clone()));
+            }
+        }
+
+        let tx = conn.transaction().await?;
+        for (field_name, value) in &replaced {
+            PasswordHistoryRepository::create(&tx, id, field_name, value).await?;
+            PasswordHistoryRepository::prune(&tx, id, field_name, settings.password_history_depth).await?;
+        }
+        PasswordRepository::update(&tx, id, &entry).await?;
+        EntryUriRepository::replace_for_entry(&tx, id, &entry.uris).await?;
+        if breached {
+            PasswordRepository::mark_compromised(&tx, id, Some(BREACH_REASON)).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
     }
 
     pub async fn delete_password(db_manager: &DatabaseManager, id: i64) -> Result<()> {
         let conn = db_manager.get_connection().await?;
-        PasswordRepository::delete(&conn, id).await
+        let tx = conn.transaction().await?;
+        RecoveryCodeService::detach_from_entry(&tx, id).await?;
+        PasswordRepository::delete(&tx, id).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Flags the entry's current password as compromised. The flag clears itself
+    /// once the password is changed.
+    pub async fn mark_compromised(db_manager: &DatabaseManager, id: i64, reason: Option<String>) -> Result<()> {
+        let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::get_password(&conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", id)))?;
+        PasswordRepository::mark_compromised(&conn, id, reason.as_deref()).await
+    }
+
+    pub async fn clear_compromised(db_manager: &DatabaseManager, id: i64) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::get_password(&conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", id)))?;
+        PasswordRepository::clear_compromised(&conn, id).await
+    }
+
+    pub async fn list_compromised(db_manager: &DatabaseManager) -> Result<Vec<PasswordSummary>> {
+        let conn = db_manager.get_connection().await?;
+        PasswordRepository::list_compromised(&conn).await
+    }
+
+    /// Substring search over website and username, or with `fuzzy` a typo-tolerant one over
+    /// website, username, URL, folder and tags, ranked by match score, favorites and recency.
+    /// Fuzzy search scores only the searchable columns and loads the best hits in full.
+    pub async fn search_passwords(
+        db_manager: &DatabaseManager,
+        query: &str,
+        kind: Option<ItemKind>,
+        fuzzy: bool,
+    ) -> Result<Vec<PasswordEntry>> {
+        let conn = db_manager.get_connection().await?;
+        if !fuzzy {
+            return PasswordRepository::search(&conn, query, kind).await;
+        }
+
+        let candidates = PasswordRepository::search_fields(&conn, kind).await?;
+        let ids = rank_search_fields(query, &candidates, FUZZY_SEARCH_LIMIT);
+        PasswordRepository::get_by_ids(&conn, &ids).await
     }
 
-    pub async fn search_passwords(db_manager: &DatabaseManager, query: &str) -> Result<Vec<PasswordEntry>> {
+    /// Returns the entries with at least one URI whose match rule accepts `url`.
+    pub async fn find_entries_for_url(db_manager: &DatabaseManager, url: &str) -> Result<Vec<PasswordEntry>> {
+        let target = parse_url(url).ok_or_else(|| DatabaseError::Validation(format!("'{}' is not a valid URL", url)))?;
         let conn = db_manager.get_connection().await?;
-        PasswordRepository::search(&conn, query).await
+
+        let matching: HashSet<i64> = EntryUriRepository::get_all(&conn)
+            .await?
+            .into_iter()
+            .filter(|(_, uri)| uri_matches(uri, &target))
+            .map(|(entry_id, _)| entry_id)
+            .collect();
+        if matching.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries = PasswordRepository::get_all(&conn, None).await?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.id.is_some_and(|id| matching.contains(&id)))
+            .collect())
+    }
+
+    /// Normalizes the entry's URIs and drops duplicates. Logins saved without any URIs
+    /// (e.g. by clients that only know about `website`) get them derived from the website text.
+    fn normalize_uris(entry: &mut PasswordEntry) -> Result<()> {
+        let mut normalized: Vec<EntryUri> = Vec::with_capacity(entry.uris.len());
+        for uri in &entry.uris {
+            let value = normalize_uri(&uri.uri, uri.match_mode).map_err(DatabaseError::Validation)?;
+            if !normalized.iter().any(|u| u.uri == value && u.match_mode == uri.match_mode) {
+                normalized.push(EntryUri {
+                    uri: value,
+                    match_mode: uri.match_mode,
+                });
+            }
+        }
+
+        if normalized.is_empty() && entry.kind() == ItemKind::Login {
+            normalized = website_uris(&entry.website);
+        }
+        entry.uris = normalized;
+        Ok(())
+    }
+
+    pub async fn get_password_history(db_manager: &DatabaseManager, entry_id: i64) -> Result<Vec<PasswordHistoryEntry>> {
+        let conn = db_manager.get_connection().await?;
+        PasswordHistoryRepository::get_for_entry(&conn, entry_id).await
+    }
+
+    pub async fn get_password_history_value(db_manager: &DatabaseManager, history_id: i64) -> Result<String> {
+        let conn = db_manager.get_connection().await?;
+        PasswordHistoryRepository::get_value(&conn, history_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password history item {}", history_id)))
+    }
+}
+
+/// Ids of the `limit` best fuzzy matches for `query`, best first. The website is the
+/// entry's name; ties go to the most recently updated entry.
+fn rank_search_fields(query: &str, candidates: &[EntrySearchFields], limit: usize) -> Vec<i64> {
+    let mut ranked: Vec<(i64, &EntrySearchFields)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let tags = candidate.tags.join(" ");
+            let fields = [
+                candidate.website.as_str(),
+                candidate.username.as_str(),
+                candidate.url.as_deref().unwrap_or(""),
+                candidate.folder.as_deref().unwrap_or(""),
+                tags.as_str(),
+            ];
+            let (score, _) = fuzzy_match_fields(query, &fields)?;
+            Some((score + if candidate.favorite { FAVORITE_BONUS } else { 0 }, candidate))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.updated_at.cmp(&a.1.updated_at)));
+    ranked.into_iter().take(limit).map(|(_, candidate)| candidate.id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_vault(size: usize) -> Vec<EntrySearchFields> {
+        (0..size as i64)
+            .map(|id| EntrySearchFields {
+                id,
+                website: format!("Service {:05}", id),
+                username: format!("user{}@example.com", id % 97),
+                url: Some(format!("https://service{}.example.com/login", id)),
+                folder: Some(format!("team-{}", id % 13)),
+                tags: vec![format!("tag{}", id % 7)],
+                favorite: false,
+                updated_at: Some(format!("2024-01-01 00:00:{:02}", id % 60)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ranks_a_large_vault_and_keeps_the_top_hits() {
+        let mut vault = synthetic_vault(5000);
+        vault[4321].website = "GitHub".to_string();
+        vault[4321].username = "octocat".to_string();
+
+        let ids = rank_search_fields("githb", &vault, FUZZY_SEARCH_LIMIT);
+        assert_eq!(ids.first(), Some(&4321));
+
+        let ids = rank_search_fields("service", &vault, FUZZY_SEARCH_LIMIT);
+        assert_eq!(ids.len(), FUZZY_SEARCH_LIMIT);
+    }
+
+    #[test]
+    fn matches_url_folder_and_tags() {
+        let mut vault = synthetic_vault(3000);
+        vault[10].folder = Some("clients/acme".to_string());
+        vault[20].tags = vec!["billing".to_string()];
+        vault[30].url = Some("https://intranet.corp.example".to_string());
+
+        assert_eq!(rank_search_fields("clients acme", &vault, 5), vec![10]);
+        assert_eq!(rank_search_fields("billing", &vault, 5), vec![20]);
+        assert_eq!(rank_search_fields("intranet", &vault, 5), vec![30]);
+    }
+
+    #[test]
+    fn favorites_and_recent_entries_rank_first() {
+        let mut vault = synthetic_vault(2);
+        for candidate in &mut vault {
+            candidate.website = "Mail".to_string();
+        }
+        vault[0].updated_at = Some("2024-06-01 00:00:00".to_string());
+        assert_eq!(rank_search_fields("mail", &vault, 10), vec![0, 1]);
+
+        vault[1].favorite = true;
+        assert_eq!(rank_search_fields("mail", &vault, 10), vec![1, 0]);
     }
 }
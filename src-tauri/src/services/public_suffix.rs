@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Mozilla's Public Suffix List (ICANN and private sections), refreshed by copying
+/// https://publicsuffix.org/list/public_suffix_list.dat over `data/public_suffix_list.dat`.
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../../data/public_suffix_list.dat");
+
+struct SuffixRules {
+    exact: HashSet<String>,
+    /// Parents of `*.` rules, e.g. `ck` for `*.ck`
+    wildcard: HashSet<String>,
+    /// `!` rules, e.g. `www.ck`
+    exception: HashSet<String>,
+}
+
+fn rules() -> &'static SuffixRules {
+    static RULES: OnceLock<SuffixRules> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let mut rules = SuffixRules {
+            exact: HashSet::new(),
+            wildcard: HashSet::new(),
+            exception: HashSet::new(),
+        };
+        for line in PUBLIC_SUFFIX_LIST.lines() {
+            let Some(rule) = line.split_whitespace().next() else {
+                continue;
+            };
+            if rule.starts_with("//") {
+                continue;
+            }
+            // The list is written in Unicode; hosts reach us in punycode
+            let (set, name) = if let Some(name) = rule.strip_prefix('!') {
+                (&mut rules.exception, name)
+            } else if let Some(name) = rule.strip_prefix("*.") {
+                (&mut rules.wildcard, name)
+            } else {
+                (&mut rules.exact, rule)
+            };
+            if let Ok(ascii) = idna::domain_to_ascii(name) {
+                set.insert(ascii);
+            }
+        }
+        rules
+    })
+}
+
+/// The public suffix of an ASCII (punycode) host name, e.g. `co.uk` for `www.example.co.uk`.
+/// Hosts with an unlisted top-level label fall back to that label, as the list's default `*` rule says.
+pub fn public_suffix(host: &str) -> Option<String> {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if host.is_empty() || host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+        return None;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) {
+        return None;
+    }
+
+    let rules = rules();
+    let mut suffix_len = 1;
+    for start in 0..labels.len() {
+        let candidate = labels[start..].join(".");
+        if rules.exception.contains(&candidate) {
+            // An exception rule wins outright; its suffix is the rule minus the leftmost label
+            suffix_len = labels.len() - start - 1;
+            break;
+        }
+        let parent_is_wildcard = start + 1 < labels.len() && rules.wildcard.contains(&labels[start + 1..].join("."));
+        if rules.exact.contains(&candidate) || parent_is_wildcard {
+            suffix_len = suffix_len.max(labels.len() - start);
+        }
+    }
+    Some(labels[labels.len() - suffix_len..].join("."))
+}
+
+/// The registrable domain (eTLD+1) of a host, e.g. `example.co.uk` for `login.example.co.uk`.
+/// Returns `None` for IP addresses and for hosts that are themselves a public suffix.
+pub fn registrable_domain(host: &str) -> Option<String> {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let suffix = public_suffix(&host)?;
+    if host == suffix {
+        return None;
+    }
+    let rest = &host[..host.len() - suffix.len() - 1];
+    let label = rest.rsplit('.').next()?;
+    Some(format!("{}.{}", label, suffix))
+}
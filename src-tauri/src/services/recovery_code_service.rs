@@ -0,0 +1,132 @@
+use libsql::Connection;
+
+use crate::database::{DatabaseError, DatabaseManager, ItemDetails, ItemKind, PasswordEntry, RecoveryCodeStatus, Result};
+use crate::repositories::PasswordRepository;
+use crate::services::recovery_codes::normalize_recovery_code;
+use crate::services::{utc_timestamp, SettingsService};
+
+pub struct RecoveryCodeService;
+
+impl RecoveryCodeService {
+    /// Marks a code as used, as when it has just been used to sign in. The code is matched
+    /// ignoring case, spaces and dashes.
+    pub async fn consume_recovery_code(db_manager: &DatabaseManager, item_id: i64, code: &str) -> Result<RecoveryCodeStatus> {
+        let threshold = SettingsService::get_settings(db_manager).await?.recovery_code_warning_threshold;
+        let conn = db_manager.get_connection().await?;
+        let mut item = Self::require_item(&conn, item_id).await?;
+        let Some(ItemDetails::RecoveryCodes(codes)) = item.details.as_mut() else {
+            return Err(not_recovery_codes(item_id));
+        };
+
+        let normalized = normalize_recovery_code(code);
+        let saved = codes
+            .codes
+            .iter_mut()
+            .find(|saved| normalize_recovery_code(&saved.code) == normalized)
+            .ok_or_else(|| DatabaseError::Validation("That isn't one of the saved recovery codes".to_string()))?;
+        if saved.used {
+            let when = saved.used_at.as_ref().map(|at| format!(" on {}", at)).unwrap_or_default();
+            return Err(DatabaseError::Validation(format!("Recovery code {} was already used{}", saved.code, when)));
+        }
+        saved.used = true;
+        saved.used_at = Some(utc_timestamp());
+
+        PasswordRepository::update(&conn, item_id, &item).await?;
+        status(item_id, &item, threshold).ok_or_else(|| not_recovery_codes(item_id))
+    }
+
+    pub async fn recovery_code_status(db_manager: &DatabaseManager, item_id: i64) -> Result<RecoveryCodeStatus> {
+        let threshold = SettingsService::get_settings(db_manager).await?.recovery_code_warning_threshold;
+        let conn = db_manager.get_connection().await?;
+        let item = Self::require_item(&conn, item_id).await?;
+        status(item_id, &item, threshold).ok_or_else(|| not_recovery_codes(item_id))
+    }
+
+    /// The recovery-codes items attached to a login.
+    pub async fn recovery_codes_for_entry(db_manager: &DatabaseManager, entry_id: i64) -> Result<Vec<RecoveryCodeStatus>> {
+        Ok(Self::all_statuses(db_manager)
+            .await?
+            .into_iter()
+            .filter(|status| status.entry_id == Some(entry_id))
+            .collect())
+    }
+
+    /// Items with fewer unused codes left than the vault's warning threshold.
+    pub async fn list_low_recovery_codes(db_manager: &DatabaseManager) -> Result<Vec<RecoveryCodeStatus>> {
+        Ok(Self::all_statuses(db_manager)
+            .await?
+            .into_iter()
+            .filter(|status| status.low)
+            .collect())
+    }
+
+    /// Checks that a recovery-codes item is attached to an existing login other than itself.
+    pub async fn validate_link(conn: &Connection, item_id: Option<i64>, entry: &PasswordEntry) -> Result<()> {
+        let Some(ItemDetails::RecoveryCodes(codes)) = &entry.details else {
+            return Ok(());
+        };
+        let Some(entry_id) = codes.entry_id else {
+            return Ok(());
+        };
+        if item_id == Some(entry_id) {
+            return Err(DatabaseError::Validation("Recovery codes can't be attached to themselves".to_string()));
+        }
+        match PasswordRepository::get_by_id(conn, entry_id).await? {
+            Some(login) if login.kind() == ItemKind::Login => Ok(()),
+            Some(other) => Err(DatabaseError::Validation(format!(
+                "Recovery codes can only be attached to a login, and {} isn't one",
+                other.website
+            ))),
+            None => Err(DatabaseError::NotFound(format!("Password entry {}", entry_id))),
+        }
+    }
+
+    /// Unlinks the recovery codes of a login that is being deleted; the codes themselves are kept.
+    pub async fn detach_from_entry(conn: &Connection, entry_id: i64) -> Result<()> {
+        for mut item in PasswordRepository::get_all(conn, Some(ItemKind::RecoveryCodes)).await? {
+            let (Some(item_id), Some(ItemDetails::RecoveryCodes(codes))) = (item.id, item.details.as_mut()) else {
+                continue;
+            };
+            if codes.entry_id == Some(entry_id) {
+                codes.entry_id = None;
+                PasswordRepository::update(conn, item_id, &item).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn all_statuses(db_manager: &DatabaseManager) -> Result<Vec<RecoveryCodeStatus>> {
+        let threshold = SettingsService::get_settings(db_manager).await?.recovery_code_warning_threshold;
+        let conn = db_manager.get_connection().await?;
+        let items = PasswordRepository::get_all(&conn, Some(ItemKind::RecoveryCodes)).await?;
+        Ok(items
+            .iter()
+            .filter_map(|item| status(item.id?, item, threshold))
+            .collect())
+    }
+
+    async fn require_item(conn: &Connection, item_id: i64) -> Result<PasswordEntry> {
+        PasswordRepository::get_by_id(conn, item_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", item_id)))
+    }
+}
+
+fn status(item_id: i64, item: &PasswordEntry, threshold: u32) -> Option<RecoveryCodeStatus> {
+    let Some(ItemDetails::RecoveryCodes(codes)) = &item.details else {
+        return None;
+    };
+    let remaining = codes.codes.iter().filter(|code| !code.used).count();
+    Some(RecoveryCodeStatus {
+        item_id,
+        title: item.website.clone(),
+        entry_id: codes.entry_id,
+        total: codes.codes.len(),
+        remaining,
+        low: remaining < threshold as usize,
+    })
+}
+
+fn not_recovery_codes(item_id: i64) -> DatabaseError {
+    DatabaseError::Validation(format!("Entry {} is not a recovery-codes item", item_id))
+}
@@ -0,0 +1,165 @@
+/// Shortest and longest recovery codes `parse_recovery_codes` accepts, counting letters and digits only.
+const MIN_CODE_LEN: usize = 6;
+const MAX_CODE_LEN: usize = 40;
+
+/// Codes compare ignoring case, spaces and dashes, so `ABCD-1234` matches `abcd 1234`.
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+/// Pulls recovery codes out of text copied from a provider's download or settings page:
+/// one code per line (GitHub, GitLab, Microsoft), numbered or bulleted lists (Discord),
+/// codes laid out in columns (Google's `1. 1234 5678    6. 2345 6789`), and codes inside a
+/// line of text (`Backup code: 1234 5678`). Headings and other prose are skipped: a code is
+/// letters and digits, at least one of them a digit, optionally split by single spaces or
+/// dashes into groups of equal length.
+pub fn parse_recovery_codes(text: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for line in text.lines() {
+        for code in split_columns(line).into_iter().flat_map(codes_in_chunk) {
+            let normalized = normalize_recovery_code(&code);
+            if !codes.iter().any(|c| normalize_recovery_code(c) == normalized) {
+                codes.push(code);
+            }
+        }
+    }
+    codes
+}
+
+/// Tabs, commas, semicolons, pipes and runs of spaces separate columns; a single space
+/// is left alone, as it separates the groups of one code.
+fn split_columns(line: &str) -> Vec<&str> {
+    line.split(['\t', ',', ';', '|'])
+        .flat_map(|part| part.split("  "))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// The chunk itself when it is a code; otherwise the value after a `label:`, or failing that
+/// the words of the chunk that are codes on their own (`Use 1234-5678 to sign in`).
+fn codes_in_chunk(chunk: &str) -> Vec<String> {
+    let chunk = strip_list_marker(chunk);
+    if let Some(code) = code_from_chunk(chunk) {
+        return vec![code];
+    }
+    if let Some(code) = chunk.rsplit_once(':').and_then(|(_, value)| code_from_chunk(value)) {
+        return vec![code];
+    }
+    chunk
+        .split_whitespace()
+        .filter_map(|word| code_from_chunk(word.trim_end_matches(['.', ',', '!', '?', ')']).trim_start_matches('(')))
+        .collect()
+}
+
+fn code_from_chunk(chunk: &str) -> Option<String> {
+    let code = chunk.trim().trim_matches(['"', '\'', '`']).trim();
+    let groups: Vec<&str> = code.split([' ', '-']).filter(|g| !g.is_empty()).collect();
+    let length: usize = groups.iter().map(|g| g.len()).sum();
+    let is_code = !groups.is_empty()
+        && groups.iter().all(|g| g.chars().all(|c| c.is_ascii_alphanumeric()) && g.len() == groups[0].len())
+        && (MIN_CODE_LEN..=MAX_CODE_LEN).contains(&length)
+        && code.chars().any(|c| c.is_ascii_digit());
+    is_code.then(|| code.to_string())
+}
+
+/// Drops a leading bullet (`-`, `*`, `•`) or list number (`1.`, `2)`).
+fn strip_list_marker(chunk: &str) -> &str {
+    let rest = chunk.trim_start_matches(['-', '*', '•', '·', '>']).trim_start();
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if (1..=3).contains(&digits) && matches!(rest[digits..].chars().next(), Some('.' | ')')) {
+        rest[digits + 1..].trim_start()
+    } else {
+        rest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Provider exports as downloaded or copied, with the codes replaced
+
+    const GITHUB: &str = "\
+5f0c1-8a2e4
+b93d7-0e6a1
+27c4e-f18b0
+e0a93-5d27c
+";
+
+    const GITLAB: &str = "\
+9c1e4b7a02f3d865
+1d0f8e6c3b2a4975
+e7b2c940a16d8f53
+";
+
+    const MICROSOFT: &str = "\
+Your new recovery code
+Write down this code and keep it somewhere safe. You'll need it if you lose access to your account.
+MFJ7K-2QWXR-P8D4V-ZT6NB-9CY3H
+";
+
+    const DISCORD: &str = "\
+These are your Discord backup codes for account someone#0001. Keep them safe!
+ * 7kq2-m9xd
+ * p4vn-3hfz
+ * 2rwc-8tjb
+";
+
+    const GOOGLE: &str = "\
+SAVE YOUR BACKUP CODES
+Keep these backup codes somewhere safe but accessible.
+
+1. 4821 9066        6. 7713 0254
+2. 3057 6142        7. 9930 4871
+3. 6604 2298        8. 1186 5530
+4. 0742 8815        9. 5529 3367
+5. 2918 4470        10. 8451 0692
+
+(someone@gmail.com)
+
+* You can only use each backup code once.
+* Need more? Visit g.co/2sv
+* Generated: Jan 15, 2024
+";
+
+    #[test]
+    fn parses_github_codes() {
+        assert_eq!(parse_recovery_codes(GITHUB), ["5f0c1-8a2e4", "b93d7-0e6a1", "27c4e-f18b0", "e0a93-5d27c"]);
+    }
+
+    #[test]
+    fn parses_gitlab_codes() {
+        assert_eq!(parse_recovery_codes(GITLAB), ["9c1e4b7a02f3d865", "1d0f8e6c3b2a4975", "e7b2c940a16d8f53"]);
+    }
+
+    #[test]
+    fn parses_microsoft_code_and_skips_the_prose() {
+        assert_eq!(parse_recovery_codes(MICROSOFT), ["MFJ7K-2QWXR-P8D4V-ZT6NB-9CY3H"]);
+    }
+
+    #[test]
+    fn parses_discord_bullets_and_skips_the_account_name() {
+        assert_eq!(parse_recovery_codes(DISCORD), ["7kq2-m9xd", "p4vn-3hfz", "2rwc-8tjb"]);
+    }
+
+    #[test]
+    fn parses_google_columns_in_reading_order() {
+        let codes = parse_recovery_codes(GOOGLE);
+        assert_eq!(codes.len(), 10);
+        assert_eq!(&codes[..4], ["4821 9066", "7713 0254", "3057 6142", "9930 4871"]);
+        assert_eq!(codes[9], "8451 0692");
+    }
+
+    #[test]
+    fn finds_codes_inside_a_line() {
+        assert_eq!(parse_recovery_codes("Backup code: 1234 5678"), ["1234 5678"]);
+        assert_eq!(parse_recovery_codes("Use 4f9a-c21e to sign in."), ["4f9a-c21e"]);
+        assert_eq!(parse_recovery_codes("Code 1: ABCD-1234, Code 2: EFGH-5678"), ["ABCD-1234", "EFGH-5678"]);
+    }
+
+    #[test]
+    fn drops_duplicates_that_differ_only_in_formatting() {
+        assert_eq!(parse_recovery_codes("ABCD-1234\nabcd 1234\n"), ["ABCD-1234"]);
+    }
+}
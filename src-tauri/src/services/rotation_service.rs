@@ -0,0 +1,212 @@
+use libsql::Connection;
+
+use crate::database::{
+    DatabaseError, DatabaseManager, GeneratorOptions, ItemKind, Result, RotationItemStatus, RotationSession, RotationSessionSummary,
+    RotationStatus,
+};
+use crate::repositories::{PasswordHistoryRepository, PasswordRepository, RotationRepository};
+use crate::services::{GeneratorService, SettingsService, PASSWORD_FIELD};
+
+pub struct RotationService;
+
+impl RotationService {
+    /// Starts rotating the given logins: a new password is generated for each under `options`
+    /// and staged next to the current one until the user confirms or rolls it back.
+    pub async fn start_session(
+        db_manager: &DatabaseManager,
+        entry_ids: &[i64],
+        options: GeneratorOptions,
+        name: Option<String>,
+    ) -> Result<RotationSession> {
+        let mut ids = entry_ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() {
+            return Err(DatabaseError::Validation("Choose at least one entry to rotate".to_string()));
+        }
+        let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+        let conn = db_manager.get_connection().await?;
+        let mut entries = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let entry = PasswordRepository::get_by_id(&conn, *id)
+                .await?
+                .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", id)))?;
+            if entry.kind() != ItemKind::Login {
+                return Err(DatabaseError::Validation(format!("{} is not a login and has no password to rotate", entry.website)));
+            }
+            entries.push(entry);
+        }
+        let busy = RotationRepository::pending_entries(&conn, &ids).await?;
+        if let Some(entry) = entries.iter().find(|e| e.id.is_some_and(|id| busy.contains(&id))) {
+            return Err(DatabaseError::Validation(format!(
+                "{} already has a new password waiting in another rotation session",
+                entry.website
+            )));
+        }
+
+        let mut passwords = Vec::with_capacity(ids.len());
+        for _ in &ids {
+            passwords.push(GeneratorService::generate_password(&options)?.password);
+        }
+
+        let tx = conn.transaction().await?;
+        let session_id = RotationRepository::create_session(&tx, name.as_deref(), &options).await?;
+        for (id, password) in ids.iter().zip(&passwords) {
+            RotationRepository::add_item(&tx, session_id, *id, password).await?;
+        }
+        let message = format!(
+            "Started rotating {} {}",
+            ids.len(),
+            if ids.len() == 1 { "entry" } else { "entries" }
+        );
+        RotationRepository::add_log(&tx, session_id, None, &message).await?;
+        tx.commit().await?;
+
+        Self::require_session(&conn, session_id).await
+    }
+
+    pub async fn get_session(db_manager: &DatabaseManager, session_id: i64) -> Result<RotationSession> {
+        let conn = db_manager.get_connection().await?;
+        Self::require_session(&conn, session_id).await
+    }
+
+    pub async fn list_sessions(db_manager: &DatabaseManager) -> Result<Vec<RotationSessionSummary>> {
+        let conn = db_manager.get_connection().await?;
+        RotationRepository::list_sessions(&conn).await
+    }
+
+    /// The staged password, for the user to enter on the site.
+    pub async fn reveal_pending_password(db_manager: &DatabaseManager, session_id: i64, entry_id: i64) -> Result<String> {
+        let conn = db_manager.get_connection().await?;
+        Self::require_pending(&conn, session_id, entry_id).await
+    }
+
+    /// Replaces the staged password with a fresh one, e.g. when the site rejects the first.
+    pub async fn regenerate_pending_password(db_manager: &DatabaseManager, session_id: i64, entry_id: i64) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        let session = Self::require_active(&conn, session_id).await?;
+        Self::require_pending(&conn, session_id, entry_id).await?;
+        let password = GeneratorService::generate_password(&session.generator_options)?.password;
+
+        let tx = conn.transaction().await?;
+        RotationRepository::set_item(&tx, session_id, entry_id, RotationItemStatus::Pending, Some(&password)).await?;
+        let message = format!("Generated another new password for {}", Self::entry_name(&tx, entry_id).await?);
+        RotationRepository::add_log(&tx, session_id, Some(entry_id), &message).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// The site accepted the new password: it becomes the entry's password and the old one goes to history.
+    pub async fn confirm_rotation(db_manager: &DatabaseManager, session_id: i64, entry_id: i64) -> Result<RotationSession> {
+        let settings = SettingsService::get_settings(db_manager).await?;
+        let conn = db_manager.get_connection().await?;
+        Self::require_active(&conn, session_id).await?;
+        let new_password = Self::require_pending(&conn, session_id, entry_id).await?;
+        let current = PasswordRepository::get_password(&conn, entry_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Password entry {}", entry_id)))?;
+
+        let tx = conn.transaction().await?;
+        if !current.is_empty() && current != new_password {
+            PasswordHistoryRepository::create(&tx, entry_id, PASSWORD_FIELD, &current).await?;
+            PasswordHistoryRepository::prune(&tx, entry_id, PASSWORD_FIELD, settings.password_history_depth).await?;
+        }
+        PasswordRepository::set_password(&tx, entry_id, &new_password).await?;
+        RotationRepository::set_item(&tx, session_id, entry_id, RotationItemStatus::Promoted, None).await?;
+        let message = format!("Confirmed the new password for {}", Self::entry_name(&tx, entry_id).await?);
+        RotationRepository::add_log(&tx, session_id, Some(entry_id), &message).await?;
+        Self::finish_if_done(&tx, session_id).await?;
+        tx.commit().await?;
+
+        Self::require_session(&conn, session_id).await
+    }
+
+    /// The site didn't take the new password: the entry keeps its current one and the staged one is discarded.
+    pub async fn roll_back_rotation(db_manager: &DatabaseManager, session_id: i64, entry_id: i64) -> Result<RotationSession> {
+        let conn = db_manager.get_connection().await?;
+        Self::require_active(&conn, session_id).await?;
+        Self::require_pending(&conn, session_id, entry_id).await?;
+
+        let tx = conn.transaction().await?;
+        RotationRepository::set_item(&tx, session_id, entry_id, RotationItemStatus::RolledBack, None).await?;
+        let message = format!("Kept the current password for {}", Self::entry_name(&tx, entry_id).await?);
+        RotationRepository::add_log(&tx, session_id, Some(entry_id), &message).await?;
+        Self::finish_if_done(&tx, session_id).await?;
+        tx.commit().await?;
+
+        Self::require_session(&conn, session_id).await
+    }
+
+    /// Rolls back every entry still pending and closes the session.
+    pub async fn cancel_session(db_manager: &DatabaseManager, session_id: i64) -> Result<RotationSession> {
+        let conn = db_manager.get_connection().await?;
+        Self::require_active(&conn, session_id).await?;
+        let items = RotationRepository::get_items(&conn, session_id).await?;
+
+        let tx = conn.transaction().await?;
+        let mut rolled_back = 0;
+        for item in items.iter().filter(|i| i.status == RotationItemStatus::Pending) {
+            RotationRepository::set_item(&tx, session_id, item.entry_id, RotationItemStatus::RolledBack, None).await?;
+            rolled_back += 1;
+        }
+        RotationRepository::set_session_status(&tx, session_id, RotationStatus::Cancelled).await?;
+        let message = format!(
+            "Cancelled the session; kept the current password for {} {}",
+            rolled_back,
+            if rolled_back == 1 { "entry" } else { "entries" }
+        );
+        RotationRepository::add_log(&tx, session_id, None, &message).await?;
+        tx.commit().await?;
+
+        Self::require_session(&conn, session_id).await
+    }
+
+    async fn finish_if_done(conn: &Connection, session_id: i64) -> Result<()> {
+        if RotationRepository::count_pending(conn, session_id).await? == 0 {
+            RotationRepository::set_session_status(conn, session_id, RotationStatus::Completed).await?;
+            RotationRepository::add_log(conn, session_id, None, "Every entry has been confirmed or rolled back").await?;
+        }
+        Ok(())
+    }
+
+    async fn require_session(conn: &Connection, session_id: i64) -> Result<RotationSession> {
+        let mut session = RotationRepository::get_session(conn, session_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Rotation session {}", session_id)))?;
+        session.items = RotationRepository::get_items(conn, session_id).await?;
+        session.log = RotationRepository::get_log(conn, session_id).await?;
+        Ok(session)
+    }
+
+    async fn require_active(conn: &Connection, session_id: i64) -> Result<RotationSession> {
+        let session = RotationRepository::get_session(conn, session_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Rotation session {}", session_id)))?;
+        if session.status != RotationStatus::Active {
+            return Err(DatabaseError::Validation(format!("Rotation session {} is already {}", session_id, session.status.as_str())));
+        }
+        Ok(session)
+    }
+
+    /// The staged password of an item that is still waiting for confirmation.
+    async fn require_pending(conn: &Connection, session_id: i64, entry_id: i64) -> Result<String> {
+        match RotationRepository::get_item(conn, session_id, entry_id).await? {
+            Some((RotationItemStatus::Pending, Some(password))) => Ok(password),
+            Some((status, _)) => Err(DatabaseError::Validation(format!(
+                "Entry {} is already {} in this session",
+                entry_id,
+                status.as_str().replace('_', " ")
+            ))),
+            None => Err(DatabaseError::NotFound(format!("Entry {} in rotation session {}", entry_id, session_id))),
+        }
+    }
+
+    async fn entry_name(conn: &Connection, entry_id: i64) -> Result<String> {
+        Ok(PasswordRepository::get_by_id(conn, entry_id)
+            .await?
+            .map(|entry| entry.website)
+            .unwrap_or_else(|| format!("entry {}", entry_id)))
+    }
+}
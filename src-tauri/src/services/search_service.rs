@@ -0,0 +1,124 @@
+use crate::database::{DatabaseError, DatabaseManager, ItemKind, Result, SearchHit, SnippetSegment, VaultQueryResults};
+use crate::repositories::{NotesRepository, PasswordRepository, SearchRepository};
+use crate::services::{fuzzy_match_fields, weak_password_strength, VaultQuery};
+
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const MAX_SEARCH_LIMIT: u32 = 500;
+
+/// Added to the fuzzy score of favorites: about one well-placed character.
+pub const FAVORITE_BONUS: i64 = 16;
+
+pub struct SearchService;
+
+impl SearchService {
+    /// Full-text search across entries and notes. Words match as prefixes (`git` finds `github`),
+    /// text in double quotes matches as a phrase, and every term has to match.
+    /// With `fuzzy`, website, username and note titles are matched typo-tolerantly instead (see `fuzzy_search_vault`).
+    pub async fn search_vault(db_manager: &DatabaseManager, query: &str, limit: Option<u32>, fuzzy: bool) -> Result<Vec<SearchHit>> {
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).min(MAX_SEARCH_LIMIT);
+        if fuzzy {
+            return Self::fuzzy_search_vault(db_manager, query, limit).await;
+        }
+        let Some(match_query) = fts_match_query(query) else {
+            return Ok(Vec::new());
+        };
+        let conn = db_manager.get_connection().await?;
+        SearchRepository::search(&conn, &match_query, limit).await
+    }
+
+    /// fzf-style search over entry websites and usernames and note titles, ranked by match
+    /// score (favorites get a boost) and then by most recently updated.
+    pub async fn fuzzy_search_vault(db_manager: &DatabaseManager, query: &str, limit: u32) -> Result<Vec<SearchHit>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let conn = db_manager.get_connection().await?;
+        let candidates = SearchRepository::get_candidates(&conn).await?;
+
+        let mut ranked: Vec<(i64, Option<String>, SearchHit)> = candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                let username = candidate.username.as_deref().unwrap_or_default();
+                let (score, positions) = fuzzy_match_fields(query, &[&candidate.title, username])?;
+                let snippet = if positions[0].is_empty() && !positions[1].is_empty() {
+                    highlight(username, &positions[1])
+                } else {
+                    highlight(&candidate.title, &positions[0])
+                };
+                let boost = if candidate.favorite { FAVORITE_BONUS } else { 0 };
+                Some((
+                    score + boost,
+                    candidate.updated_at,
+                    SearchHit {
+                        kind: candidate.kind,
+                        id: candidate.id,
+                        title: candidate.title,
+                        username: candidate.username,
+                        snippet,
+                        score: score as f64,
+                    },
+                ))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+        ranked.truncate(limit as usize);
+        Ok(ranked.into_iter().map(|(_, _, hit)| hit).collect())
+    }
+
+    /// Runs a structured query (see `VaultQuery`) over entries and notes.
+    pub async fn query_vault(db_manager: &DatabaseManager, query: &str) -> Result<VaultQueryResults> {
+        let query = VaultQuery::parse(query).map_err(|e| DatabaseError::Validation(e.to_string()))?;
+        let conn = db_manager.get_connection().await?;
+
+        let weak_ids: Vec<i64> = if query.filters_weak() {
+            PasswordRepository::get_all(&conn, Some(ItemKind::Login))
+                .await?
+                .iter()
+                .filter(|entry| weak_password_strength(entry).is_some())
+                .filter_map(|entry| entry.id)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let entry_filter = query.entry_filter(&weak_ids);
+        let note_filter = query.note_filter();
+        Ok(VaultQueryResults {
+            entries: PasswordRepository::query(&conn, &entry_filter.sql, entry_filter.params).await?,
+            notes: NotesRepository::query(&conn, &note_filter.sql, note_filter.params).await?,
+        })
+    }
+}
+
+/// Splits a text into highlighted and plain segments at the given character positions.
+pub fn highlight(text: &str, positions: &[usize]) -> Vec<SnippetSegment> {
+    let mut segments: Vec<SnippetSegment> = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = positions.binary_search(&i).is_ok();
+        match segments.last_mut() {
+            Some(segment) if segment.highlight == highlighted => segment.text.push(c),
+            _ => segments.push(SnippetSegment {
+                text: c.to_string(),
+                highlight: highlighted,
+            }),
+        }
+    }
+    segments
+}
+
+/// Turns search box input into an FTS5 MATCH expression. Every term is quoted, so FTS5
+/// operators and column filters typed by the user are searched for as plain text.
+pub fn fts_match_query(input: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    for (index, part) in input.split('"').enumerate() {
+        if index % 2 == 1 {
+            let phrase = part.trim();
+            if !phrase.is_empty() {
+                terms.push(format!("\"{}\"", phrase));
+            }
+        } else {
+            terms.extend(part.split_whitespace().map(|word| format!("\"{}\"*", word)));
+        }
+    }
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use libsql::Connection;
+use crate::database::{DatabaseManager, MasterPasswordPolicy, Result, VaultSettings};
+use crate::repositories::ConfigRepository;
+
+const PASSWORD_HISTORY_DEPTH_KEY: &str = "password_history_depth";
+const ATTACHMENT_MAX_BYTES_KEY: &str = "attachment_max_bytes";
+const EXPIRY_WARNING_DAYS_KEY: &str = "expiry_warning_days";
+const RECOVERY_CODE_WARNING_THRESHOLD_KEY: &str = "recovery_code_warning_threshold";
+const MASTER_MIN_ENTROPY_BITS_KEY: &str = "master_password_min_entropy_bits";
+const MASTER_BLOCK_COMMON_KEY: &str = "master_password_block_common";
+const MASTER_FORBID_ENTRY_REUSE_KEY: &str = "master_password_forbid_entry_reuse";
+
+pub struct SettingsService;
+
+impl SettingsService {
+    pub async fn get_settings(db_manager: &DatabaseManager) -> Result<VaultSettings> {
+        let conn = db_manager.get_connection().await?;
+        let defaults = VaultSettings::default();
+        let policy = defaults.master_password_policy;
+
+        Ok(VaultSettings {
+            password_history_depth: Self::read(&conn, PASSWORD_HISTORY_DEPTH_KEY, defaults.password_history_depth).await?,
+            attachment_max_bytes: Self::read(&conn, ATTACHMENT_MAX_BYTES_KEY, defaults.attachment_max_bytes).await?,
+            master_password_policy: MasterPasswordPolicy {
+                min_entropy_bits: Self::read(&conn, MASTER_MIN_ENTROPY_BITS_KEY, policy.min_entropy_bits).await?,
+                block_common_passwords: Self::read(&conn, MASTER_BLOCK_COMMON_KEY, policy.block_common_passwords).await?,
+                forbid_entry_reuse: Self::read(&conn, MASTER_FORBID_ENTRY_REUSE_KEY, policy.forbid_entry_reuse).await?,
+            },
+            expiry_warning_days: Self::read(&conn, EXPIRY_WARNING_DAYS_KEY, defaults.expiry_warning_days).await?,
+            recovery_code_warning_threshold: Self::read(
+                &conn,
+                RECOVERY_CODE_WARNING_THRESHOLD_KEY,
+                defaults.recovery_code_warning_threshold,
+            )
+            .await?,
+        })
+    }
+
+    pub async fn update_settings(db_manager: &DatabaseManager, settings: VaultSettings) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        let tx = conn.transaction().await?;
+
+        ConfigRepository::set(&tx, PASSWORD_HISTORY_DEPTH_KEY, &settings.password_history_depth.to_string()).await?;
+        ConfigRepository::set(&tx, ATTACHMENT_MAX_BYTES_KEY, &settings.attachment_max_bytes.to_string()).await?;
+        let policy = &settings.master_password_policy;
+        ConfigRepository::set(&tx, MASTER_MIN_ENTROPY_BITS_KEY, &policy.min_entropy_bits.to_string()).await?;
+        ConfigRepository::set(&tx, MASTER_BLOCK_COMMON_KEY, &policy.block_common_passwords.to_string()).await?;
+        ConfigRepository::set(&tx, MASTER_FORBID_ENTRY_REUSE_KEY, &policy.forbid_entry_reuse.to_string()).await?;
+        ConfigRepository::set(&tx, EXPIRY_WARNING_DAYS_KEY, &settings.expiry_warning_days.to_string()).await?;
+        ConfigRepository::set(
+            &tx,
+            RECOVERY_CODE_WARNING_THRESHOLD_KEY,
+            &settings.recovery_code_warning_threshold.to_string(),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Reads a setting, falling back to the default when it is missing or unparsable.
+    async fn read<T: FromStr>(conn: &Connection, key: &str, default: T) -> Result<T> {
+        Ok(ConfigRepository::get(conn, key)
+            .await?
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default))
+    }
+}
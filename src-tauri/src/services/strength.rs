@@ -0,0 +1,976 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::database::PasswordStrength;
+
+const COMMON_PASSWORDS: &str = include_str!("../../data/common_passwords.txt");
+const COMMON_NAMES: &str = include_str!("../../data/common_names.txt");
+const ENGLISH_WORDS: &str = include_str!("../../data/bip39_english.txt");
+
+/// Only this much of a password is analysed; longer ones are scored on their first 100 characters.
+const MAX_PASSWORD_LEN: usize = 100;
+const MAX_WORD_LEN: usize = 32;
+
+// Scoring constants from zxcvbn
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const MIN_YEAR_SPACE: f64 = 20.0;
+const MAX_SEQUENCE_DELTA: i32 = 5;
+const DATE_MIN_YEAR: i32 = 1000;
+const DATE_MAX_YEAR: i32 = 2050;
+
+/// Crack times assume an offline attack on a slow hash such as bcrypt or Argon2.
+const GUESSES_PER_SECOND: f64 = 10_000.0;
+
+/// Unranked lists count every word as this far down a frequency list.
+const ENGLISH_RANK: usize = 1024;
+
+const L33T_TABLE: &[(char, &str)] = &[
+    ('4', "a"), ('@', "a"), ('8', "b"), ('(', "c"), ('{', "c"), ('[', "c"), ('<', "c"), ('3', "e"),
+    ('6', "g"), ('9', "g"), ('1', "il"), ('!', "i"), ('|', "il"), ('0', "o"), ('$', "s"), ('5', "s"),
+    ('7', "lt"), ('+', "t"), ('%', "x"), ('2', "z"),
+];
+
+/// Substituted words with more spellings than this aren't checked.
+const MAX_L33T_VARIANTS: usize = 64;
+
+const QWERTY: &str = r#"
+`~ 1! 2@ 3# 4$ 5% 6^ 7& 8* 9( 0) -_ =+
+    qQ wW eE rR tT yY uU iI oO pP [{ ]} \|
+     aA sS dD fF gG hH jJ kK lL ;: '"
+      zZ xX cC vV bB nN mM ,< .> /?
+"#;
+
+const KEYPAD: &str = "
+  / * -
+7 8 9 +
+4 5 6
+1 2 3
+  0 .
+";
+
+pub struct StrengthService;
+
+impl StrengthService {
+    /// zxcvbn-style estimate: splits the password into the likeliest sequence of dictionary
+    /// words, keyboard walks, repeats, sequences, dates and random runs, and scores it by the
+    /// number of guesses an attacker trying those patterns would need. `user_inputs` are
+    /// details that shouldn't appear in the password, such as the site and username.
+    pub fn estimate(password: &str, user_inputs: &[String]) -> PasswordStrength {
+        let chars: Vec<char> = password.chars().take(MAX_PASSWORD_LEN).collect();
+        let user_dictionary = user_dictionary(user_inputs);
+        let analysis = most_guessable(&chars, &user_dictionary);
+
+        let guesses_log10 = analysis.guesses.log10();
+        let crack_time_seconds = analysis.guesses / GUESSES_PER_SECOND;
+        let score = guesses_to_score(analysis.guesses);
+        let (warning, feedback) = feedback(score, &analysis.sequence, chars.is_empty());
+
+        PasswordStrength {
+            score,
+            feedback,
+            warning,
+            guesses_log10,
+            crack_time_seconds,
+            crack_time_display: display_time(crack_time_seconds),
+        }
+    }
+
+    /// Whether the password, ignoring case, is on the common-passwords list.
+    pub fn is_common_password(password: &str) -> bool {
+        let password = password.to_lowercase();
+        dictionaries()
+            .iter()
+            .any(|(dictionary, words)| *dictionary == Dictionary::Passwords && words.contains_key(&password))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dictionary {
+    Passwords,
+    English,
+    Names,
+    UserInputs,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Dictionary { dictionary: Dictionary, rank: usize, reversed: bool, l33t: bool },
+    Spatial { turns: usize },
+    Repeat { base_len: usize },
+    Sequence,
+    Year,
+    Date,
+    Bruteforce,
+}
+
+#[derive(Debug, Clone)]
+struct Match {
+    i: usize,
+    j: usize, // inclusive
+    token: String,
+    pattern: Pattern,
+    guesses: f64,
+}
+
+struct Analysis {
+    guesses: f64,
+    sequence: Vec<Match>,
+}
+
+fn dictionaries() -> &'static [(Dictionary, HashMap<String, usize>)] {
+    static DICTIONARIES: OnceLock<Vec<(Dictionary, HashMap<String, usize>)>> = OnceLock::new();
+    DICTIONARIES.get_or_init(|| {
+        let ranked = |data: &str| {
+            let mut words = HashMap::new();
+            for line in data.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+                let rank = words.len() + 1;
+                words.entry(line.to_lowercase()).or_insert(rank);
+            }
+            words
+        };
+        let english = ENGLISH_WORDS.lines().map(|w| (w.trim().to_string(), ENGLISH_RANK)).collect();
+        vec![
+            (Dictionary::Passwords, ranked(COMMON_PASSWORDS)),
+            (Dictionary::Names, ranked(COMMON_NAMES)),
+            (Dictionary::English, english),
+        ]
+    })
+}
+
+/// The user inputs and their alphanumeric parts ("jane.doe@example.com" also gives
+/// "jane", "doe" and "example"), ranked in the order given.
+fn user_dictionary(user_inputs: &[String]) -> HashMap<String, usize> {
+    let mut words = HashMap::new();
+    for input in user_inputs {
+        let input = input.to_lowercase();
+        let parts = std::iter::once(input.as_str()).chain(input.split(|c: char| !c.is_alphanumeric()));
+        for part in parts.filter(|p| p.chars().count() >= 3) {
+            let rank = words.len() + 1;
+            words.entry(part.to_string()).or_insert(rank);
+        }
+    }
+    words
+}
+
+fn most_guessable(password: &[char], user_dictionary: &HashMap<String, usize>) -> Analysis {
+    let n = password.len();
+    if n == 0 {
+        return Analysis { guesses: 1.0, sequence: Vec::new() };
+    }
+    let mut matches = omnimatch(password, user_dictionary);
+    for m in &mut matches {
+        // A pattern covering only part of the password still costs the attacker something
+        if m.j - m.i + 1 < n {
+            let min = if m.i == m.j { MIN_SUBMATCH_GUESSES_SINGLE_CHAR } else { MIN_SUBMATCH_GUESSES_MULTI_CHAR };
+            m.guesses = m.guesses.max(min);
+        }
+    }
+
+    // best[k][l]: the cheapest way to cover password[..=k] with l matches,
+    // as (index into `matches`, product of guesses, total guesses)
+    let mut best: Vec<BTreeMap<usize, (usize, f64, f64)>> = vec![BTreeMap::new(); n];
+    let mut by_end: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (index, m) in matches.iter().enumerate() {
+        by_end[m.j].push(index);
+    }
+
+    for (k, ending_here) in by_end.iter().enumerate() {
+        for &index in ending_here {
+            let i = matches[index].i;
+            if i == 0 {
+                consider(&mut best, &matches, index, 1, 1.0);
+            } else {
+                let previous: Vec<(usize, f64)> = best[i - 1].iter().map(|(l, (_, pi, _))| (*l, *pi)).collect();
+                for (l, pi) in previous {
+                    consider(&mut best, &matches, index, l + 1, pi);
+                }
+            }
+        }
+
+        // Random characters from any point up to k, after anything that isn't itself random
+        for i in 0..=k {
+            let index = matches.len();
+            matches.push(bruteforce_match(password, i, k));
+            if i == 0 {
+                consider(&mut best, &matches, index, 1, 1.0);
+                continue;
+            }
+            let previous: Vec<(usize, f64)> = best[i - 1]
+                .iter()
+                .filter(|(_, (m, _, _))| !matches!(matches[*m].pattern, Pattern::Bruteforce))
+                .map(|(l, (_, pi, _))| (*l, *pi))
+                .collect();
+            for (l, pi) in previous {
+                consider(&mut best, &matches, index, l + 1, pi);
+            }
+        }
+    }
+
+    let (&length, &(_, _, guesses)) = best[n - 1]
+        .iter()
+        .min_by(|a, b| a.1 .2.total_cmp(&b.1 .2))
+        .expect("a bruteforce match always covers the whole password");
+
+    let mut sequence = Vec::with_capacity(length);
+    let (mut k, mut l) = (n as isize - 1, length);
+    while k >= 0 {
+        let (index, _, _) = best[k as usize][&l];
+        sequence.push(matches[index].clone());
+        k = matches[index].i as isize - 1;
+        l -= 1;
+    }
+    sequence.reverse();
+
+    Analysis { guesses, sequence }
+}
+
+/// Records `matches[index]` as the `l`th match of a sequence whose earlier matches multiply to
+/// `previous_pi`, unless a sequence of the same or fewer matches already ends there more cheaply.
+fn consider(best: &mut [BTreeMap<usize, (usize, f64, f64)>], matches: &[Match], index: usize, l: usize, previous_pi: f64) {
+    let m = &matches[index];
+    let pi = previous_pi * m.guesses;
+    // The attacker also has to guess how many patterns there are and in which order
+    let guesses = factorial(l) * pi + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(l as i32 - 1);
+    if best[m.j].iter().any(|(other_l, (_, _, other))| *other_l <= l && *other <= guesses) {
+        return;
+    }
+    best[m.j].insert(l, (index, pi, guesses));
+}
+
+fn bruteforce_match(password: &[char], i: usize, j: usize) -> Match {
+    let len = j - i + 1;
+    let min = if len == 1 { MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1.0 } else { MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1.0 };
+    Match {
+        i,
+        j,
+        token: password[i..=j].iter().collect(),
+        pattern: Pattern::Bruteforce,
+        guesses: BRUTEFORCE_CARDINALITY.powi(len as i32).max(min),
+    }
+}
+
+fn omnimatch(password: &[char], user_dictionary: &HashMap<String, usize>) -> Vec<Match> {
+    let lowered: Vec<char> = password.iter().flat_map(|c| c.to_lowercase()).collect();
+    // Case folding that changes the length (ß, İ) would misalign positions; skip dictionaries then
+    let lowered = (lowered.len() == password.len()).then_some(lowered);
+
+    let mut matches = Vec::new();
+    if let Some(lowered) = &lowered {
+        dictionary_matches(password, lowered, user_dictionary, &mut matches);
+        reversed_dictionary_matches(password, lowered, user_dictionary, &mut matches);
+        l33t_matches(password, lowered, user_dictionary, &mut matches);
+    }
+    spatial_matches(password, &mut matches);
+    repeat_matches(password, user_dictionary, &mut matches);
+    sequence_matches(password, &mut matches);
+    year_matches(password, &mut matches);
+    date_matches(password, &mut matches);
+    matches
+}
+
+fn all_dictionaries(user_dictionary: &HashMap<String, usize>) -> impl Iterator<Item = (Dictionary, &HashMap<String, usize>)> {
+    dictionaries()
+        .iter()
+        .map(|(dictionary, words)| (*dictionary, words))
+        .chain(std::iter::once((Dictionary::UserInputs, user_dictionary)))
+}
+
+fn dictionary_matches(password: &[char], lowered: &[char], user_dictionary: &HashMap<String, usize>, matches: &mut Vec<Match>) {
+    for i in 0..password.len() {
+        for j in i..password.len().min(i + MAX_WORD_LEN) {
+            let word: String = lowered[i..=j].iter().collect();
+            for (dictionary, words) in all_dictionaries(user_dictionary) {
+                if let Some(&rank) = words.get(&word) {
+                    let token: String = password[i..=j].iter().collect();
+                    let guesses = rank as f64 * uppercase_variations(&token);
+                    matches.push(Match {
+                        i,
+                        j,
+                        token,
+                        pattern: Pattern::Dictionary { dictionary, rank, reversed: false, l33t: false },
+                        guesses,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn reversed_dictionary_matches(
+    password: &[char],
+    lowered: &[char],
+    user_dictionary: &HashMap<String, usize>,
+    matches: &mut Vec<Match>,
+) {
+    let n = password.len();
+    let reversed_password: Vec<char> = password.iter().rev().copied().collect();
+    let reversed_lowered: Vec<char> = lowered.iter().rev().copied().collect();
+    let mut reversed = Vec::new();
+    dictionary_matches(&reversed_password, &reversed_lowered, user_dictionary, &mut reversed);
+
+    for mut m in reversed {
+        let token: String = m.token.chars().rev().collect();
+        if token.to_lowercase() == m.token.to_lowercase() {
+            continue; // palindromes were already found forwards
+        }
+        if let Pattern::Dictionary { reversed, .. } = &mut m.pattern {
+            *reversed = true;
+        }
+        matches.push(Match { i: n - 1 - m.j, j: n - 1 - m.i, token, guesses: m.guesses * 2.0, pattern: m.pattern });
+    }
+}
+
+/// Dictionary words spelled with look-alike digits and symbols ("p4$$w0rd").
+fn l33t_matches(password: &[char], lowered: &[char], user_dictionary: &HashMap<String, usize>, matches: &mut Vec<Match>) {
+    let substitutes = |c: char| L33T_TABLE.iter().find(|(l33t, _)| *l33t == c).map(|(_, letters)| *letters);
+
+    for i in 0..password.len() {
+        for j in (i + 1)..password.len().min(i + MAX_WORD_LEN) {
+            let window = &lowered[i..=j];
+            if !window.iter().any(|c| substitutes(*c).is_some()) {
+                continue;
+            }
+            // Every spelling, trying each letter an ambiguous character could stand for
+            let mut variants: Vec<Vec<char>> = vec![Vec::new()];
+            for &c in window {
+                let options: Vec<char> = substitutes(c).map(|s| s.chars().collect()).unwrap_or_else(|| vec![c]);
+                variants = variants
+                    .into_iter()
+                    .flat_map(|prefix| {
+                        options.iter().map(move |o| {
+                            let mut next = prefix.clone();
+                            next.push(*o);
+                            next
+                        })
+                    })
+                    .collect();
+                if variants.len() > MAX_L33T_VARIANTS {
+                    break;
+                }
+            }
+            if variants.len() > MAX_L33T_VARIANTS {
+                continue;
+            }
+
+            for variant in variants {
+                let word: String = variant.iter().collect();
+                for (dictionary, words) in all_dictionaries(user_dictionary) {
+                    let Some(&rank) = words.get(&word) else {
+                        continue;
+                    };
+                    let token: String = password[i..=j].iter().collect();
+                    let guesses = rank as f64 * uppercase_variations(&token) * l33t_variations(window, &variant);
+                    matches.push(Match {
+                        i,
+                        j,
+                        token,
+                        pattern: Pattern::Dictionary { dictionary, rank, reversed: false, l33t: true },
+                        guesses,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Walks across neighbouring keys on a QWERTY keyboard or number pad ("qwerty", "zxcvbn", "7896").
+fn spatial_matches(password: &[char], matches: &mut Vec<Match>) {
+    let n = password.len();
+    for keyboard in keyboards() {
+        let mut i = 0;
+        while i + 1 < n {
+            let mut j = i + 1;
+            let mut last_direction = None;
+            let mut turns = 0;
+            let mut shifted = usize::from(keyboard.is_shifted(password[i]));
+            loop {
+                let step = (j < n).then(|| keyboard.step(password[j - 1], password[j])).flatten();
+                match step {
+                    Some((direction, is_shifted)) => {
+                        if last_direction != Some(direction) {
+                            turns += 1;
+                            last_direction = Some(direction);
+                        }
+                        shifted += usize::from(is_shifted);
+                        j += 1;
+                    }
+                    None => {
+                        if j - i > 2 {
+                            matches.push(Match {
+                                i,
+                                j: j - 1,
+                                token: password[i..j].iter().collect(),
+                                pattern: Pattern::Spatial { turns },
+                                guesses: keyboard.guesses(j - i, turns, shifted),
+                            });
+                        }
+                        i = j;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct Keyboard {
+    neighbours: HashMap<char, Vec<Option<&'static str>>>,
+    starting_positions: f64,
+    average_degree: f64,
+    shifted_keys: bool,
+}
+
+impl Keyboard {
+    /// Parses a layout drawing. Slanted keyboards have each row offset by a third of a key
+    /// per row, as on a real keyboard; number pads are a straight grid.
+    fn new(layout: &'static str, slanted: bool) -> Self {
+        let x_unit = if slanted { 3 } else { 2 };
+        let mut positions: HashMap<(isize, isize), &'static str> = HashMap::new();
+        for (y, line) in layout.lines().filter(|l| !l.trim().is_empty()).enumerate() {
+            let mut start = None;
+            for (index, c) in line.char_indices().chain(std::iter::once((line.len(), ' '))) {
+                match (c == ' ', start) {
+                    (false, None) => start = Some(index),
+                    (true, Some(s)) => {
+                        let x = (s - if slanted { y } else { 0 }) / x_unit;
+                        positions.insert((x as isize, y as isize), &line[s..index]);
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let directions: &[(isize, isize)] = if slanted {
+            &[(-1, 0), (0, -1), (1, -1), (1, 0), (0, 1), (-1, 1)]
+        } else {
+            &[(-1, 0), (-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1)]
+        };
+        let mut neighbours = HashMap::new();
+        let mut degree_total = 0;
+        for (&(x, y), key) in &positions {
+            let adjacent: Vec<Option<&'static str>> =
+                directions.iter().map(|(dx, dy)| positions.get(&(x + dx, y + dy)).copied()).collect();
+            degree_total += adjacent.iter().flatten().count();
+            for c in key.chars() {
+                neighbours.insert(c, adjacent.clone());
+            }
+        }
+
+        Self {
+            neighbours,
+            starting_positions: positions.len() as f64,
+            average_degree: degree_total as f64 / positions.len() as f64,
+            shifted_keys: slanted,
+        }
+    }
+
+    fn is_shifted(&self, c: char) -> bool {
+        self.shifted_keys
+            && self
+                .neighbours
+                .values()
+                .flatten()
+                .flatten()
+                .any(|key| key.chars().nth(1) == Some(c))
+    }
+
+    /// The direction from `from` to a neighbouring key holding `to`, and whether `to` needs Shift.
+    fn step(&self, from: char, to: char) -> Option<(usize, bool)> {
+        self.neighbours.get(&from)?.iter().enumerate().find_map(|(direction, key)| {
+            let position = (*key)?.chars().position(|c| c == to)?;
+            Some((direction, self.shifted_keys && position == 1))
+        })
+    }
+
+    fn guesses(&self, len: usize, turns: usize, shifted: usize) -> f64 {
+        let mut guesses = 0.0;
+        for i in 2..=len {
+            for j in 1..=turns.min(i - 1) {
+                guesses += n_choose_k(i - 1, j - 1) * self.starting_positions * self.average_degree.powi(j as i32);
+            }
+        }
+        if shifted > 0 {
+            let unshifted = len - shifted;
+            guesses *= if unshifted == 0 { 2.0 } else { (1..=shifted.min(unshifted)).map(|i| n_choose_k(len, i)).sum() };
+        }
+        guesses
+    }
+}
+
+fn keyboards() -> &'static [Keyboard] {
+    static KEYBOARDS: OnceLock<Vec<Keyboard>> = OnceLock::new();
+    KEYBOARDS.get_or_init(|| vec![Keyboard::new(QWERTY, true), Keyboard::new(KEYPAD, false)])
+}
+
+/// Runs of a repeated block ("aaaa", "abcabcabc"), scored as the block times the repeat count.
+fn repeat_matches(password: &[char], user_dictionary: &HashMap<String, usize>, matches: &mut Vec<Match>) {
+    let n = password.len();
+    let mut i = 0;
+    while i < n {
+        // The longest run starting here, with the shortest block that makes it
+        let mut best: Option<(usize, usize)> = None; // (block length, covered length)
+        for block in 1..=(n - i) / 2 {
+            let mut count = 1;
+            while i + (count + 1) * block <= n
+                && password[i..i + block] == password[i + count * block..i + (count + 1) * block]
+            {
+                count += 1;
+            }
+            if count > 1 && best.is_none_or(|(_, covered)| count * block > covered) {
+                best = Some((block, count * block));
+            }
+        }
+
+        let Some((block, covered)) = best else {
+            i += 1;
+            continue;
+        };
+        let base_guesses = most_guessable(&password[i..i + block], user_dictionary).guesses;
+        matches.push(Match {
+            i,
+            j: i + covered - 1,
+            token: password[i..i + covered].iter().collect(),
+            pattern: Pattern::Repeat { base_len: block },
+            guesses: base_guesses * (covered / block) as f64,
+        });
+        i += covered;
+    }
+}
+
+/// Evenly spaced characters ("abcd", "9753", "zyx").
+fn sequence_matches(password: &[char], matches: &mut Vec<Match>) {
+    let n = password.len();
+    if n < 2 {
+        return;
+    }
+    let mut push = |i: usize, j: usize, delta: i32| {
+        if (j - i > 1 || delta.abs() == 1) && delta != 0 && delta.abs() <= MAX_SEQUENCE_DELTA {
+            let token: String = password[i..=j].iter().collect();
+            let first = password[i];
+            let base = if matches!(first, 'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9') {
+                4.0
+            } else if first.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            let direction = if delta > 0 { 1.0 } else { 2.0 };
+            matches.push(Match { i, j, token, pattern: Pattern::Sequence, guesses: base * direction * (j - i + 1) as f64 });
+        }
+    };
+
+    let delta = |k: usize| password[k] as i32 - password[k - 1] as i32;
+    let mut i = 0;
+    let mut last_delta = delta(1);
+    for k in 2..n {
+        let current = delta(k);
+        if current == last_delta {
+            continue;
+        }
+        push(i, k - 1, last_delta);
+        i = k - 1;
+        last_delta = current;
+    }
+    push(i, n - 1, last_delta);
+}
+
+fn year_matches(password: &[char], matches: &mut Vec<Match>) {
+    for i in 0..password.len().saturating_sub(3) {
+        let Some(year) = parse_digits(&password[i..i + 4]) else {
+            continue;
+        };
+        if (1900..=2099).contains(&year) {
+            matches.push(Match {
+                i,
+                j: i + 3,
+                token: password[i..i + 4].iter().collect(),
+                pattern: Pattern::Year,
+                guesses: year_space(year),
+            });
+        }
+    }
+}
+
+/// Dates with or without separators: "13.05.1987", "1987-5-13", "130587", "5131987".
+fn date_matches(password: &[char], matches: &mut Vec<Match>) {
+    let n = password.len();
+    let mut found: Vec<(usize, usize, i32, bool)> = Vec::new();
+
+    for i in 0..n {
+        for j in (i + 3)..n.min(i + 8) {
+            let token = &password[i..=j];
+            let Some(_) = parse_digits(token) else {
+                continue;
+            };
+            let splits: &[(usize, usize)] = match token.len() {
+                4 => &[(1, 2), (2, 3)],
+                5 => &[(1, 3), (2, 3)],
+                6 => &[(1, 2), (2, 4), (4, 5)],
+                7 => &[(1, 3), (2, 3), (4, 5), (4, 6)],
+                _ => &[(2, 4), (4, 6)],
+            };
+            let year = splits
+                .iter()
+                .filter_map(|&(k, l)| {
+                    let parts = [parse_digits(&token[..k])?, parse_digits(&token[k..l])?, parse_digits(&token[l..])?];
+                    date_year(parts)
+                })
+                .min_by_key(|year| (year - reference_year()).abs());
+            if let Some(year) = year {
+                found.push((i, j, year, false));
+            }
+        }
+
+        for j in (i + 5)..n.min(i + 10) {
+            if let Some(year) = separated_date(&password[i..=j]) {
+                found.push((i, j, year, true));
+            }
+        }
+    }
+
+    // Drop dates inside longer dates
+    for &(i, j, year, separator) in &found {
+        let inner = found.iter().any(|&(oi, oj, ..)| (oi, oj) != (i, j) && oi <= i && oj >= j);
+        if !inner {
+            let guesses = year_space(year) * 365.0 * if separator { 4.0 } else { 1.0 };
+            matches.push(Match { i, j, token: password[i..=j].iter().collect(), pattern: Pattern::Date, guesses });
+        }
+    }
+}
+
+/// Up to four digits, a separator, one or two digits, the same separator, up to four digits.
+fn separated_date(token: &[char]) -> Option<i32> {
+    let is_separator = |c: &char| matches!(c, ' ' | '/' | '\\' | '_' | '.' | '-');
+    let first = token.iter().position(is_separator)?;
+    let separator = token[first];
+    let second = first + 1 + token[first + 1..].iter().position(|c| *c == separator)?;
+    let parts = [&token[..first], &token[first + 1..second], &token[second + 1..]];
+    if parts[0].len() > 4 || !(1..=2).contains(&parts[1].len()) || parts[2].len() > 4 {
+        return None;
+    }
+    date_year([parse_digits(parts[0])?, parse_digits(parts[1])?, parse_digits(parts[2])?])
+}
+
+/// The year of a day, month and year in any common order, if the numbers make a date.
+fn date_year(parts: [i32; 3]) -> Option<i32> {
+    if parts[1] > 31 || parts[1] <= 0 {
+        return None;
+    }
+    let mut over_12 = 0;
+    let mut over_31 = 0;
+    let mut under_1 = 0;
+    for &part in &parts {
+        if (99 < part && part < DATE_MIN_YEAR) || part > DATE_MAX_YEAR {
+            return None;
+        }
+        over_31 += usize::from(part > 31);
+        over_12 += usize::from(part > 12);
+        under_1 += usize::from(part <= 0);
+    }
+    if over_31 >= 2 || over_12 == 3 || under_1 >= 2 {
+        return None;
+    }
+
+    let splits = [(parts[2], [parts[0], parts[1]]), (parts[0], [parts[1], parts[2]])];
+    for (year, rest) in splits {
+        if (DATE_MIN_YEAR..=DATE_MAX_YEAR).contains(&year) {
+            return is_day_month(rest).then_some(year);
+        }
+    }
+    for (year, rest) in splits {
+        if is_day_month(rest) {
+            return Some(match year {
+                y if y > 99 => y,
+                y if y > 50 => 1900 + y,
+                y => 2000 + y,
+            });
+        }
+    }
+    None
+}
+
+fn is_day_month([a, b]: [i32; 2]) -> bool {
+    [(a, b), (b, a)].iter().any(|&(day, month)| (1..=31).contains(&day) && (1..=12).contains(&month))
+}
+
+fn parse_digits(chars: &[char]) -> Option<i32> {
+    if chars.is_empty() || chars.len() > 8 || !chars.iter().all(char::is_ascii_digit) {
+        return None;
+    }
+    chars.iter().collect::<String>().parse().ok()
+}
+
+fn year_space(year: i32) -> f64 {
+    ((year - reference_year()).abs() as f64).max(MIN_YEAR_SPACE)
+}
+
+fn reference_year() -> i32 {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    1970 + (seconds / 31_556_952) as i32
+}
+
+/// "Password" or "PASSWORD" are barely harder than "password"; mixed case costs more.
+fn uppercase_variations(word: &str) -> f64 {
+    let upper = word.chars().filter(|c| c.is_uppercase()).count();
+    let lower = word.chars().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return 1.0;
+    }
+    let first_only = word.chars().next().is_some_and(char::is_uppercase) && upper == 1;
+    let last_only = word.chars().last().is_some_and(char::is_uppercase) && upper == 1;
+    if first_only || last_only || lower == 0 {
+        return 2.0;
+    }
+    (1..=upper.min(lower)).map(|i| n_choose_k(upper + lower, i)).sum()
+}
+
+/// Guesses needed for which of the look-alike characters were substituted.
+fn l33t_variations(original: &[char], word: &[char]) -> f64 {
+    let mut substitutions: Vec<(char, char)> =
+        original.iter().zip(word).filter(|(o, w)| o != w).map(|(o, w)| (*o, *w)).collect();
+    substitutions.sort_unstable();
+    substitutions.dedup();
+
+    let mut variations = 1.0;
+    for (l33t, letter) in substitutions {
+        let substituted = original.iter().filter(|c| **c == l33t).count();
+        let unsubstituted = original.iter().filter(|c| **c == letter).count();
+        variations *= if unsubstituted == 0 {
+            2.0
+        } else {
+            (1..=substituted.min(unsubstituted)).map(|i| n_choose_k(substituted + unsubstituted, i)).sum()
+        };
+    }
+    variations
+}
+
+fn n_choose_k(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |acc, i| acc * (n + 1 - i) as f64 / i as f64)
+}
+
+fn factorial(n: usize) -> f64 {
+    (2..=n).fold(1.0, |acc, i| acc * i as f64)
+}
+
+fn guesses_to_score(guesses: f64) -> u8 {
+    const DELTA: f64 = 5.0;
+    match guesses {
+        g if g < 1e3 + DELTA => 0,
+        g if g < 1e6 + DELTA => 1,
+        g if g < 1e8 + DELTA => 2,
+        g if g < 1e10 + DELTA => 3,
+        _ => 4,
+    }
+}
+
+fn display_time(seconds: f64) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = MINUTE * 60.0;
+    const DAY: f64 = HOUR * 24.0;
+    const MONTH: f64 = DAY * 31.0;
+    const YEAR: f64 = MONTH * 12.0;
+    const CENTURY: f64 = YEAR * 100.0;
+
+    let (amount, unit) = match seconds {
+        s if s < 1.0 => return "less than a second".to_string(),
+        s if s < MINUTE => (s, "second"),
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < MONTH => (s / DAY, "day"),
+        s if s < YEAR => (s / MONTH, "month"),
+        s if s < CENTURY => (s / YEAR, "year"),
+        _ => return "centuries".to_string(),
+    };
+    let amount = amount.round() as u64;
+    format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" })
+}
+
+/// A warning about the most significant pattern and suggestions for a stronger password.
+fn feedback(score: u8, sequence: &[Match], empty: bool) -> (Option<String>, Vec<String>) {
+    if empty {
+        return (
+            None,
+            vec![
+                "Use a few words, avoid common phrases".to_string(),
+                "No need for symbols, digits, or uppercase letters".to_string(),
+            ],
+        );
+    }
+    if score > 2 {
+        return (None, Vec::new());
+    }
+
+    let mut suggestions = vec!["Add another word or two. Uncommon words are better.".to_string()];
+    let Some(longest) = sequence.iter().max_by_key(|m| m.j - m.i) else {
+        return (None, suggestions);
+    };
+    let sole_match = sequence.len() == 1;
+
+    let warning = match &longest.pattern {
+        Pattern::Dictionary { dictionary, rank, reversed, l33t } => {
+            let warning = match dictionary {
+                Dictionary::Passwords if sole_match && !l33t && !reversed => Some(match rank {
+                    r if *r <= 10 => "This is a top-10 common password",
+                    r if *r <= 100 => "This is a top-100 common password",
+                    _ => "This is a very common password",
+                }),
+                Dictionary::Passwords if longest.guesses.log10() <= 4.0 => Some("This is similar to a commonly used password"),
+                Dictionary::English if sole_match => Some("A word by itself is easy to guess"),
+                Dictionary::Names if sole_match => Some("Names and surnames by themselves are easy to guess"),
+                Dictionary::Names => Some("Common names and surnames are easy to guess"),
+                Dictionary::UserInputs => Some("Avoid the site name, your username and other details saved with the entry"),
+                _ => None,
+            };
+
+            let token = &longest.token;
+            let lower = token.to_lowercase();
+            if token.chars().next().is_some_and(char::is_uppercase) && token.chars().skip(1).all(|c| !c.is_uppercase()) {
+                suggestions.push("Capitalization doesn't help very much".to_string());
+            } else if token.to_uppercase() == *token && lower != *token {
+                suggestions.push("All-uppercase is almost as easy to guess as all-lowercase".to_string());
+            }
+            if *reversed && token.chars().count() >= 4 {
+                suggestions.push("Reversed words aren't much harder to guess".to_string());
+            }
+            if *l33t {
+                suggestions.push("Predictable substitutions like '@' instead of 'a' don't help very much".to_string());
+            }
+            warning
+        }
+        Pattern::Spatial { turns } => {
+            suggestions.push("Use a longer keyboard pattern with more turns".to_string());
+            Some(if *turns == 1 { "Straight rows of keys are easy to guess" } else { "Short keyboard patterns are easy to guess" })
+        }
+        Pattern::Repeat { base_len } => {
+            suggestions.push("Avoid repeated words and characters".to_string());
+            Some(if *base_len == 1 {
+                "Repeats like \"aaa\" are easy to guess"
+            } else {
+                "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\""
+            })
+        }
+        Pattern::Sequence => {
+            suggestions.push("Avoid sequences".to_string());
+            Some("Sequences like abc or 6543 are easy to guess")
+        }
+        Pattern::Year => {
+            suggestions.push("Avoid recent years".to_string());
+            suggestions.push("Avoid years that are associated with you".to_string());
+            Some("Recent years are easy to guess")
+        }
+        Pattern::Date => {
+            suggestions.push("Avoid dates and years that are associated with you".to_string());
+            Some("Dates are often easy to guess")
+        }
+        Pattern::Bruteforce => None,
+    };
+
+    (warning.map(str::to_string), suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(password: &str) -> Vec<Match> {
+        let chars: Vec<char> = password.chars().collect();
+        most_guessable(&chars, &HashMap::new()).sequence
+    }
+
+    fn sole_pattern(password: &str) -> Pattern {
+        let sequence = sequence(password);
+        assert_eq!(sequence.len(), 1, "{}: {:?}", password, sequence);
+        sequence[0].pattern.clone()
+    }
+
+    #[test]
+    fn common_passwords_score_zero() {
+        for password in ["password", "password1", "123456", "qwerty", "p@ssw0rd", "Password"] {
+            assert_eq!(StrengthService::estimate(password, &[]).score, 0, "{}", password);
+        }
+        assert!(StrengthService::is_common_password("PASSWORD"));
+        assert!(!StrengthService::is_common_password("kX9#vQ2m!Lr8"));
+    }
+
+    #[test]
+    fn finds_dictionary_words() {
+        let words: Vec<String> = sequence("correcthorsebatterystaple")
+            .into_iter()
+            .filter(|m| matches!(m.pattern, Pattern::Dictionary { dictionary: Dictionary::English, .. }))
+            .map(|m| m.token)
+            .collect();
+        assert!(words.contains(&"correct".to_string()), "{:?}", words);
+        assert!(words.contains(&"horse".to_string()), "{:?}", words);
+    }
+
+    #[test]
+    fn finds_reversed_and_l33t_words() {
+        assert!(matches!(sole_pattern("drowssap"), Pattern::Dictionary { reversed: true, .. }));
+        assert!(matches!(
+            sole_pattern("h0rs3"),
+            Pattern::Dictionary { dictionary: Dictionary::English, l33t: true, .. }
+        ));
+    }
+
+    #[test]
+    fn finds_keyboard_walks() {
+        assert!(matches!(sole_pattern("xcvbnm"), Pattern::Spatial { turns: 1 }));
+        assert!(matches!(sole_pattern("sdfghjk"), Pattern::Spatial { turns: 1 }));
+
+        // Common walks are on the password list too, which makes them cheaper still
+        let qwerty: Vec<char> = "qwerty".chars().collect();
+        assert!(omnimatch(&qwerty, &HashMap::new())
+            .iter()
+            .any(|m| m.token == "qwerty" && matches!(m.pattern, Pattern::Spatial { turns: 1 })));
+    }
+
+    #[test]
+    fn finds_repeats_sequences_years_and_dates() {
+        assert!(matches!(sole_pattern("aaaaaaaa"), Pattern::Repeat { base_len: 1 }));
+        assert!(matches!(sole_pattern("abcdefgh"), Pattern::Sequence));
+        assert!(matches!(sole_pattern("1987"), Pattern::Year));
+        assert!(matches!(sole_pattern("1987-03-14"), Pattern::Date));
+        assert!(matches!(sole_pattern("14031987"), Pattern::Date));
+    }
+
+    #[test]
+    fn user_inputs_count_as_dictionary_words() {
+        let user_inputs = ["octocat".to_string()];
+        let with_input = StrengthService::estimate("octocat2024", &user_inputs);
+        let without = StrengthService::estimate("octocat2024", &[]);
+        assert!(with_input.guesses_log10 < without.guesses_log10);
+    }
+
+    #[test]
+    fn random_passwords_score_high() {
+        let strength = StrengthService::estimate("kX9#vQ2m!Lr8@Tz4", &[]);
+        assert_eq!(strength.score, 4);
+        assert!(strength.warning.is_none());
+    }
+
+    #[test]
+    fn scores_follow_guess_thresholds() {
+        assert_eq!(guesses_to_score(10.0), 0);
+        assert_eq!(guesses_to_score(1e5), 1);
+        assert_eq!(guesses_to_score(1e7), 2);
+        assert_eq!(guesses_to_score(1e9), 3);
+        assert_eq!(guesses_to_score(1e11), 4);
+        assert_eq!(display_time(0.5), "less than a second");
+        assert_eq!(display_time(3600.0), "1 hour");
+    }
+
+    #[test]
+    fn empty_password_scores_zero() {
+        let strength = StrengthService::estimate("", &[]);
+        assert_eq!(strength.score, 0);
+        assert_eq!(strength.guesses_log10, 0.0);
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+
+use libsql::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::database::{
+    CustomField, DatabaseError, DatabaseManager, EntryTemplate, FieldType, PasswordEntry, Result, TemplateField,
+    TemplateImportSummary,
+};
+use crate::repositories::TemplateRepository;
+use crate::services::{is_valid_date, is_valid_email};
+
+const TEMPLATE_EXPORT_FORMAT: &str = "vault-entry-templates";
+const TEMPLATE_EXPORT_VERSION: u32 = 1;
+
+/// File layout used to move templates between vaults.
+#[derive(Debug, Serialize, Deserialize)]
+struct TemplateBundle {
+    format: String,
+    version: u32,
+    templates: Vec<PortableTemplate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PortableTemplate {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    fields: Vec<TemplateField>,
+}
+
+pub struct TemplateService;
+
+impl TemplateService {
+    pub async fn get_templates(db_manager: &DatabaseManager) -> Result<Vec<EntryTemplate>> {
+        let conn = db_manager.get_connection().await?;
+        TemplateRepository::get_all(&conn).await
+    }
+
+    pub async fn create_template(db_manager: &DatabaseManager, mut template: EntryTemplate) -> Result<i64> {
+        Self::validate_template(&mut template)?;
+        let conn = db_manager.get_connection().await?;
+        Self::ensure_name_available(&conn, &template.name, None).await?;
+        TemplateRepository::create(&conn, &template).await
+    }
+
+    pub async fn update_template(db_manager: &DatabaseManager, id: i64, mut template: EntryTemplate) -> Result<()> {
+        Self::validate_template(&mut template)?;
+        let conn = db_manager.get_connection().await?;
+        Self::require_template(&conn, id).await?;
+        Self::ensure_name_available(&conn, &template.name, Some(id)).await?;
+        TemplateRepository::update(&conn, id, &template).await
+    }
+
+    /// Deletes a template. Entries created from it keep their fields but are no longer validated against it.
+    pub async fn delete_template(db_manager: &DatabaseManager, id: i64) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        TemplateRepository::delete(&conn, id).await
+    }
+
+    /// Returns an unsaved entry with the template's fields laid out and empty, ready to be filled in.
+    pub async fn new_entry_from_template(db_manager: &DatabaseManager, id: i64) -> Result<PasswordEntry> {
+        let conn = db_manager.get_connection().await?;
+        let template = Self::require_template(&conn, id).await?;
+
+        Ok(PasswordEntry {
+            template_id: Some(id),
+            custom_fields: template
+                .fields
+                .iter()
+                .map(|field| CustomField {
+                    name: field.name.clone(),
+                    value: String::new(),
+                    concealed: field.field_type == FieldType::Concealed,
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Checks an entry's custom fields against its template (if any) and normalizes them in place.
+    pub async fn validate_custom_fields(conn: &Connection, entry: &mut PasswordEntry) -> Result<()> {
+        let mut seen = HashSet::new();
+        for field in entry.custom_fields.iter_mut() {
+            field.name = field.name.trim().to_string();
+            if field.name.is_empty() {
+                return Err(invalid("Custom fields need a name"));
+            }
+            if !seen.insert(field.name.to_lowercase()) {
+                return Err(invalid(&format!("Custom field '{}' appears more than once", field.name)));
+            }
+        }
+
+        let Some(template_id) = entry.template_id else {
+            return Ok(());
+        };
+        let template = Self::require_template(conn, template_id).await?;
+
+        for field in &entry.custom_fields {
+            if !template.fields.iter().any(|f| f.name.eq_ignore_ascii_case(&field.name)) {
+                return Err(invalid(&format!(
+                    "Field '{}' is not part of template '{}'",
+                    field.name, template.name
+                )));
+            }
+        }
+
+        for definition in &template.fields {
+            let value = entry
+                .custom_fields
+                .iter_mut()
+                .find(|f| f.name.eq_ignore_ascii_case(&definition.name));
+
+            match value {
+                Some(field) => {
+                    field.name = definition.name.clone();
+                    field.concealed = definition.field_type == FieldType::Concealed;
+                    if field.value.trim().is_empty() {
+                        if definition.required {
+                            return Err(invalid(&format!("Field '{}' is required", definition.name)));
+                        }
+                    } else {
+                        check_field_value(definition, &field.value)?;
+                    }
+                }
+                None if definition.required => {
+                    return Err(invalid(&format!("Field '{}' is required", definition.name)));
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes templates to a portable JSON document. Exports every template when `ids` is `None`.
+    pub async fn export_templates(db_manager: &DatabaseManager, ids: Option<Vec<i64>>) -> Result<String> {
+        let conn = db_manager.get_connection().await?;
+        let templates = TemplateRepository::get_all(&conn)
+            .await?
+            .into_iter()
+            .filter(|t| ids.as_ref().is_none_or(|ids| t.id.is_some_and(|id| ids.contains(&id))))
+            .map(|t| PortableTemplate {
+                name: t.name,
+                description: t.description,
+                fields: t.fields,
+            })
+            .collect();
+
+        let bundle = TemplateBundle {
+            format: TEMPLATE_EXPORT_FORMAT.to_string(),
+            version: TEMPLATE_EXPORT_VERSION,
+            templates,
+        };
+        serde_json::to_string_pretty(&bundle).map_err(|e| DatabaseError::Query(format!("Failed to encode templates: {}", e)))
+    }
+
+    /// Imports templates from a document produced by `export_templates`.
+    /// Templates identical to an existing one are skipped; name clashes with different fields get a suffix.
+    pub async fn import_templates(db_manager: &DatabaseManager, data: &str) -> Result<TemplateImportSummary> {
+        let bundle: TemplateBundle =
+            serde_json::from_str(data).map_err(|e| invalid(&format!("Template file is not valid: {}", e)))?;
+        if bundle.format != TEMPLATE_EXPORT_FORMAT {
+            return Err(invalid("File does not contain entry templates"));
+        }
+        if bundle.version > TEMPLATE_EXPORT_VERSION {
+            return Err(invalid("Template file was written by a newer version of the app"));
+        }
+
+        let conn = db_manager.get_connection().await?;
+        let tx = conn.transaction().await?;
+        let mut summary = TemplateImportSummary {
+            imported: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for portable in bundle.templates {
+            let mut template = EntryTemplate {
+                id: None,
+                name: portable.name,
+                description: portable.description,
+                fields: portable.fields,
+                created_at: None,
+                updated_at: None,
+            };
+            Self::validate_template(&mut template)?;
+
+            if let Some(existing) = TemplateRepository::get_by_name(&tx, &template.name).await? {
+                if existing.fields == template.fields {
+                    summary.skipped.push(template.name);
+                    continue;
+                }
+                template.name = Self::unused_name(&tx, &template.name).await?;
+            }
+
+            TemplateRepository::create(&tx, &template).await?;
+            summary.imported.push(template.name);
+        }
+
+        tx.commit().await?;
+        Ok(summary)
+    }
+
+    fn validate_template(template: &mut EntryTemplate) -> Result<()> {
+        template.name = template.name.trim().to_string();
+        if template.name.is_empty() {
+            return Err(invalid("Templates need a name"));
+        }
+        if template.fields.is_empty() {
+            return Err(invalid("Templates need at least one field"));
+        }
+
+        let mut seen = HashSet::new();
+        for field in template.fields.iter_mut() {
+            field.name = field.name.trim().to_string();
+            if field.name.is_empty() {
+                return Err(invalid("Template fields need a name"));
+            }
+            if !seen.insert(field.name.to_lowercase()) {
+                return Err(invalid(&format!("Field '{}' appears more than once", field.name)));
+            }
+        }
+
+        template.description = template
+            .description
+            .take()
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+        Ok(())
+    }
+
+    async fn ensure_name_available(conn: &Connection, name: &str, own_id: Option<i64>) -> Result<()> {
+        match TemplateRepository::get_by_name(conn, name).await? {
+            Some(existing) if existing.id != own_id => {
+                Err(invalid(&format!("A template named '{}' already exists", name)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn unused_name(conn: &Connection, name: &str) -> Result<String> {
+        let mut counter = 1;
+        loop {
+            let candidate = if counter == 1 {
+                format!("{} (imported)", name)
+            } else {
+                format!("{} (imported {})", name, counter)
+            };
+            if TemplateRepository::get_by_name(conn, &candidate).await?.is_none() {
+                return Ok(candidate);
+            }
+            counter += 1;
+        }
+    }
+
+    async fn require_template(conn: &Connection, id: i64) -> Result<EntryTemplate> {
+        TemplateRepository::get_by_id(conn, id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound(format!("Template {}", id)))
+    }
+}
+
+fn check_field_value(definition: &TemplateField, value: &str) -> Result<()> {
+    let value = value.trim();
+    let valid = match definition.field_type {
+        FieldType::Text | FieldType::Multiline | FieldType::Concealed => true,
+        FieldType::Url => value.contains("://") || (value.contains('.') && !value.contains(char::is_whitespace)),
+        FieldType::Email => is_valid_email(value),
+        FieldType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+        FieldType::Date => is_valid_date(value),
+        FieldType::Boolean => matches!(value, "true" | "false"),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid(&format!(
+            "Field '{}' expects a {} value",
+            definition.name,
+            match definition.field_type {
+                FieldType::Url => "URL",
+                FieldType::Email => "email address",
+                FieldType::Number => "numeric",
+                FieldType::Date => "YYYY-MM-DD date",
+                FieldType::Boolean => "true/false",
+                _ => "text",
+            }
+        )))
+    }
+}
+
+fn invalid(message: &str) -> DatabaseError {
+    DatabaseError::Validation(message.to_string())
+}
@@ -0,0 +1,191 @@
+use crate::database::{DiffLine, DiffLineKind};
+
+/// Line-level diff of two texts using Myers' O(ND) algorithm.
+///
+/// Common leading and trailing lines are stripped before the search so typical
+/// edits to long notes stay cheap; the returned lines always cover both texts in full.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let prefix = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut result = Vec::with_capacity(a.len().max(b.len()));
+    for (i, line) in a[..prefix].iter().enumerate() {
+        result.push(unchanged(i, i, line));
+    }
+
+    for (kind, i, j) in myers(&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]) {
+        let (i, j) = (i + prefix, j + prefix);
+        result.push(match kind {
+            DiffLineKind::Unchanged => unchanged(i, j, a[i]),
+            DiffLineKind::Removed => DiffLine {
+                kind,
+                old_line: Some(i + 1),
+                new_line: None,
+                text: a[i].to_string(),
+            },
+            DiffLineKind::Added => DiffLine {
+                kind,
+                old_line: None,
+                new_line: Some(j + 1),
+                text: b[j].to_string(),
+            },
+        });
+    }
+
+    for k in 0..suffix {
+        let (i, j) = (a.len() - suffix + k, b.len() - suffix + k);
+        result.push(unchanged(i, j, a[i]));
+    }
+
+    result
+}
+
+fn unchanged(i: usize, j: usize, text: &str) -> DiffLine {
+    DiffLine {
+        kind: DiffLineKind::Unchanged,
+        old_line: Some(i + 1),
+        new_line: Some(j + 1),
+        text: text.to_string(),
+    }
+}
+
+/// Returns the edit script as (kind, index into `a`, index into `b`) in forward order.
+/// For added lines the `a` index is meaningless, and likewise `b` for removed lines.
+fn myers(a: &[&str], b: &[&str]) -> Vec<(DiffLineKind, usize, usize)> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = n + m;
+    if max == 0 {
+        return Vec::new();
+    }
+
+    let offset = max + 1;
+    let mut v = vec![0isize; (2 * max + 3) as usize];
+    // trace[d] holds the furthest-reaching x for diagonals -d..=d before round d
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut script = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (0..trace.len() as isize).rev() {
+        let row = &trace[d as usize];
+        let at = |k: isize| row[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = if d == 0 { 0 } else { at(prev_k) };
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            script.push((DiffLineKind::Unchanged, x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                script.push((DiffLineKind::Added, x as usize, y as usize));
+            } else {
+                x -= 1;
+                script.push((DiffLineKind::Removed, x as usize, y as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    script.reverse();
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The diff as `' '`, `'+'` or `'-'` followed by the line, with line numbers checked along the way.
+    fn render(old: &str, new: &str) -> Vec<String> {
+        let (a, b): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+        diff_lines(old, new)
+            .into_iter()
+            .map(|line| {
+                if let Some(n) = line.old_line {
+                    assert_eq!(a[n - 1], line.text);
+                }
+                if let Some(n) = line.new_line {
+                    assert_eq!(b[n - 1], line.text);
+                }
+                let marker = match line.kind {
+                    DiffLineKind::Unchanged => ' ',
+                    DiffLineKind::Added => '+',
+                    DiffLineKind::Removed => '-',
+                };
+                format!("{}{}", marker, line.text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_texts_are_all_unchanged() {
+        assert_eq!(render("a\nb\nc", "a\nb\nc"), [" a", " b", " c"]);
+        assert!(render("", "").is_empty());
+    }
+
+    #[test]
+    fn empty_side_is_all_added_or_removed() {
+        assert_eq!(render("", "a\nb"), ["+a", "+b"]);
+        assert_eq!(render("a\nb", ""), ["-a", "-b"]);
+    }
+
+    #[test]
+    fn pure_insert() {
+        assert_eq!(render("a\nd", "a\nb\nc\nd"), [" a", "+b", "+c", " d"]);
+        assert_eq!(render("b", "a\nb"), ["+a", " b"]);
+        assert_eq!(render("a", "a\nb"), [" a", "+b"]);
+    }
+
+    #[test]
+    fn pure_delete() {
+        assert_eq!(render("a\nb\nc\nd", "a\nd"), [" a", "-b", "-c", " d"]);
+        assert_eq!(render("a\nb", "b"), ["-a", " b"]);
+    }
+
+    #[test]
+    fn middle_replacement() {
+        assert_eq!(render("a\nb\nc\nd", "a\nx\ny\nd"), [" a", "-b", "-c", "+x", "+y", " d"]);
+    }
+
+    #[test]
+    fn keeps_common_lines_between_edits() {
+        assert_eq!(
+            render("a\nb\nc\nd\ne", "x\nb\nc\ny\ne"),
+            ["-a", "+x", " b", " c", "-d", "+y", " e"]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use regex::Regex;
+use url::Url;
+
+use crate::database::{EntryUri, UriMatchMode, UriMatchQuality};
+use crate::services::public_suffix::registrable_domain;
+
+/// Normalizes a URI for storage: adds `https://` to bare hosts, lowercases the scheme
+/// and host (IDN hosts become punycode), drops default ports and a lone trailing `/`.
+/// Regex URIs are only checked for validity and stored as written.
+pub fn normalize_uri(raw: &str, match_mode: UriMatchMode) -> Result<String, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err("URI is empty".to_string());
+    }
+
+    if match_mode == UriMatchMode::Regex {
+        Regex::new(raw).map_err(|e| format!("Invalid regular expression '{}': {}", raw, e))?;
+        return Ok(raw.to_string());
+    }
+
+    let url = parse_url(raw).ok_or_else(|| format!("'{}' is not a valid URI", raw))?;
+    Ok(display_url(&url))
+}
+
+/// Parses a URL, treating scheme-less input such as `example.com/login` as https.
+pub fn parse_url(raw: &str) -> Option<Url> {
+    let raw = raw.trim();
+    let parsed = if raw.contains("://") {
+        Url::parse(raw).ok()?
+    } else {
+        Url::parse(&format!("https://{}", raw)).ok()?
+    };
+
+    // Web URLs need a host; app schemes such as androidapp:// are accepted as they are
+    if matches!(parsed.scheme(), "http" | "https") && parsed.host_str().is_none_or(str::is_empty) {
+        return None;
+    }
+    Some(parsed)
+}
+
+fn display_url(url: &Url) -> String {
+    let text = url.as_str();
+    if url.path() == "/" && url.query().is_none() && url.fragment().is_none() {
+        text.trim_end_matches('/').to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Pulls URI candidates out of a free-text `website` value, which may hold several
+/// addresses separated by whitespace, commas or semicolons alongside plain words.
+pub fn website_uris(website: &str) -> Vec<EntryUri> {
+    let mut uris: Vec<EntryUri> = Vec::new();
+    for token in website.split(|c: char| c.is_whitespace() || c == ',' || c == ';') {
+        let token = token.trim_matches(|c: char| matches!(c, '<' | '>' | '(' | ')' | '"' | '\''));
+        if !looks_like_uri(token) {
+            continue;
+        }
+        if let Ok(uri) = normalize_uri(token, UriMatchMode::BaseDomain) {
+            if !uris.iter().any(|existing| existing.uri == uri) {
+                uris.push(EntryUri {
+                    uri,
+                    match_mode: UriMatchMode::BaseDomain,
+                });
+            }
+        }
+    }
+    uris
+}
+
+fn looks_like_uri(token: &str) -> bool {
+    if token.contains("://") {
+        return true;
+    }
+    // Bare host names: at least one dot and an alphabetic top-level label, e.g. "mail.example.com/inbox"
+    let host = token.split(['/', ':', '?', '#']).next().unwrap_or_default();
+    let labels: Vec<&str> = host.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        && labels.last().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic))
+}
+
+/// Checks whether a stored entry URI applies to the given page URL.
+pub fn uri_matches(entry_uri: &EntryUri, target: &Url) -> bool {
+    match_quality(entry_uri, target).is_some()
+}
+
+/// How well a stored entry URI matches the given page URL, or `None` if it doesn't apply.
+pub fn match_quality(entry_uri: &EntryUri, target: &Url) -> Option<UriMatchQuality> {
+    match entry_uri.match_mode {
+        UriMatchMode::Never => return None,
+        UriMatchMode::Regex => {
+            let re = Regex::new(&entry_uri.uri).ok()?;
+            return re.is_match(target.as_str()).then_some(UriMatchQuality::Regex);
+        }
+        _ => {}
+    }
+
+    let stored = parse_url(&entry_uri.uri)?;
+    if is_app_uri(&stored) || is_app_uri(target) {
+        // Package names look like reversed domains but aren't: only the same app matches
+        let same_app = stored.scheme() == target.scheme()
+            && stored.host_str().map(str::to_ascii_lowercase) == target.host_str().map(str::to_ascii_lowercase);
+        return same_app.then_some(UriMatchQuality::App);
+    }
+
+    // Compared with the trailing `/` kept, so `https://example.com` never prefixes `https://example.com.evil.net`
+    let prefix_match = target.as_str().starts_with(stored.as_str());
+    if entry_uri.match_mode == UriMatchMode::StartsWith {
+        return prefix_match.then_some(UriMatchQuality::ExactUrl);
+    }
+    if !schemes_compatible(&stored, target) {
+        return None;
+    }
+
+    let has_path = stored.path() != "/" || stored.query().is_some();
+    let same_host = stored.host_str() == target.host_str() && (stored.port().is_none() || stored.port() == target.port());
+    let (Some(stored_host), Some(target_host)) = (stored.host_str(), target.host_str()) else {
+        return (stored.as_str() == target.as_str()).then_some(UriMatchQuality::ExactUrl);
+    };
+
+    let quality = if same_host && has_path && prefix_match {
+        UriMatchQuality::ExactUrl
+    } else if same_host {
+        UriMatchQuality::Host
+    } else if entry_uri.match_mode == UriMatchMode::Host || base_domain(stored_host) != base_domain(target_host) {
+        return None;
+    } else if is_subdomain_of(stored_host, target_host) || is_subdomain_of(target_host, stored_host) {
+        UriMatchQuality::Subdomain
+    } else {
+        UriMatchQuality::Domain
+    };
+    Some(quality)
+}
+
+/// Android app URIs (`androidapp://com.example.app`) name a package rather than a site.
+fn is_app_uri(url: &Url) -> bool {
+    url.scheme() == "androidapp"
+}
+
+fn is_subdomain_of(host: &str, parent: &str) -> bool {
+    host.strip_suffix(parent).is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// http and https are interchangeable for matching; any other scheme must be equal.
+fn schemes_compatible(a: &Url, b: &Url) -> bool {
+    let web = |s: &str| matches!(s, "http" | "https");
+    a.scheme() == b.scheme() || (web(a.scheme()) && web(b.scheme()))
+}
+
+/// The registrable domain (eTLD+1) a host belongs to. IP addresses, and hosts that are
+/// themselves a public suffix, are kept whole so they only ever match themselves.
+pub fn base_domain(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    registrable_domain(&host).unwrap_or(host)
+}
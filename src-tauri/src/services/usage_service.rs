@@ -0,0 +1,35 @@
+use crate::database::{DatabaseError, DatabaseManager, Result, SearchHitKind, UsedItem};
+use crate::repositories::{NotesRepository, PasswordRepository, SearchRepository};
+
+const DEFAULT_QUICK_ACCESS_LIMIT: u32 = 10;
+const MAX_QUICK_ACCESS_LIMIT: u32 = 100;
+
+pub struct UsageService;
+
+impl UsageService {
+    /// Counts a use of an entry or note, such as copying its password or opening it.
+    /// `reveal_password` already counts itself.
+    pub async fn record_usage(db_manager: &DatabaseManager, kind: SearchHitKind, id: i64) -> Result<()> {
+        let conn = db_manager.get_connection().await?;
+        let found = match kind {
+            SearchHitKind::Entry => PasswordRepository::record_usage(&conn, id).await?,
+            SearchHitKind::Note => NotesRepository::record_usage(&conn, id).await?,
+        };
+        if !found {
+            let what = match kind {
+                SearchHitKind::Entry => "Password entry",
+                SearchHitKind::Note => "Note",
+            };
+            return Err(DatabaseError::NotFound(format!("{} {}", what, id)));
+        }
+
+        Ok(())
+    }
+
+    /// The most used entries and notes, for a quick-access list.
+    pub async fn frequently_used(db_manager: &DatabaseManager, limit: Option<u32>) -> Result<Vec<UsedItem>> {
+        let limit = limit.unwrap_or(DEFAULT_QUICK_ACCESS_LIMIT).clamp(1, MAX_QUICK_ACCESS_LIMIT);
+        let conn = db_manager.get_connection().await?;
+        SearchRepository::frequently_used(&conn, limit).await
+    }
+}
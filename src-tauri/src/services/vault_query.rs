@@ -0,0 +1,497 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+use libsql::Value;
+
+use crate::database::ItemKind;
+use crate::repositories::PASSWORD_AGE_SQL;
+
+/// Filters understood by `query_vault`, listed in parse errors.
+const FIELDS: &str = "tag, folder, user, site, type, favorite, weak, compromised, age, used";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParseError {
+    /// 1-based character column where the problem starts
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.column)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Greater => ">",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Word(String),   // prefix match over the full-text index
+    Phrase(String), // exact phrase over the full-text index
+    Tag(String),
+    Folder(String), // the folder or any folder inside it
+    User(String),
+    Site(String),
+    Kind(Option<ItemKind>), // None is `type:note`
+    Favorite,
+    Weak,
+    Compromised,
+    Trashed,
+    Age(Comparison, f64), // days
+    Used(Comparison, u32), // times used
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clause {
+    negated: bool,
+    term: Term,
+}
+
+/// A parsed structured query such as `tag:prod folder:clients user:admin weak: age>365d used>5 -trashed "exact phrase"`.
+///
+/// Terms are combined with AND; a leading `-` negates one. Bare words match as prefixes and
+/// quoted text as a phrase over website, username, notes and note titles and content.
+/// Filters that only make sense for entries (`user:`, `site:`, `weak:`, `compromised:`) never match notes.
+/// Deleting is permanent, so `trashed` matches nothing and `-trashed` everything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultQuery {
+    clauses: Vec<Clause>,
+}
+
+/// A WHERE clause with its positional parameters.
+pub struct SqlFilter {
+    pub sql: String,
+    pub params: Vec<Value>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Table {
+    Passwords,
+    Notes,
+}
+
+impl Table {
+    fn name(&self) -> &'static str {
+        match self {
+            Table::Passwords => "passwords",
+            Table::Notes => "notes",
+        }
+    }
+}
+
+impl VaultQuery {
+    pub fn parse(input: &str) -> Result<Self, QueryParseError> {
+        Parser {
+            chars: input.char_indices().peekable(),
+            input,
+        }
+        .parse()
+    }
+
+    /// Whether the query uses `weak:`, whose matches have to be worked out with the strength
+    /// estimator before the filter is built.
+    pub fn filters_weak(&self) -> bool {
+        self.clauses.iter().any(|clause| clause.term == Term::Weak)
+    }
+
+    /// Filter over the `passwords` table; `weak_ids` are the entries `weak:` matches.
+    pub fn entry_filter(&self, weak_ids: &[i64]) -> SqlFilter {
+        self.compile(Table::Passwords, weak_ids)
+    }
+
+    /// Filter over the `notes` table.
+    pub fn note_filter(&self) -> SqlFilter {
+        self.compile(Table::Notes, &[])
+    }
+
+    fn compile(&self, table: Table, weak_ids: &[i64]) -> SqlFilter {
+        let mut params = Vec::new();
+        let parts: Vec<String> = self
+            .clauses
+            .iter()
+            .map(|clause| {
+                let sql = compile_term(&clause.term, table, weak_ids, &mut params);
+                // COALESCE so a NULL column counts as "doesn't match" on both sides of a negation
+                if clause.negated {
+                    format!("NOT COALESCE(({}), 0)", sql)
+                } else {
+                    format!("COALESCE(({}), 0)", sql)
+                }
+            })
+            .collect();
+
+        SqlFilter {
+            sql: if parts.is_empty() { "1".to_string() } else { parts.join(" AND ") },
+            params,
+        }
+    }
+}
+
+fn compile_term(term: &Term, table: Table, weak_ids: &[i64], params: &mut Vec<Value>) -> String {
+    let name = table.name();
+    match (term, table) {
+        (Term::Word(word), _) => full_text_match(table, format!("\"{}\"*", word), params),
+        (Term::Phrase(phrase), _) => full_text_match(table, format!("\"{}\"", phrase), params),
+        (Term::Tag(tag), _) => {
+            params.push(Value::Text(tag.clone()));
+            format!("EXISTS (SELECT 1 FROM json_each({}.tags) WHERE lower(json_each.value) = lower(?))", name)
+        }
+        (Term::Folder(folder), _) => {
+            let folder = folder.trim_matches('/');
+            params.push(Value::Text(folder.to_string()));
+            params.push(Value::Text(format!("{}/%", escape_like(folder))));
+            format!("{0}.folder = ? COLLATE NOCASE OR {0}.folder LIKE ? ESCAPE '\\'", name)
+        }
+        (Term::User(user), Table::Passwords) => {
+            params.push(Value::Text(format!("%{}%", escape_like(user))));
+            "passwords.username LIKE ? ESCAPE '\\'".to_string()
+        }
+        (Term::Site(site), Table::Passwords) => {
+            let pattern = format!("%{}%", escape_like(site));
+            params.push(Value::Text(pattern.clone()));
+            params.push(Value::Text(pattern));
+            "passwords.website LIKE ? ESCAPE '\\'
+             OR EXISTS (SELECT 1 FROM entry_uris WHERE entry_id = passwords.id AND uri LIKE ? ESCAPE '\\')"
+                .to_string()
+        }
+        (Term::Kind(Some(kind)), Table::Passwords) => {
+            params.push(Value::Text(kind.as_str().to_string()));
+            "passwords.item_type = ?".to_string()
+        }
+        (Term::Kind(None), Table::Notes) => "1".to_string(),
+        (Term::Favorite, _) => format!("{}.favorite = 1", name),
+        (Term::Weak, Table::Passwords) => {
+            params.push(Value::Text(serde_json::Value::from(weak_ids).to_string()));
+            "passwords.id IN (SELECT value FROM json_each(?))".to_string()
+        }
+        (Term::Compromised, Table::Passwords) => "passwords.compromised_at IS NOT NULL".to_string(),
+        (Term::Age(comparison, days), _) => {
+            params.push(Value::Real(*days));
+            let age = match table {
+                Table::Passwords => PASSWORD_AGE_SQL,
+                Table::Notes => "julianday('now') - julianday(notes.updated_at)",
+            };
+            if *comparison == Comparison::Equal {
+                format!("CAST(({}) AS INTEGER) = ?", age)
+            } else {
+                format!("{} {} ?", age, comparison.as_sql())
+            }
+        }
+        (Term::Used(comparison, count), _) => {
+            params.push(Value::Integer(i64::from(*count)));
+            format!("{}.use_count {} ?", name, comparison.as_sql())
+        }
+        // Entry-only filters never match notes, and nothing is ever in the trash
+        _ => "0".to_string(),
+    }
+}
+
+fn full_text_match(table: Table, match_query: String, params: &mut Vec<Value>) -> String {
+    params.push(Value::Text(match_query));
+    format!("{0}.id IN (SELECT rowid FROM {0}_fts WHERE {0}_fts MATCH ?)", table.name())
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    input: &'a str,
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> Result<VaultQuery, QueryParseError> {
+        let mut clauses = Vec::new();
+        loop {
+            while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            let Some(&(start, c)) = self.chars.peek() else {
+                break;
+            };
+
+            let negated = c == '-';
+            if negated {
+                self.chars.next();
+                if self.chars.peek().is_none_or(|(_, c)| c.is_whitespace()) {
+                    return Err(self.error(start, "'-' must be followed by the term to exclude"));
+                }
+            }
+            let term = self.parse_term()?;
+            clauses.push(Clause { negated, term });
+        }
+        Ok(VaultQuery { clauses })
+    }
+
+    fn parse_term(&mut self) -> Result<Term, QueryParseError> {
+        let &(start, c) = self.chars.peek().expect("caller checked for input");
+        if c == '"' {
+            let phrase = self.parse_quoted()?;
+            if phrase.is_empty() {
+                return Err(self.error(start, "Empty phrase"));
+            }
+            return Ok(Term::Phrase(phrase));
+        }
+
+        let word = self.take_while(|c| !c.is_whitespace() && !matches!(c, ':' | '<' | '>' | '=' | '"'));
+        match self.chars.peek().map(|&(_, c)| c) {
+            Some(':') => {
+                self.chars.next();
+                let value_start = self.position();
+                let value = self.parse_value()?;
+                self.field_term(&word, value, start, value_start)
+            }
+            Some('<' | '>' | '=') => {
+                let op_start = self.position();
+                let comparison = self.parse_comparison();
+                let value_start = self.position();
+                if word.eq_ignore_ascii_case("used") {
+                    let value = self.parse_value()?;
+                    let count = value
+                        .parse()
+                        .map_err(|_| self.error(value_start, "used needs a number of times, like used>5 or used=0"))?;
+                    return Ok(Term::Used(comparison, count));
+                }
+                if !word.eq_ignore_ascii_case("age") {
+                    return Err(self.error(op_start, &format!("Only age and used can be compared with <, > or =, not '{}'", word)));
+                }
+                let value = self.parse_value()?;
+                let days = parse_days(&value).ok_or_else(|| {
+                    self.error(value_start, "age needs a number of days, weeks, months or years, like 365d, 12w, 6m or 2y")
+                })?;
+                Ok(Term::Age(comparison, days))
+            }
+            Some('"') => {
+                let quote = self.position();
+                Err(self.error(quote, "Put a space before a quoted phrase"))
+            }
+            _ if word.eq_ignore_ascii_case("trashed") => Ok(Term::Trashed),
+            _ => Ok(Term::Word(word)),
+        }
+    }
+
+    fn field_term(&self, field: &str, value: String, start: usize, value_start: usize) -> Result<Term, QueryParseError> {
+        let field = field.to_ascii_lowercase();
+        let needs_value = |term: fn(String) -> Term| {
+            if value.is_empty() {
+                Err(self.error(value_start, &format!("{}: needs a value", field)))
+            } else {
+                Ok(term(value.clone()))
+            }
+        };
+
+        match field.as_str() {
+            "tag" => needs_value(Term::Tag),
+            "folder" => needs_value(Term::Folder),
+            "user" => needs_value(Term::User),
+            "site" => needs_value(Term::Site),
+            "type" => {
+                let value = value.to_ascii_lowercase();
+                if value == "note" {
+                    return Ok(Term::Kind(None));
+                }
+                ItemKind::parse(&value).map(|kind| Term::Kind(Some(kind))).ok_or_else(|| {
+                    let kinds: Vec<&str> = ItemKind::ALL.iter().map(ItemKind::as_str).collect();
+                    self.error(value_start, &format!("Unknown type '{}'; expected note, {}", value, kinds.join(", ")))
+                })
+            }
+            "favorite" if value.is_empty() => Ok(Term::Favorite),
+            "favorite" => Err(self.error(value_start, "favorite: takes no value")),
+            "weak" if value.is_empty() => Ok(Term::Weak),
+            "weak" => Err(self.error(value_start, "weak: takes no value")),
+            "compromised" if value.is_empty() => Ok(Term::Compromised),
+            "compromised" => Err(self.error(value_start, "compromised: takes no value")),
+            "age" => Err(self.error(start, "age is compared with <, > or =, like age>365d")),
+            "used" => Err(self.error(start, "used is compared with <, > or =, like used>5")),
+            _ => Err(self.error(start, &format!("Unknown filter '{}:'; expected one of {}", field, FIELDS))),
+        }
+    }
+
+    /// A filter value: quoted (may contain spaces) or everything up to the next space.
+    fn parse_value(&mut self) -> Result<String, QueryParseError> {
+        match self.chars.peek() {
+            Some((_, '"')) => self.parse_quoted(),
+            _ => Ok(self.take_while(|c| !c.is_whitespace())),
+        }
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, QueryParseError> {
+        let (start, _) = self.chars.next().expect("caller peeked a quote");
+        let text = self.take_while(|c| c != '"');
+        if self.chars.next().is_none() {
+            return Err(self.error(start, "Quote is never closed"));
+        }
+        Ok(text.trim().to_string())
+    }
+
+    fn parse_comparison(&mut self) -> Comparison {
+        let first = self.chars.next().map(|(_, c)| c);
+        let or_equal = self.chars.next_if(|(_, c)| *c == '=').is_some();
+        match (first, or_equal) {
+            (Some('<'), false) => Comparison::Less,
+            (Some('<'), true) => Comparison::LessOrEqual,
+            (Some('>'), false) => Comparison::Greater,
+            (Some('>'), true) => Comparison::GreaterOrEqual,
+            _ => Comparison::Equal,
+        }
+    }
+
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> String {
+        let mut text = String::new();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| keep(*c)) {
+            text.push(c);
+        }
+        text
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |(i, _)| *i)
+    }
+
+    fn error(&self, byte_offset: usize, message: &str) -> QueryParseError {
+        QueryParseError {
+            column: self.input[..byte_offset].chars().count() + 1,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// `365`, `365d`, `12w`, `6m` or `2y` as a number of days.
+fn parse_days(value: &str) -> Option<f64> {
+    let value = value.to_ascii_lowercase();
+    let (number, unit_days) = match value.char_indices().last()? {
+        (i, 'd') => (&value[..i], 1.0),
+        (i, 'w') => (&value[..i], 7.0),
+        (i, 'm') => (&value[..i], 30.0),
+        (i, 'y') => (&value[..i], 365.0),
+        _ => (value.as_str(), 1.0),
+    };
+    let number: f64 = number.parse().ok()?;
+    (number.is_finite() && number >= 0.0).then_some(number * unit_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clause(negated: bool, term: Term) -> Clause {
+        Clause { negated, term }
+    }
+
+    #[test]
+    fn parses_terms() {
+        let cases = [
+            ("github", vec![clause(false, Term::Word("github".to_string()))]),
+            ("\"work  laptop\"", vec![clause(false, Term::Phrase("work  laptop".to_string()))]),
+            ("-trashed", vec![clause(true, Term::Trashed)]),
+            ("-tag:old", vec![clause(true, Term::Tag("old".to_string()))]),
+            ("tag:\"two words\"", vec![clause(false, Term::Tag("two words".to_string()))]),
+            ("folder:clients/acme", vec![clause(false, Term::Folder("clients/acme".to_string()))]),
+            ("age>365d", vec![clause(false, Term::Age(Comparison::Greater, 365.0))]),
+            ("AGE<=2w", vec![clause(false, Term::Age(Comparison::LessOrEqual, 14.0))]),
+            ("used>5", vec![clause(false, Term::Used(Comparison::Greater, 5))]),
+            ("used=0", vec![clause(false, Term::Used(Comparison::Equal, 0))]),
+            ("type:note", vec![clause(false, Term::Kind(None))]),
+            (
+                "user:admin weak: -favorite:",
+                vec![
+                    clause(false, Term::User("admin".to_string())),
+                    clause(false, Term::Weak),
+                    clause(true, Term::Favorite),
+                ],
+            ),
+            ("", vec![]),
+        ];
+        for (input, clauses) in cases {
+            assert_eq!(VaultQuery::parse(input), Ok(VaultQuery { clauses }), "{}", input);
+        }
+    }
+
+    #[test]
+    fn reports_errors_at_the_right_column() {
+        let cases = [
+            ("\"unclosed", 1, "Quote is never closed"),
+            ("tag:\"unclosed", 5, "Quote is never closed"),
+            ("a \"\"", 3, "Empty phrase"),
+            ("\"   \"", 1, "Empty phrase"),
+            ("tag:", 5, "tag: needs a value"),
+            ("folder:", 8, "folder: needs a value"),
+            ("- tag:x", 1, "'-' must be followed"),
+            ("age>soon", 5, "age needs a number"),
+            ("used>lots", 6, "used needs a number"),
+            ("size>5", 5, "Only age and used"),
+            ("age:5", 1, "age is compared"),
+            ("colour:red", 1, "Unknown filter 'colour:'"),
+            ("weak:yes", 6, "weak: takes no value"),
+            ("word\"phrase\"", 5, "Put a space before"),
+        ];
+        for (input, column, message) in cases {
+            let error = VaultQuery::parse(input).unwrap_err();
+            assert_eq!(error.column, column, "{}: {:?}", input, error);
+            assert!(error.message.starts_with(message), "{}: {:?}", input, error);
+        }
+    }
+
+    #[test]
+    fn compiles_negation_and_parameters() {
+        let query = VaultQuery::parse("-tag:old folder:/clients/ used>5").unwrap();
+        let filter = query.note_filter();
+        assert_eq!(
+            filter.sql,
+            "NOT COALESCE((EXISTS (SELECT 1 FROM json_each(notes.tags) WHERE lower(json_each.value) = lower(?))), 0) \
+             AND COALESCE((notes.folder = ? COLLATE NOCASE OR notes.folder LIKE ? ESCAPE '\\'), 0) \
+             AND COALESCE((notes.use_count > ?), 0)"
+        );
+        assert_eq!(
+            filter.params,
+            [
+                Value::Text("old".to_string()),
+                Value::Text("clients".to_string()),
+                Value::Text("clients/%".to_string()),
+                Value::Integer(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_only_filters_never_match_notes() {
+        let query = VaultQuery::parse("user:admin").unwrap();
+        assert_eq!(query.note_filter().sql, "COALESCE((0), 0)");
+        assert_eq!(VaultQuery::parse("").unwrap().entry_filter(&[]).sql, "1");
+    }
+
+    #[test]
+    fn weak_filter_uses_the_given_ids() {
+        let query = VaultQuery::parse("weak:").unwrap();
+        assert!(query.filters_weak());
+        let filter = query.entry_filter(&[3, 7]);
+        assert_eq!(filter.sql, "COALESCE((passwords.id IN (SELECT value FROM json_each(?))), 0)");
+        assert_eq!(filter.params, [Value::Text("[3,7]".to_string())]);
+    }
+
+    #[test]
+    fn escapes_like_wildcards() {
+        let filter = VaultQuery::parse("folder:100%_done").unwrap().note_filter();
+        assert_eq!(filter.params[1], Value::Text("100\\%\\_done/%".to_string()));
+    }
+}
@@ -5,7 +5,8 @@ import { Input } from '@/components/ui/input';
 import { Label } from '@/components/ui/label';
 import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, DialogTitle } from '@/components/ui/dialog';
 import { FolderPlus, FolderOpen, Clock, Shield, ArrowLeft } from 'lucide-react';
-import { authAPI } from '@/lib/tauri';
+import { authAPI, generatorAPI } from '@/lib/tauri';
+import type { PasswordStrength } from '@/types';
 import { open } from '@tauri-apps/plugin-dialog';
 
 interface VaultInfo {
@@ -278,6 +279,30 @@ const SetupScreenWithVaultCreation: React.FC<SetupScreenProps> = ({ onVaultCreat
   const [confirmPassword, setConfirmPassword] = useState('');
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState('');
+  const [strength, setStrength] = useState<PasswordStrength | null>(null);
+
+  useEffect(() => {
+    if (!password) {
+      setStrength(null);
+      return;
+    }
+    let cancelled = false;
+    generatorAPI
+      .estimatePasswordStrength(password)
+      .then((result) => {
+        if (!cancelled) {
+          setStrength(result);
+        }
+      })
+      .catch(() => {
+        if (!cancelled) {
+          setStrength(null);
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [password]);
 
   const validatePassword = (password: string) => {
     if (password.length < 8) {
@@ -357,6 +382,27 @@ const SetupScreenWithVaultCreation: React.FC<SetupScreenProps> = ({ onVaultCreat
                   onChange={(e) => setPassword(e.target.value)}
                   placeholder="Enter a strong master password"
                 />
+                {strength && (
+                  <div className="space-y-1">
+                    <div className="flex items-center gap-2">
+                      <div className="h-2 flex-1 rounded-full bg-gray-200">
+                        <div
+                          className={`h-2 rounded-full transition-all ${strength.score < 2 ? 'bg-red-500' : strength.score < 4 ? 'bg-yellow-500' : 'bg-green-500'}`}
+                          style={{ width: `${(strength.score / 4) * 100}%` }}
+                        />
+                      </div>
+                      <span className="text-gray-500 text-xs">
+                        {strength.crack_time_display} to crack
+                      </span>
+                    </div>
+                    {strength.warning && (
+                      <p className="text-yellow-700 text-sm dark:text-yellow-400">{strength.warning}</p>
+                    )}
+                    {strength.feedback.map((suggestion, index) => (
+                      <p key={index} className="text-gray-500 text-sm">{suggestion}</p>
+                    ))}
+                  </div>
+                )}
               </div>
               
               <div className="space-y-2">
@@ -5,9 +5,12 @@ import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Input } from '@/components/ui/input';
 import { Label } from '@/components/ui/label';
-import { passwordAPI } from '@/lib/tauri';
+import { generatorAPI, healthAPI, passwordAPI } from '@/lib/tauri';
 import type { PasswordEntry, PasswordStrength } from '@/types';
 
+// Wait for a pause in typing before checking the password
+const CHECK_DELAY_MS = 300;
+
 type PasswordFormProps = {
   onClose: () => void;
   onSave: () => void;
@@ -28,10 +31,9 @@ export const PasswordForm: React.FC<PasswordFormProps> = ({
   const [showPassword, setShowPassword] = useState(false);
   const [loading, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
-  const [passwordStrength, setPasswordStrength] = useState<PasswordStrength>({
-    score: 0,
-    feedback: [],
-  });
+  const [passwordStrength, setPasswordStrength] =
+    useState<PasswordStrength | null>(null);
+  const [breached, setBreached] = useState(false);
 
   useEffect(() => {
     if (editingPassword) {
@@ -49,75 +51,73 @@ export const PasswordForm: React.FC<PasswordFormProps> = ({
   }, [editingPassword]);
 
   useEffect(() => {
-    if (formData.password) {
-      calculatePasswordStrength(formData.password);
-    } else {
-      setPasswordStrength({ score: 0, feedback: [] });
-    }
-  }, [formData.password,]);
-
-  const calculatePasswordStrength = (password: string) => {
-    let score = 0;
-    const feedback: string[] = [];
-
-    if (password.length >= 8) {
-      score += 1;
-    } else {
-      feedback.push('Use at least 8 characters');
-    }
-
-    if (/[a-z]/.test(password) && /[A-Z]/.test(password)) {
-      score += 1;
-    } else {
-      feedback.push('Include both uppercase and lowercase letters');
-    }
-
-    if (/\d/.test(password)) {
-      score += 1;
-    } else {
-      feedback.push('Include at least one number');
-    }
-
-    if (/[^a-zA-Z0-9]/.test(password)) {
-      score += 1;
-    } else {
-      feedback.push('Include special characters');
+    if (!formData.password) {
+      setPasswordStrength(null);
+      return;
     }
+    let cancelled = false;
+    const timeout = window.setTimeout(() => {
+      generatorAPI
+        .estimatePasswordStrength(formData.password, [
+          formData.website,
+          formData.username,
+        ])
+        .then((strength) => {
+          if (!cancelled) {
+            setPasswordStrength(strength);
+          }
+        })
+        .catch(() => {
+          if (!cancelled) {
+            setPasswordStrength(null);
+          }
+        });
+    }, CHECK_DELAY_MS);
+    return () => {
+      cancelled = true;
+      window.clearTimeout(timeout);
+    };
+  }, [formData.password, formData.website, formData.username]);
 
-    if (password.length >= 12) {
-      score += 1;
+  useEffect(() => {
+    if (!formData.password) {
+      setBreached(false);
+      return;
     }
-
-    setPasswordStrength({ score: Math.min(score, 4), feedback });
-  };
-
-  const generatePassword = () => {
-    const uppercase = 'ABCDEFGHIJKLMNOPQRSTUVWXYZ';
-    const lowercase = 'abcdefghijklmnopqrstuvwxyz';
-    const numbers = '0123456789';
-    const symbols = '!@#$%^&*()_+-=[]{}|;:,.<>?';
-
-    const allChars = uppercase + lowercase + numbers + symbols;
-    let password = '';
-
-    // Ensure at least one character from each category
-    password += uppercase[Math.floor(Math.random() * uppercase.length)];
-    password += lowercase[Math.floor(Math.random() * lowercase.length)];
-    password += numbers[Math.floor(Math.random() * numbers.length)];
-    password += symbols[Math.floor(Math.random() * symbols.length)];
-
-    // Fill the rest randomly
-    for (let i = 4; i < 16; i++) {
-      password += allChars[Math.floor(Math.random() * allChars.length)];
+    let cancelled = false;
+    // Each check opens the index file, so only check once typing pauses
+    const timeout = window.setTimeout(() => {
+      // Rejects when no breach index has been imported; treat that as unknown
+      healthAPI
+        .isPasswordBreached(formData.password)
+        .then((found) => {
+          if (!cancelled) {
+            setBreached(found);
+          }
+        })
+        .catch(() => {
+          if (!cancelled) {
+            setBreached(false);
+          }
+        });
+    }, CHECK_DELAY_MS);
+    return () => {
+      cancelled = true;
+      window.clearTimeout(timeout);
+    };
+  }, [formData.password]);
+
+  const generatePassword = async () => {
+    try {
+      const generated = await generatorAPI.generatePassword({
+        length: 16,
+        include_symbols: true,
+        exclude_ambiguous: false,
+      });
+      setFormData((prev) => ({ ...prev, password: generated.password }));
+    } catch (_err) {
+      setError('Failed to generate password');
     }
-
-    // Shuffle the password
-    const shuffled = password
-      .split('')
-      .sort(() => Math.random() - 0.5)
-      .join('');
-
-    setFormData((prev) => ({ ...prev, password: shuffled }));
   };
 
   const handleSubmit = async (e: React.FormEvent) => {
@@ -270,7 +270,7 @@ export const PasswordForm: React.FC<PasswordFormProps> = ({
               </Button>
             </div>
 
-            {formData.password && (
+            {formData.password && passwordStrength && (
               <div className="space-y-2">
                 <div className="flex items-center gap-2">
                   <div className="h-2 flex-1 rounded-full bg-gray-200">
@@ -285,6 +285,22 @@ export const PasswordForm: React.FC<PasswordFormProps> = ({
                     {getStrengthText(passwordStrength.score)}
                   </span>
                 </div>
+                <p className="text-muted-foreground text-xs">
+                  Time to crack: {passwordStrength.crack_time_display}
+                </p>
+
+                {breached && (
+                  <p className="text-sm text-red-600">
+                    This password appears in a known data breach and will be
+                    flagged as compromised when saved. Choose a different one.
+                  </p>
+                )}
+
+                {passwordStrength.warning && (
+                  <p className="text-sm text-yellow-700">
+                    {passwordStrength.warning}
+                  </p>
+                )}
 
                 {passwordStrength.feedback.length > 0 && (
                   <div className="text-muted-foreground text-sm">
@@ -1,4 +1,4 @@
-import { Check, Copy, Edit, Eye, EyeOff, Plus, Trash2, View } from 'lucide-react';
+import { Check, Copy, Edit, Eye, EyeOff, Plus, ShieldAlert, Trash2, View } from 'lucide-react';
 import type React from 'react';
 import { useEffect, useState } from 'react';
 import { Button } from '@/components/ui/button';
@@ -18,7 +18,7 @@ import {
   TableHeader,
   TableRow,
 } from '@/components/ui/table';
-import { passwordAPI } from '@/lib/tauri';
+import { passwordAPI, usageAPI } from '@/lib/tauri';
 import { copyToClipboardWithClear } from '@/lib/clipboard';
 import { useSettings } from '@/contexts/SettingsContext';
 import type { PasswordEntry } from '@/types';
@@ -222,7 +222,18 @@ export const PasswordList: React.FC<PasswordListProps> = ({
                     onClick={() => onView?.(password)}
                   >
                     <TableCell className="font-medium">
-                      {password.website}
+                      <div className="flex items-center gap-2">
+                        <span>{password.website}</span>
+                        {password.compromised_at && (
+                          <span
+                            className="flex items-center gap-1 text-red-600 text-xs"
+                            title={password.compromised_reason ?? undefined}
+                          >
+                            <ShieldAlert className="h-3 w-3" />
+                            Compromised
+                          </span>
+                        )}
+                      </div>
                     </TableCell>
                     <TableCell>
                       <div className="flex items-center gap-2">
@@ -271,6 +282,9 @@ export const PasswordList: React.FC<PasswordListProps> = ({
                           onClick={(e) => {
                             e.stopPropagation();
                             handleCopy(password.password, 'Password', `password-${password.id}`);
+                            if (password.id) {
+                              usageAPI.recordUsage('entry', password.id).catch(console.error);
+                            }
                           }}
                           size="sm"
                           variant="ghost"
@@ -5,6 +5,7 @@ import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Label } from '@/components/ui/label';
 import { copyToClipboardWithClear } from '@/lib/clipboard';
+import { usageAPI } from '@/lib/tauri';
 import { useSettings } from '@/contexts/SettingsContext';
 import type { PasswordEntry } from '@/types';
 
@@ -153,7 +154,12 @@ export const PasswordViewer: React.FC<PasswordViewerProps> = ({
               )}
             </Button>
             <Button
-              onClick={() => handleCopy(password.password, 'Password', 'password')}
+              onClick={() => {
+                handleCopy(password.password, 'Password', 'password');
+                if (password.id) {
+                  usageAPI.recordUsage('entry', password.id).catch(console.error);
+                }
+              }}
               size="sm"
               variant="outline"
             >
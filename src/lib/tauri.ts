@@ -1,5 +1,47 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Note, PasswordEntry } from '@/types';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  Attachment,
+  AttachmentParent,
+  AutofillCandidate,
+  BreachedEntry,
+  EntryTemplate,
+  ExpiringEntry,
+  FolderRotation,
+  GeneratedPassword,
+  GeneratorOptions,
+  HealthExportFormat,
+  ItemKind,
+  ListOptions,
+  Note,
+  NoteListOptions,
+  NoteRevision,
+  NoteRevisionDiff,
+  Page,
+  PasskeyAssertion,
+  PasskeyAssertionRequest,
+  PasskeyCreationOptions,
+  PasskeyRegistration,
+  PasskeySummary,
+  PassphraseOptions,
+  PasswordEntry,
+  PasswordHistoryEntry,
+  PasswordStrength,
+  PasswordSummary,
+  PhishingWarning,
+  PwnedIndexInfo,
+  RecoveryCodeStatus,
+  RotationSession,
+  RotationSessionSummary,
+  SearchHit,
+  SearchHitKind,
+  TemplateImportSummary,
+  UsedItem,
+  VaultHealthReport,
+  VaultQueryResults,
+  VaultSettings,
+  WordlistInfo,
+} from '@/types';
 
 // Authentication commands
 export const authAPI = {
@@ -27,6 +69,11 @@ export const authAPI = {
     return await invoke('create_new_vault', { password });
   },
 
+  // Resolves to whether the vault is still unlocked; if not, it opens with the new password
+  async changeMasterPassword(currentPassword: string, newPassword: string): Promise<boolean> {
+    return await invoke('change_master_password', { currentPassword, newPassword });
+  },
+
   async getVaultDirectory(): Promise<string> {
     return await invoke('get_vault_directory');
   },
@@ -38,8 +85,16 @@ export const authAPI = {
 
 // Password management commands
 export const passwordAPI = {
-  async getPasswords(): Promise<PasswordEntry[]> {
-    return await invoke('get_passwords');
+  async getPasswords(kind?: ItemKind): Promise<PasswordEntry[]> {
+    return await invoke('get_passwords', { kind });
+  },
+
+  async listPasswords(options?: ListOptions): Promise<Page<PasswordSummary>> {
+    return await invoke('list_passwords', { options });
+  },
+
+  async revealPassword(id: number): Promise<string> {
+    return await invoke('reveal_password', { id });
   },
 
   async createPassword(entry: PasswordEntry): Promise<number> {
@@ -54,15 +109,96 @@ export const passwordAPI = {
     return await invoke('delete_password', { id });
   },
 
-  async searchPasswords(query: string): Promise<PasswordEntry[]> {
-    return await invoke('search_passwords', { query });
+  // The flag clears itself when the entry's password is changed
+  async markCompromised(id: number, reason?: string): Promise<void> {
+    return await invoke('mark_compromised', { id, reason });
+  },
+
+  async clearCompromised(id: number): Promise<void> {
+    return await invoke('clear_compromised', { id });
+  },
+
+  // Most recently flagged first
+  async listCompromised(): Promise<PasswordSummary[]> {
+    return await invoke('list_compromised');
+  },
+
+  // `fuzzy` ranks typo-tolerant matches by score, favorites and recency
+  async searchPasswords(query: string, kind?: ItemKind, fuzzy?: boolean): Promise<PasswordEntry[]> {
+    return await invoke('search_passwords', { query, kind, fuzzy });
+  },
+
+  // e.g. `tag:prod folder:clients user:admin weak: age>365d used>5 -trashed "exact phrase"`
+  async queryVault(query: string): Promise<VaultQueryResults> {
+    return await invoke('query_vault', { query });
+  },
+
+  async listPasswordHistory(entryId: number): Promise<PasswordHistoryEntry[]> {
+    return await invoke('list_password_history', { entryId });
+  },
+
+  async copyPasswordHistory(historyId: number, clearAfterSeconds?: number): Promise<void> {
+    return await invoke('copy_password_history', { historyId, clearAfterSeconds });
+  },
+
+  async findEntriesForUrl(url: string): Promise<PasswordEntry[]> {
+    return await invoke('find_entries_for_url', { url });
+  },
+
+  async getAutofillCandidates(url: string, fieldNames?: string[]): Promise<AutofillCandidate[]> {
+    return await invoke('get_autofill_candidates', { url, fieldNames });
+  },
+
+  async checkUrlLookalikes(url: string): Promise<PhishingWarning[]> {
+    return await invoke('check_url_lookalikes', { url });
+  },
+};
+
+// Vault-wide search
+export const searchAPI = {
+  async searchVault(query: string, limit?: number, fuzzy?: boolean): Promise<SearchHit[]> {
+    return await invoke('search_vault', { query, limit, fuzzy });
+  },
+};
+
+// Usage tracking for sorting and quick access
+export const usageAPI = {
+  // Call when a password is copied or a note is opened; `revealPassword` counts on its own
+  async recordUsage(kind: SearchHitKind, id: number): Promise<void> {
+    return await invoke('record_usage', { kind, id });
+  },
+
+  // Most used first; defaults to 10, at most 100
+  async frequentlyUsed(limit?: number): Promise<UsedItem[]> {
+    return await invoke('frequently_used', { limit });
+  },
+};
+
+// Password generation and strength commands
+export const generatorAPI = {
+  async generatePassword(options?: GeneratorOptions): Promise<GeneratedPassword> {
+    return await invoke('generate_password', { options });
+  },
+
+  async generatePassphrase(options?: PassphraseOptions): Promise<GeneratedPassword> {
+    return await invoke('generate_passphrase', { options });
+  },
+
+  // Opens a file picker; resolves to null if the user cancels
+  async pickWordlist(): Promise<WordlistInfo | null> {
+    return await invoke('pick_wordlist');
+  },
+
+  // `userInputs` are details the password shouldn't contain, e.g. the site and username
+  async estimatePasswordStrength(password: string, userInputs?: string[]): Promise<PasswordStrength> {
+    return await invoke('estimate_password_strength', { password, userInputs });
   },
 };
 
 // Notes management commands
 export const notesAPI = {
-  async getNotes(): Promise<Note[]> {
-    return await invoke('get_notes');
+  async getNotes(options?: NoteListOptions): Promise<Note[]> {
+    return await invoke('get_notes', { options });
   },
 
   async createNote(note: Note): Promise<number> {
@@ -76,4 +212,234 @@ export const notesAPI = {
   async deleteNote(id: number): Promise<void> {
     return await invoke('delete_note', { id });
   },
+
+  async listNoteRevisions(noteId: number): Promise<NoteRevision[]> {
+    return await invoke('list_note_revisions', { noteId });
+  },
+
+  async diffNoteRevisions(noteId: number, fromRevision: number, toRevision: number): Promise<NoteRevisionDiff> {
+    return await invoke('diff_note_revisions', { noteId, fromRevision, toRevision });
+  },
+
+  async restoreNoteRevision(noteId: number, revision: number): Promise<number> {
+    return await invoke('restore_note_revision', { noteId, revision });
+  },
+};
+
+// Attachment commands
+export const attachmentAPI = {
+  // Opens a file picker; resolves to null if the user cancels
+  async addAttachment(parent: AttachmentParent): Promise<Attachment | null> {
+    return await invoke('add_attachment', { parent });
+  },
+
+  async listAttachments(parent: AttachmentParent): Promise<Attachment[]> {
+    return await invoke('list_attachments', { parent });
+  },
+
+  // Opens a save dialog; resolves to the saved path, or null if the user cancels
+  async exportAttachment(id: number): Promise<string | null> {
+    return await invoke('export_attachment', { id });
+  },
+
+  async verifyAttachment(id: number): Promise<boolean> {
+    return await invoke('verify_attachment', { id });
+  },
+
+  async deleteAttachment(id: number): Promise<void> {
+    return await invoke('delete_attachment', { id });
+  },
+};
+
+// Entry template commands
+export const templateAPI = {
+  async getTemplates(): Promise<EntryTemplate[]> {
+    return await invoke('get_templates');
+  },
+
+  async createTemplate(template: EntryTemplate): Promise<number> {
+    return await invoke('create_template', { template });
+  },
+
+  async updateTemplate(id: number, template: EntryTemplate): Promise<void> {
+    return await invoke('update_template', { id, template });
+  },
+
+  async deleteTemplate(id: number): Promise<void> {
+    return await invoke('delete_template', { id });
+  },
+
+  async newEntryFromTemplate(templateId: number): Promise<PasswordEntry> {
+    return await invoke('new_entry_from_template', { templateId });
+  },
+
+  // Opens a save dialog; resolves to the saved path, or null if the user cancels
+  async exportTemplates(ids?: number[]): Promise<string | null> {
+    return await invoke('export_templates', { ids });
+  },
+
+  // Opens a file picker; resolves to null if the user cancels
+  async importTemplates(): Promise<TemplateImportSummary | null> {
+    return await invoke('import_templates');
+  },
+};
+
+// Vault settings commands
+export const settingsAPI = {
+  async getVaultSettings(): Promise<VaultSettings> {
+    return await invoke('get_vault_settings');
+  },
+
+  async updateVaultSettings(settings: VaultSettings): Promise<void> {
+    return await invoke('update_vault_settings', { settings });
+  },
+};
+
+// Vault health commands
+export const healthAPI = {
+  // maxAgeDays defaults to 365 on the Rust side
+  async vaultHealthReport(maxAgeDays?: number): Promise<VaultHealthReport> {
+    return await invoke('vault_health_report', { maxAgeDays });
+  },
+
+  // Opens a save dialog; resolves to the saved path, or null if the user cancels
+  async exportVaultHealthReport(format: HealthExportFormat, maxAgeDays?: number): Promise<string | null> {
+    return await invoke('export_vault_health_report', { format, maxAgeDays });
+  },
+
+  // Opens a picker for a Have I Been Pwned SHA-1 dump: a folder of range files when
+  // fromFolder is set, otherwise the single ordered-by-hash file. Emits
+  // 'pwned-import-progress' events; resolves to null if the user cancels
+  async importPwnedPasswords(fromFolder?: boolean): Promise<PwnedIndexInfo | null> {
+    return await invoke('import_pwned_passwords', { fromFolder });
+  },
+
+  async getPwnedIndexInfo(): Promise<PwnedIndexInfo | null> {
+    return await invoke('get_pwned_index_info');
+  },
+
+  // Entries not already flagged are marked compromised
+  async checkBreachedPasswords(): Promise<BreachedEntry[]> {
+    return await invoke('check_breached_passwords');
+  },
+
+  async isPasswordBreached(password: string): Promise<boolean> {
+    return await invoke('is_password_breached', { password });
+  },
+};
+
+// Password expiry and rotation commands
+export const expiryAPI = {
+  // withinDays defaults to the vault's expiry_warning_days setting
+  async listExpiringEntries(withinDays?: number): Promise<ExpiringEntry[]> {
+    return await invoke('list_expiring_entries', { withinDays });
+  },
+
+  async listExpiredEntries(): Promise<ExpiringEntry[]> {
+    return await invoke('list_expired_entries');
+  },
+
+  async getFolderRotations(): Promise<FolderRotation[]> {
+    return await invoke('get_folder_rotations');
+  },
+
+  // Pass null to clear the folder's interval
+  async setFolderRotation(folder: string, rotationDays: number | null): Promise<void> {
+    return await invoke('set_folder_rotation', { folder, rotationDays });
+  },
+
+  // The vault checks after unlock and every few hours while it stays open
+  async onEntriesExpiring(handler: (entries: ExpiringEntry[]) => void): Promise<UnlistenFn> {
+    return await listen<ExpiringEntry[]>('entries-expiring', (event) => handler(event.payload));
+  },
+
+  async onEntriesExpired(handler: (entries: ExpiringEntry[]) => void): Promise<UnlistenFn> {
+    return await listen<ExpiringEntry[]>('entries-expired', (event) => handler(event.payload));
+  },
+};
+
+// Two-factor recovery codes, stored as 'recovery_codes' items
+export const recoveryCodeAPI = {
+  // Matches ignoring case, spaces and dashes; rejects codes that were already used
+  async consumeRecoveryCode(itemId: number, code: string): Promise<RecoveryCodeStatus> {
+    return await invoke('consume_recovery_code', { itemId, code });
+  },
+
+  async getRecoveryCodeStatus(itemId: number): Promise<RecoveryCodeStatus> {
+    return await invoke('get_recovery_code_status', { itemId });
+  },
+
+  async listRecoveryCodesForEntry(entryId: number): Promise<RecoveryCodeStatus[]> {
+    return await invoke('list_recovery_codes_for_entry', { entryId });
+  },
+
+  // Items below the vault's recovery_code_warning_threshold
+  async listLowRecoveryCodes(): Promise<RecoveryCodeStatus[]> {
+    return await invoke('list_low_recovery_codes');
+  },
+
+  // Extracts codes from text pasted from a provider's download or settings page
+  async parseRecoveryCodes(text: string): Promise<string[]> {
+    return await invoke('parse_recovery_codes', { text });
+  },
+};
+
+// Software WebAuthn authenticator for 'passkey' items; binary values are base64url
+export const passkeyAPI = {
+  // Saves a new passkey and returns the response for navigator.credentials.create()
+  async createPasskey(options: PasskeyCreationOptions): Promise<PasskeyRegistration> {
+    return await invoke('create_passkey', { options });
+  },
+
+  // Never includes private keys
+  async listPasskeys(rpId?: string): Promise<PasskeySummary[]> {
+    return await invoke('list_passkeys', { rpId });
+  },
+
+  // Signs for navigator.credentials.get() and bumps the passkey's signature counter
+  async getPasskeyAssertion(request: PasskeyAssertionRequest): Promise<PasskeyAssertion> {
+    return await invoke('get_passkey_assertion', { request });
+  },
+};
+
+// Bulk password rotation commands
+export const rotationAPI = {
+  // Unset options take the generator defaults
+  async startRotationSession(
+    entryIds: number[],
+    options?: GeneratorOptions,
+    name?: string,
+  ): Promise<RotationSession> {
+    return await invoke('start_rotation_session', { entryIds, options, name });
+  },
+
+  async listRotationSessions(): Promise<RotationSessionSummary[]> {
+    return await invoke('list_rotation_sessions');
+  },
+
+  async getRotationSession(sessionId: number): Promise<RotationSession> {
+    return await invoke('get_rotation_session', { sessionId });
+  },
+
+  async revealPendingPassword(sessionId: number, entryId: number): Promise<string> {
+    return await invoke('reveal_pending_password', { sessionId, entryId });
+  },
+
+  async regeneratePendingPassword(sessionId: number, entryId: number): Promise<void> {
+    return await invoke('regenerate_pending_password', { sessionId, entryId });
+  },
+
+  // The site accepted the new password; the old one moves to history
+  async confirmRotation(sessionId: number, entryId: number): Promise<RotationSession> {
+    return await invoke('confirm_rotation', { sessionId, entryId });
+  },
+
+  // The site rejected the new password; the entry keeps its current one
+  async rollBackRotation(sessionId: number, entryId: number): Promise<RotationSession> {
+    return await invoke('roll_back_rotation', { sessionId, entryId });
+  },
+
+  async cancelRotationSession(sessionId: number): Promise<RotationSession> {
+    return await invoke('cancel_rotation_session', { sessionId });
+  },
 };
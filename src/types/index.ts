@@ -1,19 +1,350 @@
 export type PasswordEntry = {
   id?: number;
-  website: string;
+  website: string; // display title for non-login items
   username: string;
   password: string;
   notes?: string;
   created_at?: string;
   updated_at?: string;
+  details?: ItemDetails | null; // absent for plain website logins
+  template_id?: number | null;
+  custom_fields?: CustomField[];
+  uris?: EntryUri[]; // derived from `website` when left empty
+  folder?: string | null; // nested folders are separated by `/`
+  tags?: string[];
+  favorite?: boolean;
+  last_used_at?: string | null; // set when the password is revealed or copied
+  use_count?: number; // read-only; counted by `recordUsage`
+  expires_on?: string | null; // YYYY-MM-DD; takes precedence over rotation intervals
+  rotation_days?: number | null; // overrides the folder's interval
+  compromised_at?: string | null; // read-only; set with `markCompromised`, cleared when the password changes
+  compromised_reason?: string | null;
+};
+
+// An entry without its secrets; fetch the password with `revealPassword`
+export type PasswordSummary = {
+  id: number;
+  website: string;
+  username: string;
+  kind: ItemKind;
+  uris: EntryUri[];
+  folder: string | null;
+  tags: string[];
+  favorite: boolean;
+  created_at?: string;
+  updated_at?: string;
+  last_used_at?: string | null;
+  use_count: number;
+  compromised_at?: string | null;
+  compromised_reason?: string | null;
+};
+
+export type EntrySort = 'name' | 'created' | 'updated' | 'last_used' | 'most_used';
+
+export type ListOptions = {
+  kind?: ItemKind;
+  sort?: EntrySort; // defaults to 'created', newest first
+  reverse?: boolean;
+  offset?: number;
+  limit?: number; // defaults to 100, at most 1000
+  favorites_only?: boolean;
+  compromised_only?: boolean;
+};
+
+// 'name' sorts notes by title
+export type NoteListOptions = {
+  sort?: EntrySort;
+  reverse?: boolean;
+  favorites_only?: boolean;
+};
+
+export type Page<T> = {
+  items: T[];
+  total: number;
+  offset: number;
+  limit: number;
+};
+
+export type SearchHitKind = 'entry' | 'note';
+
+export type SnippetSegment = {
+  text: string;
+  highlight: boolean;
+};
+
+export type SearchHit = {
+  kind: SearchHitKind;
+  id: number;
+  title: string;
+  username?: string; // entries only
+  snippet: SnippetSegment[];
+  score: number; // higher is more relevant
 };
 
+export type UsedItem = {
+  kind: SearchHitKind;
+  id: number;
+  title: string; // website for entries, title for notes
+  username?: string; // entries only
+  favorite: boolean;
+  use_count: number;
+  last_used_at?: string | null;
+};
+
+export type UriMatchMode = 'base_domain' | 'host' | 'starts_with' | 'regex' | 'never';
+
+export type EntryUri = {
+  uri: string;
+  match_mode: UriMatchMode;
+};
+
+export type UriMatchQuality = 'exact_url' | 'app' | 'host' | 'regex' | 'subdomain' | 'domain';
+
+export type AutofillCandidate = {
+  entry_id: number;
+  title: string;
+  username: string;
+  matched_uri: string;
+  match_quality: UriMatchQuality;
+  insecure: boolean; // saved for https, page is http
+  last_used_at?: string;
+  updated_at?: string;
+};
+
+export type LookalikeKind =
+  | 'homoglyph'
+  | 'typo'
+  | 'different_suffix'
+  | 'subdomain_trick'
+  | 'embedded_name'
+  | 'mixed_script';
+
+export type WarningSeverity = 'low' | 'medium' | 'high';
+
+export type PhishingWarning = {
+  kind: LookalikeKind;
+  severity: WarningSeverity;
+  requested_domain: string;
+  similar_to?: string;
+  entry_ids: number[];
+  message: string;
+};
+
+export type CustomField = {
+  name: string;
+  value: string;
+  concealed: boolean;
+};
+
+export type FieldType = 'text' | 'multiline' | 'concealed' | 'url' | 'email' | 'number' | 'date' | 'boolean';
+
+export type TemplateField = {
+  name: string;
+  field_type: FieldType;
+  required: boolean;
+};
+
+export type EntryTemplate = {
+  id?: number;
+  name: string;
+  description?: string;
+  fields: TemplateField[];
+  created_at?: string;
+  updated_at?: string;
+};
+
+export type TemplateImportSummary = {
+  imported: string[];
+  skipped: string[];
+};
+
+export type ItemKind =
+  | 'login'
+  | 'payment_card'
+  | 'identity'
+  | 'ssh_key'
+  | 'api_credential'
+  | 'wifi_network'
+  | 'software_license'
+  | 'recovery_codes'
+  | 'passkey';
+
+export type PaymentCard = {
+  cardholder_name: string;
+  number: string;
+  brand?: string;
+  expiry_month: number;
+  expiry_year: number;
+  security_code?: string;
+  pin?: string;
+};
+
+export type Identity = {
+  title?: string;
+  first_name: string;
+  middle_name?: string;
+  last_name: string;
+  email?: string;
+  phone?: string;
+  date_of_birth?: string;
+  address_line1?: string;
+  address_line2?: string;
+  city?: string;
+  region?: string;
+  postal_code?: string;
+  country?: string;
+  passport_number?: string;
+  national_id?: string;
+};
+
+export type SshKey = {
+  private_key?: string;
+  public_key?: string;
+  passphrase?: string;
+  key_type?: string;
+  fingerprint?: string;
+  comment?: string;
+};
+
+export type ApiCredential = {
+  key_id?: string;
+  secret: string;
+  endpoint?: string;
+  scopes: string[];
+  expires_on?: string;
+};
+
+export type WifiSecurity = 'open' | 'wep' | 'wpa_personal' | 'wpa2_personal' | 'wpa3_personal' | 'enterprise';
+
+export type WifiNetwork = {
+  ssid: string;
+  security: WifiSecurity;
+  passphrase?: string;
+  hidden: boolean;
+};
+
+export type SoftwareLicense = {
+  product: string;
+  version?: string;
+  license_key: string;
+  licensed_to?: string;
+  email?: string;
+  purchase_date?: string;
+  expires_on?: string;
+  seats?: number;
+};
+
+export type RecoveryCode = {
+  code: string;
+  used: boolean;
+  used_at?: string | null; // UTC, "YYYY-MM-DD HH:MM:SS"
+};
+
+// Two-factor backup codes for the account of a login entry
+export type RecoveryCodes = {
+  entry_id?: number | null; // the login the codes belong to
+  codes: RecoveryCode[];
+};
+
+export type PasskeyAlgorithm = 'es256' | 'eddsa';
+
+// A WebAuthn credential; binary values are base64url without padding
+export type Passkey = {
+  credential_id: string;
+  rp_id: string;
+  rp_name?: string;
+  user_handle: string;
+  user_name?: string;
+  user_display_name?: string;
+  algorithm: PasskeyAlgorithm;
+  private_key: string; // PKCS#8
+  sign_count: number;
+};
+
+export type ItemDetails =
+  | ({ kind: 'payment_card' } & PaymentCard)
+  | ({ kind: 'identity' } & Identity)
+  | ({ kind: 'ssh_key' } & SshKey)
+  | ({ kind: 'api_credential' } & ApiCredential)
+  | ({ kind: 'wifi_network' } & WifiNetwork)
+  | ({ kind: 'software_license' } & SoftwareLicense)
+  | ({ kind: 'recovery_codes' } & RecoveryCodes)
+  | ({ kind: 'passkey' } & Passkey);
+
 export type Note = {
   id?: number;
   title: string;
   content: string;
   created_at?: string;
   updated_at?: string;
+  folder?: string | null;
+  tags?: string[];
+  favorite?: boolean;
+  last_used_at?: string | null; // set when the note is opened or copied
+  use_count?: number; // read-only; counted by `recordUsage`
+};
+
+export type VaultQueryResults = {
+  entries: PasswordEntry[];
+  notes: Note[];
+};
+
+export type NoteRevision = {
+  id: number;
+  note_id: number;
+  revision: number;
+  title: string;
+  content: string;
+  created_at?: string;
+};
+
+export type DiffLine = {
+  kind: 'unchanged' | 'added' | 'removed';
+  old_line?: number;
+  new_line?: number;
+  text: string;
+};
+
+export type NoteRevisionDiff = {
+  note_id: number;
+  from_revision: number;
+  to_revision: number;
+  old_title: string;
+  new_title: string;
+  lines: DiffLine[];
+};
+
+export type AttachmentParent = { kind: 'entry'; id: number } | { kind: 'note'; id: number };
+
+export type Attachment = {
+  id: number;
+  parent: AttachmentParent;
+  file_name: string;
+  mime_type: string;
+  size: number;
+  sha256: string;
+  created_at?: string;
+};
+
+export type PasswordHistoryEntry = {
+  id: number;
+  entry_id: number;
+  field_name: string;
+  changed_at?: string;
+};
+
+export type VaultSettings = {
+  password_history_depth: number;
+  attachment_max_bytes: number;
+  master_password_policy: MasterPasswordPolicy;
+  expiry_warning_days: number;
+  recovery_code_warning_threshold: number; // warn when fewer unused codes than this are left
+};
+
+export type MasterPasswordPolicy = {
+  min_entropy_bits: number;
+  block_common_passwords: boolean;
+  forbid_entry_reuse: boolean;
 };
 
 export type AuthState = {
@@ -31,13 +362,240 @@ export type AppState = {
 export type PasswordStrength = {
   score: number; // 0-4
   feedback: string[];
-  warning?: string;
+  warning?: string | null;
+  guesses_log10: number;
+  crack_time_seconds: number; // offline attack on a slow hash, 10k guesses per second
+  crack_time_display: string;
 };
 
+export type RepeatRule = 'allow' | 'no_adjacent' | 'never';
+
+// Omitted fields take the Rust defaults: 20 characters, letters and numbers, at least one of each
 export type GeneratorOptions = {
-  include_uppercase: boolean;
-  include_lowercase: boolean;
-  include_numbers: boolean;
-  include_symbols: boolean;
-  exclude_ambiguous: boolean;
+  length?: number;
+  include_uppercase?: boolean;
+  include_lowercase?: boolean;
+  include_numbers?: boolean;
+  include_symbols?: boolean;
+  exclude_ambiguous?: boolean;
+  min_uppercase?: number;
+  min_lowercase?: number;
+  min_numbers?: number;
+  min_symbols?: number;
+  include_chars?: string;
+  exclude_chars?: string;
+  repeats?: RepeatRule;
+};
+
+export type WordlistSource =
+  | { kind: 'bip39_english' } // embedded, 2048 words
+  | { kind: 'custom'; path: string }; // one word per line, or the EFF `11111<TAB>word` format
+
+export type WordCase = 'lower' | 'capitalized' | 'upper';
+
+// Omitted fields take the Rust defaults: seven lowercase BIP-39 words joined by `-`
+export type PassphraseOptions = {
+  word_count?: number;
+  separator?: string;
+  case?: WordCase;
+  include_number?: boolean;
+  include_symbol?: boolean;
+  wordlist?: WordlistSource;
+};
+
+export type WordlistInfo = {
+  path: string;
+  word_count: number;
+  bits_per_word: number;
+};
+
+export type GeneratedPassword = {
+  password: string;
+  entropy_bits: number;
+};
+
+export type HealthIssueKind =
+  | 'no_password'
+  | 'compromised'
+  | 'reused'
+  | 'weak'
+  | 'old'
+  | 'insecure_url'
+  | 'duplicate';
+
+export type HealthIssue = {
+  kind: HealthIssueKind;
+  detail: string;
+  remediation: string;
+};
+
+export type HealthReportItem = {
+  entry_id: number;
+  website: string;
+  username: string;
+  kind: ItemKind;
+  password_age_days?: number;
+  issues: HealthIssue[];
+};
+
+export type ReusedPasswordGroup = {
+  password_sha256: string;
+  entry_ids: number[];
+};
+
+export type HealthCounts = {
+  no_password: number;
+  compromised: number;
+  reused: number;
+  weak: number;
+  old: number;
+  insecure_url: number;
+  duplicate: number;
+};
+
+export type VaultHealthReport = {
+  score: number; // 0-100, higher is healthier
+  generated_at: string; // UTC, "YYYY-MM-DD HH:MM"
+  total_entries: number;
+  max_password_age_days: number;
+  counts: HealthCounts;
+  reused_groups: ReusedPasswordGroup[];
+  duplicate_groups: number[][];
+  items: HealthReportItem[];
+};
+
+export type HealthExportFormat = 'html' | 'csv';
+
+export type PwnedIndexInfo = {
+  path: string;
+  hash_count: number;
+};
+
+export type BreachedEntry = {
+  entry_id: number;
+  website: string;
+  username: string;
+};
+
+export type ExpirySource = 'date' | 'entry' | 'folder';
+
+export type ExpiringEntry = {
+  entry_id: number;
+  website: string;
+  username: string;
+  kind: ItemKind;
+  folder: string | null;
+  due_at: string; // UTC, "YYYY-MM-DD HH:MM:SS"
+  days_left: number; // negative once overdue
+  source: ExpirySource;
+};
+
+export type FolderRotation = {
+  folder: string;
+  rotation_days: number;
+};
+
+export type RotationStatus = 'active' | 'completed' | 'cancelled';
+
+export type RotationItemStatus = 'pending' | 'promoted' | 'rolled_back';
+
+// Fetch the staged password with `revealPendingPassword`
+export type RotationItem = {
+  entry_id: number;
+  website: string;
+  username: string;
+  status: RotationItemStatus;
+  updated_at?: string | null;
+};
+
+export type RotationLogEntry = {
+  id: number;
+  entry_id: number | null;
+  message: string;
+  created_at?: string | null;
+};
+
+export type RotationSession = {
+  id: number;
+  name: string | null;
+  status: RotationStatus;
+  generator_options: GeneratorOptions;
+  created_at?: string | null;
+  finished_at?: string | null;
+  items: RotationItem[];
+  log: RotationLogEntry[];
+};
+
+export type RotationSessionSummary = {
+  id: number;
+  name: string | null;
+  status: RotationStatus;
+  created_at?: string | null;
+  finished_at?: string | null;
+  total: number;
+  pending: number;
+  promoted: number;
+  rolled_back: number;
+};
+
+export type RecoveryCodeStatus = {
+  item_id: number;
+  title: string;
+  entry_id?: number | null;
+  total: number;
+  remaining: number;
+  low: boolean; // fewer than the vault's warning threshold are left
+};
+
+// A site's navigator.credentials.create() request, as passed on by a browser bridge
+export type PasskeyCreationOptions = {
+  rp_id: string;
+  rp_name?: string;
+  user_handle: string;
+  user_name?: string;
+  user_display_name?: string;
+  algorithms?: number[]; // COSE ids from pubKeyCredParams, most preferred first
+  exclude_credentials?: string[];
+  client_data_hash: string; // SHA-256 of the clientDataJSON
+  user_verified?: boolean;
+  folder?: string;
+};
+
+export type PasskeyRegistration = {
+  item_id?: number | null;
+  credential_id: string;
+  public_key_algorithm: number;
+  public_key: string; // SubjectPublicKeyInfo DER, as from getPublicKey()
+  authenticator_data: string;
+  attestation_object: string;
+};
+
+// A site's navigator.credentials.get() request
+export type PasskeyAssertionRequest = {
+  rp_id: string;
+  client_data_hash: string;
+  allow_credentials?: string[];
+  item_id?: number; // the passkey the user picked, when several match
+  user_verified?: boolean;
+};
+
+export type PasskeyAssertion = {
+  item_id?: number | null;
+  credential_id: string;
+  authenticator_data: string;
+  signature: string;
+  user_handle: string;
+};
+
+export type PasskeySummary = {
+  item_id: number;
+  title: string;
+  credential_id: string;
+  rp_id: string;
+  user_handle: string;
+  user_name?: string | null;
+  user_display_name?: string | null;
+  algorithm: PasskeyAlgorithm;
+  sign_count: number;
+  last_used_at?: string | null;
 };